@@ -0,0 +1,42 @@
+use std::time::{Duration, Instant};
+
+use jni::JavaVM;
+use typed_jni::{define_java_class, Context, LocalClass, LocalObject};
+
+define_java_class!(JavaInteger, "java.lang.Integer");
+
+const ITERATIONS: u32 = 100_000;
+
+fn bench(name: &str, f: impl Fn(i32)) {
+    let begin = Instant::now();
+
+    for i in 0..ITERATIONS {
+        f((i % 256) as i32 - 128);
+    }
+
+    let elapsed = begin.elapsed();
+
+    println!(
+        "{:<24} {:>10.2?} total {:>10.2?}/iter",
+        name,
+        elapsed,
+        Duration::from_nanos((elapsed.as_nanos() / ITERATIONS as u128) as u64)
+    );
+}
+
+fn main() {
+    let vm = JavaVM::new(jni::InitArgsBuilder::new().build().unwrap()).unwrap();
+    let env = vm.attach_current_thread().unwrap();
+
+    typed_jni::attach_vm(vm.get_java_vm_pointer() as _).unwrap();
+
+    let ctx = unsafe { Context::from_raw(env.get_raw() as _) };
+
+    bench("box_int", |i| drop(ctx.box_int(i).unwrap()));
+    bench("call_method valueOf", |i| {
+        let c_integer = LocalClass::<JavaInteger>::find_class(ctx).unwrap();
+        let o_integer: LocalObject<JavaInteger> = c_integer.call_method(ctx, "valueOf", i).unwrap();
+
+        drop(o_integer)
+    });
+}
@@ -0,0 +1,36 @@
+use std::time::{Duration, Instant};
+
+use jni::JavaVM;
+use typed_jni::{Context, JString, LocalObject};
+
+const ITERATIONS: u32 = 100_000;
+
+fn bench(name: &str, ctx: &Context, s: &str) {
+    let begin = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        drop(LocalObject::<JString>::new_string(ctx, s));
+    }
+
+    let elapsed = begin.elapsed();
+
+    println!(
+        "{:<24} {:>10.2?} total {:>10.2?}/iter",
+        name,
+        elapsed,
+        Duration::from_nanos((elapsed.as_nanos() / ITERATIONS as u128) as u64)
+    );
+}
+
+fn main() {
+    let vm = JavaVM::new(jni::InitArgsBuilder::new().build().unwrap()).unwrap();
+    let env = vm.attach_current_thread().unwrap();
+
+    typed_jni::attach_vm(vm.get_java_vm_pointer() as _).unwrap();
+
+    let ctx = unsafe { Context::from_raw(env.get_raw() as _) };
+
+    bench("short ascii", ctx, "hello world");
+    bench("short non-ascii", ctx, "héllo wörld");
+    bench("long ascii", ctx, &"hello world ".repeat(32));
+}
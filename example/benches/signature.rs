@@ -0,0 +1,55 @@
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use typed_jni::{MethodSignature, Signature};
+
+const ITERATIONS: u32 = 1_000_000;
+
+const STRING: Signature = Signature::Object("java/lang/String");
+
+const WIDE: MethodSignature = MethodSignature {
+    args: &[
+        Signature::Int,
+        Signature::Long,
+        Signature::Boolean,
+        Signature::Double,
+        STRING,
+        Signature::Array(&Signature::Byte),
+        Signature::Array(&STRING),
+        Signature::Object("java/util/List"),
+        Signature::Char,
+        Signature::Short,
+        Signature::Float,
+        Signature::Array(&Signature::Array(&Signature::Int)),
+    ],
+    ret: STRING,
+};
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    let begin = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        f();
+    }
+
+    let elapsed = begin.elapsed();
+
+    println!(
+        "{:<24} {:>10.2?} total {:>10.2?}/iter",
+        name,
+        elapsed,
+        Duration::from_nanos((elapsed.as_nanos() / ITERATIONS as u128) as u64)
+    );
+}
+
+fn main() {
+    bench("to_string", || drop(std::hint::black_box(WIDE).to_string()));
+
+    let mut buf = String::new();
+    bench("write reused buffer", || {
+        buf.clear();
+        write!(buf, "{}", std::hint::black_box(WIDE)).unwrap();
+    });
+}
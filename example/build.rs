@@ -0,0 +1,29 @@
+use std::{path::PathBuf, process::Command};
+
+fn java_home() -> Option<PathBuf> {
+    if let Ok(home) = std::env::var("JAVA_HOME") {
+        return Some(PathBuf::from(home));
+    }
+
+    let output = Command::new("java")
+        .args(["-XshowSettings:properties", "-version"])
+        .output()
+        .ok()?;
+
+    String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("java.home = ").map(PathBuf::from))
+}
+
+fn main() {
+    // integration tests create the vm through typed-jni and need libjvm at link time
+    if let Some(home) = java_home() {
+        let server = home.join("lib").join("server");
+
+        println!("cargo:rustc-link-arg-tests=-L{}", server.display());
+        println!("cargo:rustc-link-arg-tests=-Wl,-rpath,{}", server.display());
+        println!("cargo:rustc-link-arg-tests=-ljvm");
+    }
+
+    println!("cargo:rerun-if-env-changed=JAVA_HOME");
+}
@@ -1,7 +1,6 @@
 use typed_jni::{
-    define_java_class,
-    sys::{jint, JavaVM, JNI_VERSION_1_6},
-    Context, JString, TrampolineClass, TrampolineObject,
+    prelude::*,
+    sys::{jint, JavaVM, JNI_ERR, JNI_VERSION_1_6},
 };
 
 #[cfg(test)]
@@ -24,7 +23,9 @@ pub extern "C" fn Java_com_github_kr328_typedjni_Example_nativeFunction<'ctx>(
 
 #[unsafe(no_mangle)]
 pub extern "C" fn JNI_OnLoad(vm: *mut JavaVM, _: *const ()) -> jint {
-    typed_jni::attach_vm(vm);
+    if typed_jni::attach_vm(vm).is_err() {
+        return JNI_ERR;
+    }
 
     JNI_VERSION_1_6 as _
 }
@@ -2,7 +2,7 @@ use std::{path::Path, process::Stdio};
 
 use example::JavaExample;
 use jni::JavaVM;
-use typed_jni::{define_java_class, Context, JString, LocalClass, LocalObject, NoArgs, Type};
+use typed_jni::prelude::*;
 
 define_java_class!(JavaSystem, "java.lang.System");
 define_java_class!(JavaPrintStream, "java.io.PrintStream");
@@ -43,7 +43,7 @@ fn main() {
     )
     .unwrap();
 
-    typed_jni::attach_vm(vm.get_java_vm_pointer() as _);
+    typed_jni::attach_vm(vm.get_java_vm_pointer() as _).unwrap();
 
     let env = vm.attach_current_thread().unwrap();
     let ctx = unsafe { Context::from_raw(env.get_raw() as _) };
@@ -58,26 +58,26 @@ fn main() {
         drop(c_str);
     }
 
-    let c_system = LocalClass::<JavaSystem>::find_class(&ctx).unwrap();
-    let o_out: LocalObject<JavaPrintStream> = c_system.get_field(&ctx, "out").unwrap();
+    let c_system = LocalClass::<JavaSystem>::find_class(ctx).unwrap();
+    let o_out: LocalObject<JavaPrintStream> = c_system.get_field(ctx, "out").unwrap();
 
     o_out
-        .call_method::<(), _>(&ctx, "println", &LocalObject::<JString>::new_string(&ctx, "Hello World!"))
+        .call_method::<(), _>(ctx, "println", &LocalObject::<JString>::new_string(ctx, "Hello World!"))
         .unwrap();
 
     o_out
-        .call_method::<(), _>(&ctx, "println", &LocalObject::<JString>::new_string(&ctx, "Hello World!!!!"))
+        .call_method::<(), _>(ctx, "println", &LocalObject::<JString>::new_string(ctx, "Hello World!!!!"))
         .unwrap();
 
     o_out
         .call_method::<(), _>(
-            &ctx,
+            ctx,
             "println",
-            &LocalObject::<JString>::new_string(&ctx, "Hello World!!!!!!!!!"),
+            &LocalObject::<JString>::new_string(ctx, "Hello World!!!!!!!!!"),
         )
         .unwrap();
 
     let v = LocalClass::<JavaExample>::find_class(ctx).unwrap();
 
-    let _: () = v.call_method(&ctx, "run", NoArgs).unwrap();
+    let _: () = v.call_method(ctx, "run", NoArgs).unwrap();
 }
@@ -1,9 +1,12 @@
-use std::{process::Stdio, ptr, sync::OnceLock};
+use std::{cell::RefCell, process::Stdio, ptr, sync::OnceLock};
 
 use jni::JavaVM;
 use typed_jni::{
-    define_java_class, Array, AsRaw, Class, Context, JString, LocalClass, LocalObject, NoArgs, Object, TrampolineClass,
-    TrampolineObject,
+    define_java_class, java_bindings, Array, AsDirectBuffer, AsRaw, CallArg, Class, ClassCache, Context, Error, Extends,
+    FieldLayout, FieldReader, FromRaw, IndexOutOfBounds, IntoRaw, JAtomicInteger, JAtomicLong, JBooleanArray, JByteArray,
+    JCharSequence, JClass, JClassLoader, JCompletableFuture, JDuration, JFuture, JInputStream, JIntArray, JIterator, JList,
+    JNumber, JObject, JOutputStream, JRunnable, JRuntime, JSet, JString, JThread, Jchar, Local, LocalClass, LocalObject, NoArgs,
+    Null, Object, StrongRef, Throwable, TrampolineClass, TrampolineObject, TrampolineReturn, VarArgs, VmError, WeakClass,
 };
 
 fn with_java_vm<R, F: FnOnce(&Context) -> R>(f: F) -> R {
@@ -11,7 +14,7 @@ fn with_java_vm<R, F: FnOnce(&Context) -> R>(f: F) -> R {
     let vm = VM.get_or_init(|| JavaVM::new(jni::InitArgsBuilder::new().build().unwrap()).unwrap());
     let env = vm.attach_current_thread().unwrap();
 
-    typed_jni::attach_vm(vm.get_java_vm_pointer() as _);
+    typed_jni::attach_vm(vm.get_java_vm_pointer() as _).unwrap();
 
     // temp workaround for jni crate not match jni-sys
     f(unsafe { Context::from_raw(env.get_raw() as _) })
@@ -92,6 +95,7 @@ fn test_bool_array() {
     })
 }
 
+define_java_class!(JavaObject, "java.lang.Object");
 define_java_class!(JavaFile, "java.io.File");
 define_java_class!(JavaURI, "java.net.URI");
 define_java_class!(JavaURL, "java.net.URL");
@@ -173,6 +177,7 @@ fn test_inner_class() {
 }
 
 #[test]
+#[allow(clippy::let_and_return)]
 fn test_register_native() {
     with_java_vm(|ctx| {
         let loader = compile_file_and_load_classes(
@@ -405,6 +410,7 @@ fn test_int_array_access() {
 }
 
 #[test]
+#[allow(clippy::needless_as_bytes)]
 fn test_bytes_access() {
     let s = "Hello你好こんにちは안녕하세요";
 
@@ -425,3 +431,3087 @@ fn test_bytes_access() {
         assert_eq!(&*array.get_bytes_elements(ctx), s.as_bytes());
     })
 }
+
+#[test]
+fn test_debug_refs_tracking() {
+    with_java_vm(|ctx| {
+        let outstanding = typed_jni::debug_refs::outstanding_local_refs();
+
+        let s = LocalObject::<JString>::new_string(ctx, "tracked");
+        let cloned = s.clone();
+        assert_eq!(typed_jni::debug_refs::outstanding_local_refs(), outstanding + 2);
+
+        drop(s);
+        drop(cloned);
+        assert_eq!(typed_jni::debug_refs::outstanding_local_refs(), outstanding);
+    })
+}
+
+#[test]
+fn test_debug_refs_explicit_detach() {
+    with_java_vm(|_| {
+        std::thread::spawn(|| {
+            Context::attach_current_thread().unwrap();
+
+            Context::with_attached(|ctx| core::mem::forget(LocalObject::<JString>::new_string(ctx, "leaked")));
+            assert_eq!(typed_jni::debug_refs::outstanding_local_refs(), 1);
+
+            // reported and reset when the thread detaches
+            unsafe { Context::detach_current_thread() }.unwrap();
+            assert_eq!(typed_jni::debug_refs::outstanding_local_refs(), 0);
+        })
+        .join()
+        .unwrap();
+    })
+}
+
+#[test]
+fn test_debug_refs_native_leak() {
+    thread_local! {
+        static REPORTED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    typed_jni::debug_refs::set_leak_reporter(|message| REPORTED.with_borrow_mut(|r| r.push(message.to_string())));
+
+    with_java_vm(|ctx| {
+        let runnable = ctx
+            .new_runnable(|| {
+                Context::with_current(|ctx| core::mem::forget(LocalObject::<JString>::new_string(ctx, "leaked"))).unwrap()
+            })
+            .unwrap();
+
+        let outstanding = typed_jni::debug_refs::outstanding_local_refs();
+
+        let _: () = runnable.call_method(ctx, "run", NoArgs).unwrap();
+
+        // reported and reset when the native method returns
+        assert_eq!(typed_jni::debug_refs::outstanding_local_refs(), outstanding);
+        assert!(REPORTED.with_borrow(|r| r
+            .iter()
+            .any(|m| m == "1 local references leaked before returning from a native method")));
+    })
+}
+
+#[test]
+fn test_throwable_causes() {
+    with_java_vm(|ctx| {
+        let c_throwable = LocalClass::<Throwable>::find_class(ctx).unwrap();
+
+        let root = c_throwable
+            .new_object(ctx, &LocalObject::<JString>::new_string(ctx, "root"))
+            .unwrap();
+        let middle = c_throwable
+            .new_object(ctx, (&LocalObject::<JString>::new_string(ctx, "middle"), &root))
+            .unwrap();
+        let top = c_throwable
+            .new_object(ctx, (&LocalObject::<JString>::new_string(ctx, "top"), &middle))
+            .unwrap();
+
+        let messages = top
+            .causes(ctx)
+            .map(|t| {
+                t.unwrap()
+                    .call_method::<LocalObject<JString>, _>(ctx, "getMessage", NoArgs)
+                    .unwrap()
+                    .get_string(ctx)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(messages, ["middle", "root"]);
+        assert!(root.cause(ctx).unwrap().is_none());
+
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "SelfCause",
+            r#"
+                public class SelfCause extends RuntimeException {
+                    @Override
+                    public synchronized Throwable getCause() {
+                        return this;
+                    }
+                }
+            "#,
+        );
+
+        define_java_class!(JavaSelfCause, "SelfCause");
+
+        let c_self_cause: LocalClass<JavaSelfCause> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "SelfCause"))
+            .unwrap();
+        let o_self_cause = unsafe { c_self_cause.new_object(ctx, NoArgs).unwrap().cast(ctx, &c_throwable).unwrap() };
+
+        assert!(o_self_cause.cause(ctx).unwrap().is_none());
+        assert_eq!(o_self_cause.causes(ctx).count(), 0);
+
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "CycleCause",
+            r#"
+                public class CycleCause extends RuntimeException {
+                    public CycleCause other;
+
+                    public static CycleCause create() {
+                        CycleCause a = new CycleCause();
+                        CycleCause b = new CycleCause();
+                        a.other = b;
+                        b.other = a;
+                        return a;
+                    }
+
+                    @Override
+                    public synchronized Throwable getCause() {
+                        return other;
+                    }
+                }
+            "#,
+        );
+
+        define_java_class!(JavaCycleCause, "CycleCause");
+
+        let c_cycle_cause: LocalClass<JavaCycleCause> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "CycleCause"))
+            .unwrap();
+        let o_cycle_cause: LocalObject<JavaCycleCause> = c_cycle_cause.call_method(ctx, "create", NoArgs).unwrap();
+        let o_cycle_cause = unsafe { o_cycle_cause.cast(ctx, &c_throwable).unwrap() };
+
+        assert_eq!(o_cycle_cause.causes(ctx).count(), 1);
+        assert!(o_cycle_cause.into_error(ctx).unwrap().cause().unwrap().cause().is_none());
+
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "ThrowingCause",
+            r#"
+                public class ThrowingCause extends RuntimeException {
+                    @Override
+                    public synchronized Throwable getCause() {
+                        throw new IllegalStateException("no cause");
+                    }
+                }
+            "#,
+        );
+
+        define_java_class!(JavaThrowingCause, "ThrowingCause");
+
+        let c_throwing_cause: LocalClass<JavaThrowingCause> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "ThrowingCause"))
+            .unwrap();
+        let o_throwing_cause = unsafe {
+            c_throwing_cause
+                .new_object(ctx, NoArgs)
+                .unwrap()
+                .cast(ctx, &c_throwable)
+                .unwrap()
+        };
+
+        assert!(matches!(o_throwing_cause.cause(ctx), Err(Error::Throwable(_))));
+        assert!(o_throwing_cause.into_error(ctx).is_err());
+    })
+}
+
+#[test]
+fn test_java_error() {
+    fn assert_send_static<T: Send + Sync + 'static>(_: &T) {}
+
+    with_java_vm(|ctx| {
+        let c_throwable = LocalClass::<Throwable>::find_class(ctx).unwrap();
+
+        let root = c_throwable
+            .new_object(ctx, &LocalObject::<JString>::new_string(ctx, "root"))
+            .unwrap();
+        let top = c_throwable
+            .new_object(ctx, (&LocalObject::<JString>::new_string(ctx, "top"), &root))
+            .unwrap();
+
+        let error = top.into_error(ctx).unwrap();
+        assert_send_static(&error);
+
+        assert_eq!(error.to_string(), "java.lang.Throwable: top");
+
+        let source = std::error::Error::source(&error).unwrap();
+        assert_eq!(source.to_string(), "java.lang.Throwable: root");
+        assert!(source.source().is_none());
+
+        let boxed: Box<dyn std::error::Error + Send + Sync> = error.into();
+        assert_eq!(boxed.to_string(), "java.lang.Throwable: top");
+    })
+}
+
+#[test]
+fn test_new_string_ascii_fast_path() {
+    with_java_vm(|ctx| {
+        for s in [
+            "",
+            "hello",
+            "with\0nul",
+            &"a".repeat(127),
+            &"b".repeat(128),
+            &"c".repeat(1024),
+        ] {
+            assert_eq!(LocalObject::<JString>::new_string(ctx, s).get_string(ctx), s);
+        }
+
+        let s = unsafe { LocalObject::<JString>::from_raw(ctx.new_string_utf(c"from c string")) };
+        assert_eq!(s.get_string(ctx), "from c string");
+    })
+}
+
+#[test]
+fn test_string_args() {
+    with_java_vm(|ctx| {
+        let o_hello = LocalObject::<JString>::new_string(ctx, "hello");
+
+        let concat: LocalObject<JString> = o_hello.call_method(ctx, "concat", " world").unwrap();
+        assert_eq!(concat.get_string(ctx), "hello world");
+
+        let concat: LocalObject<JString> = o_hello.call_method(ctx, "concat", String::from(" 你好")).unwrap();
+        assert_eq!(concat.get_string(ctx), "hello 你好");
+
+        let outstanding = typed_jni::debug_refs::outstanding_local_refs();
+
+        for _ in 0..1024 {
+            let index: i32 = o_hello.call_method(ctx, "indexOf", ("l", 3)).unwrap();
+            assert_eq!(index, 3);
+        }
+
+        assert_eq!(typed_jni::debug_refs::outstanding_local_refs(), outstanding);
+    })
+}
+
+#[test]
+fn test_wide_args() {
+    with_java_vm(|ctx| {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustWideArgs",
+            r#"
+                public class RustWideArgs {
+                    public static String join(int a, long b, String c, boolean d, double e, float f, short g, byte h, char i, String j, int k, int l, int m) {
+                        return a + "," + b + "," + c + "," + d + "," + e + "," + f + "," + g + "," + h + "," + i + "," + j + "," + k + "," + l + "," + m;
+                    }
+
+                    public static int sum(int a, int b, int c, int d, int e, int f, int g, int h, int i, int j, int k, int l, int m, int n, int o, int p) {
+                        return a + 2 * b + 3 * c + 4 * d + 5 * e + 6 * f + 7 * g + 8 * h + 9 * i + 10 * j + 11 * k + 12 * l + 13 * m + 14 * n + 15 * o + 16 * p;
+                    }
+                }
+            "#,
+        );
+
+        define_java_class!(JavaRustWideArgs, "RustWideArgs");
+
+        let c_wide: LocalClass<JavaRustWideArgs> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustWideArgs"))
+            .unwrap();
+
+        let joined: LocalObject<JString> = c_wide
+            .call_method(
+                ctx,
+                "join",
+                (
+                    1i32, 2i64, "three", true, 5.5f64, 6.5f32, 7i16, 8i8, 'i' as u16, "ten", 11i32, 12i32, 13i32,
+                ),
+            )
+            .unwrap();
+        assert_eq!(joined.get_string(ctx), "1,2,three,true,5.5,6.5,7,8,i,ten,11,12,13");
+
+        let sum: i32 = c_wide
+            .call_method(ctx, "sum", (1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 100))
+            .unwrap();
+        assert_eq!(sum, 120 + 1600);
+    })
+}
+
+#[test]
+fn test_unit_args() {
+    with_java_vm(|ctx| {
+        let o_string = LocalObject::<JString>::new_string(ctx, "unit");
+
+        let length: i32 = o_string.call_method(ctx, "length", ()).unwrap();
+        assert_eq!(length, 4);
+
+        let upper: LocalObject<JString> = o_string.call_method(ctx, "toUpperCase", ()).unwrap();
+        assert_eq!(upper.get_string(ctx), "UNIT");
+
+        let c_object = LocalClass::<JString>::find_class(ctx).unwrap();
+        assert_eq!(c_object.new_object(ctx, ()).unwrap().get_string(ctx), "");
+    })
+}
+
+#[test]
+fn test_send_global_across_threads() {
+    with_java_vm(|ctx| {
+        let global = std::thread::spawn(|| {
+            Context::with_attached(|ctx| LocalObject::<JString>::new_string(ctx, "from worker").to_global().unwrap())
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(global.get_string(ctx), "from worker");
+    })
+}
+
+#[test]
+fn test_find_method_recursive() {
+    with_java_vm(|ctx| {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustRecursive",
+            r#"
+                public class RustRecursive {
+                    public interface Base {
+                        default String base() {
+                            return "base";
+                        }
+                    }
+
+                    public interface Derived extends Base {}
+
+                    public static class Parent {
+                        public String parent() {
+                            return "parent";
+                        }
+                    }
+
+                    public static class Child extends Parent implements Derived {}
+                }
+            "#,
+        );
+
+        let load = |name: &str| -> LocalClass<JavaObject> {
+            loader
+                .loader
+                .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, name))
+                .unwrap()
+        };
+
+        let c_derived = load("RustRecursive$Derived");
+        let c_child = load("RustRecursive$Child");
+        let o_child = c_child.new_object(ctx, ()).unwrap();
+
+        unsafe {
+            let m_base = ctx
+                .find_method_recursive::<false, _>(c_derived.as_raw(), c"base", c"()Ljava/lang/String;")
+                .unwrap();
+            let base: Local = ctx.call_method(o_child.as_raw(), m_base, []).unwrap();
+            assert_eq!(ctx.get_string(&base), "base");
+
+            let m_parent = ctx
+                .find_method_recursive::<false, _>(c_child.as_raw(), c"parent", c"()Ljava/lang/String;")
+                .unwrap();
+            let parent: Local = ctx.call_method(o_child.as_raw(), m_parent, []).unwrap();
+            assert_eq!(ctx.get_string(&parent), "parent");
+
+            let m_hash_code = ctx
+                .find_method_recursive::<false, _>(c_derived.as_raw(), c"hashCode", c"()I")
+                .unwrap();
+            let _: i32 = ctx.call_method(o_child.as_raw(), m_hash_code, []).unwrap();
+
+            assert!(ctx
+                .find_method_recursive::<false, _>(c_derived.as_raw(), c"missing", c"()V")
+                .is_err());
+        }
+    })
+}
+
+#[test]
+fn test_jchar_round_trip() {
+    with_java_vm(|ctx| {
+        define_java_class!(JavaCharacter, "java.lang.Character");
+
+        let c_character = LocalClass::<JavaCharacter>::find_class(ctx).unwrap();
+
+        let upper: Jchar = c_character
+            .call_method(ctx, "toUpperCase", Jchar::try_from('q').unwrap())
+            .unwrap();
+        assert_eq!(char::try_from(upper).unwrap(), 'Q');
+
+        let o_emoji = LocalObject::<JString>::new_string(ctx, "😀");
+        let high: Jchar = o_emoji.call_method(ctx, "charAt", 0).unwrap();
+        assert!(char::try_from(high).is_err());
+    })
+}
+
+#[test]
+fn test_read_fields_into() {
+    #[repr(C)]
+    #[derive(Default, Debug, PartialEq)]
+    struct Sample {
+        id: i32,
+        timestamp: i64,
+        value: f64,
+        valid: bool,
+    }
+
+    impl FieldLayout for Sample {
+        fn read_fields<'ctx, R: StrongRef>(&mut self, fields: &mut FieldReader<'_, 'ctx, R>) -> Result<(), Error<'ctx>> {
+            self.id = fields.read("id")?;
+            self.timestamp = fields.read("timestamp")?;
+            self.value = fields.read("value")?;
+            self.valid = fields.read("valid")?;
+
+            Ok(())
+        }
+    }
+
+    // a second layout of the same class is cached apart from the first
+    #[derive(Default)]
+    struct Valid(bool);
+
+    impl FieldLayout for Valid {
+        fn read_fields<'ctx, R: StrongRef>(&mut self, fields: &mut FieldReader<'_, 'ctx, R>) -> Result<(), Error<'ctx>> {
+            self.0 = fields.read("valid")?;
+
+            Ok(())
+        }
+    }
+
+    with_java_vm(|ctx| {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustSample",
+            r#"
+                public class RustSample {
+                    public int id;
+                    public long timestamp;
+                    public double value;
+                    public boolean valid;
+
+                    public RustSample(int id) {
+                        this.id = id;
+                        this.timestamp = 1000L * id;
+                        this.value = id / 2.0;
+                        this.valid = id % 2 == 0;
+                    }
+                }
+            "#,
+        );
+
+        let c_sample: LocalClass<JavaObject> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustSample"))
+            .unwrap();
+
+        let mut sample = Sample::default();
+        for id in 0..16 {
+            let o_sample = c_sample.new_object(ctx, id).unwrap();
+
+            o_sample.read_fields_into(ctx, &mut sample).unwrap();
+
+            assert_eq!(
+                sample,
+                Sample {
+                    id,
+                    timestamp: 1000 * id as i64,
+                    value: id as f64 / 2.0,
+                    valid: id % 2 == 0,
+                }
+            );
+
+            let mut valid = Valid::default();
+            o_sample.read_fields_into(ctx, &mut valid).unwrap();
+            assert_eq!(valid.0, id % 2 == 0);
+        }
+    })
+}
+
+#[test]
+fn test_attach_named() {
+    define_java_class!(JavaThread, "java.lang.Thread");
+    define_java_class!(JavaThreadGroup, "java.lang.ThreadGroup");
+
+    with_java_vm(|ctx| {
+        let c_group = LocalClass::<JavaThreadGroup>::find_class(ctx).unwrap();
+        let group = c_group
+            .new_object(ctx, &LocalObject::<JString>::new_string(ctx, "rust-workers"))
+            .unwrap()
+            .to_global()
+            .unwrap();
+
+        let (name, group_name) = std::thread::spawn(move || {
+            Context::with_attached_named("rust-worker-1", Some(group.as_raw()), |ctx| {
+                let c_thread = LocalClass::<JavaThread>::find_class(ctx).unwrap();
+                let o_thread: LocalObject<JavaThread> = c_thread.call_method(ctx, "currentThread", ()).unwrap();
+
+                let name: LocalObject<JString> = o_thread.call_method(ctx, "getName", ()).unwrap();
+                let o_group: LocalObject<JavaThreadGroup> = o_thread.call_method(ctx, "getThreadGroup", ()).unwrap();
+                let group_name: LocalObject<JString> = o_group.call_method(ctx, "getName", ()).unwrap();
+
+                (name.get_string(ctx), group_name.get_string(ctx))
+            })
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(name, "rust-worker-1");
+        assert_eq!(group_name, "rust-workers");
+    })
+}
+
+#[test]
+fn test_jstring_constructors() {
+    with_java_vm(|ctx| {
+        for s in ["hello", "你好", "😀 emoji", "embedded\0nul", ""] {
+            let o_str = JString::new(ctx, s);
+            assert_eq!(o_str.get_string(ctx), s);
+
+            let length: i32 = o_str.call_method(ctx, "length", ()).unwrap();
+            assert_eq!(length as usize, s.encode_utf16().count());
+        }
+
+        let o_surrogate = JString::new_utf16(ctx, &[0x61, 0xD83D, 0x62]);
+        let length: i32 = o_surrogate.call_method(ctx, "length", ()).unwrap();
+        let high: Jchar = o_surrogate.call_method(ctx, "charAt", 1).unwrap();
+        assert_eq!(length, 3);
+        assert_eq!(high, Jchar(0xD83D));
+    })
+}
+
+#[test]
+fn test_cache_after_class_unload() {
+    define_java_class!(JavaSystem, "java.lang.System");
+
+    fn load_and_call(ctx: &Context, value: i32) -> (i32, WeakClass<JavaObject>) {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustUnload",
+            &format!("public class RustUnload {{ public int value() {{ return {value}; }} }}"),
+        );
+
+        let c_unload: LocalClass<JavaObject> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustUnload"))
+            .unwrap();
+        let o_unload = c_unload.new_object(ctx, ()).unwrap();
+
+        (o_unload.call_method(ctx, "value", ()).unwrap(), c_unload.downgrade_weak())
+    }
+
+    with_java_vm(|ctx| {
+        let (value, w_unload) = load_and_call(ctx, 1);
+        assert_eq!(value, 1);
+
+        let c_system = LocalClass::<JavaSystem>::find_class(ctx).unwrap();
+        for _ in 0..10 {
+            if w_unload.upgrade_local(ctx).is_none() {
+                break;
+            }
+
+            c_system.call_method::<(), _>(ctx, "gc", ()).unwrap();
+        }
+
+        let (value, _) = load_and_call(ctx, 2);
+        assert_eq!(value, 2);
+    })
+}
+
+#[test]
+fn test_object_array_null_element() {
+    define_java_class!(JavaArrayIndexOutOfBoundsException, "java.lang.ArrayIndexOutOfBoundsException");
+
+    with_java_vm(|ctx| {
+        let c_string = LocalClass::<JString>::find_class(ctx).unwrap();
+        let o_array = LocalObject::<Array<JString>>::new(ctx, 2, &c_string).unwrap();
+        o_array.set_element(ctx, 1, Some(&JString::new(ctx, "value"))).unwrap();
+
+        assert!(o_array.get_element(ctx, 0).unwrap().is_none());
+        assert_eq!(o_array.get_element(ctx, 1).unwrap().unwrap().get_string(ctx), "value");
+
+        let c_out_of_bounds = LocalClass::<JavaArrayIndexOutOfBoundsException>::find_class(ctx).unwrap();
+        for index in [-1, 2] {
+            let err = o_array.get_element(ctx, index).unwrap_err();
+            assert!(err.is_instance_of(ctx, &c_out_of_bounds));
+        }
+    })
+}
+
+#[test]
+fn test_call_static_by_type() {
+    define_java_class!(JavaInteger, "java.lang.Integer");
+
+    with_java_vm(|ctx| {
+        let parsed: i32 = JavaInteger::call_static(ctx, "parseInt", "1234").unwrap();
+        assert_eq!(parsed, 1234);
+
+        let max: i32 = JavaInteger::call_static(ctx, "max", (3, 7)).unwrap();
+        assert_eq!(max, 7);
+
+        assert!(JavaInteger::call_static::<i32, _>(ctx, "parseInt", "not a number").is_err());
+    })
+}
+
+#[test]
+fn test_string_charset_bytes() {
+    with_java_vm(|ctx| {
+        let o_str = JString::new(ctx, "café");
+
+        assert_eq!(o_str.get_bytes(ctx, "ISO-8859-1").unwrap(), b"caf\xE9");
+        assert_eq!(o_str.get_bytes(ctx, "UTF-8").unwrap(), "café".as_bytes());
+
+        let o_decoded = JString::from_bytes(ctx, b"caf\xE9", "ISO-8859-1").unwrap();
+        assert_eq!(o_decoded.get_string(ctx), "café");
+
+        assert!(o_str.get_bytes(ctx, "NO-SUCH-CHARSET").is_err());
+        assert!(JString::from_bytes(ctx, b"", "NO-SUCH-CHARSET").is_err());
+    })
+}
+
+#[test]
+fn test_interrupt_thread() {
+    define_java_class!(JavaInterruptedException, "java.lang.InterruptedException");
+
+    with_java_vm(|ctx| {
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        let worker = std::thread::spawn(move || {
+            Context::with_attached(|ctx| {
+                sender.send(ctx.current_thread().unwrap().to_global().unwrap()).unwrap();
+
+                let c_thread = LocalClass::<JThread>::find_class(ctx).unwrap();
+                let err = c_thread.call_method::<(), _>(ctx, "sleep", 60_000i64).unwrap_err();
+
+                let c_interrupted = LocalClass::<JavaInterruptedException>::find_class(ctx).unwrap();
+                err.throwable().unwrap().is_instance_of(ctx, &c_interrupted)
+            })
+        });
+
+        let thread = receiver.recv().unwrap();
+        assert!(!thread.is_interrupted(ctx).unwrap());
+
+        thread.interrupt(ctx).unwrap();
+
+        assert!(worker.join().unwrap());
+    })
+}
+
+#[test]
+fn test_class_cache() {
+    static CLASSES: ClassCache = ClassCache::new();
+
+    with_java_vm(|ctx| {
+        let c_string = CLASSES.get_or_load(ctx, "java.lang.String").unwrap();
+        assert_eq!(CLASSES.get_or_load(ctx, "java.lang.String").unwrap(), c_string);
+        assert_eq!(c_string.to_string(), "class java.lang.String");
+
+        let c_from_thread =
+            std::thread::spawn(|| Context::with_attached(|ctx| CLASSES.get_or_load(ctx, "java.lang.String").unwrap()))
+                .join()
+                .unwrap();
+        assert_eq!(c_from_thread, c_string);
+
+        assert!(CLASSES.get_or_load(ctx, "com.example.Missing").is_err());
+        assert!(matches!(CLASSES.get_or_load(ctx, "a\0b"), Err(Error::InvalidName)));
+
+        assert_eq!(CLASSES.remove("java.lang.String"), Some(c_string));
+        assert!(CLASSES.remove("java.lang.String").is_none());
+
+        let loader = compile_file_and_load_classes(ctx, "RustCached", "public class RustCached {}");
+        let c_loader = LocalClass::<JClassLoader>::find_class(ctx).unwrap();
+        let loader = unsafe { loader.loader.cast(ctx, &c_loader).unwrap().to_global().unwrap() };
+
+        let classes = ClassCache::with_loader(loader);
+        let c_cached = classes.get_or_load(ctx, "RustCached").unwrap();
+        assert_eq!(c_cached.to_string(), "class RustCached");
+
+        classes.clear();
+        assert!(classes.remove("RustCached").is_none());
+    })
+}
+
+#[test]
+fn test_null_object_arg() {
+    define_java_class!(JavaObjects, "java.util.Objects");
+
+    with_java_vm(|ctx| {
+        let is_null: bool = JavaObjects::call_static(ctx, "isNull", Null::<JavaObject>::new()).unwrap();
+        assert!(is_null);
+
+        let is_null: bool = JavaObjects::call_static(ctx, "isNull", None::<&LocalObject<JavaObject>>).unwrap();
+        assert!(is_null);
+
+        let o_value = JString::new(ctx, "value");
+        let o_fallback: LocalObject<JString> =
+            JavaObjects::call_static(ctx, "toString", (Null::<JavaObject>::new(), &o_value)).unwrap();
+        assert_eq!(o_fallback.get_string(ctx), "value");
+    })
+}
+
+#[test]
+fn test_is_same_object() {
+    with_java_vm(|ctx| {
+        let o_first = JString::new(ctx, "same");
+        let o_second = JString::new(ctx, "same");
+        let g_first = o_first.to_global().unwrap();
+
+        assert!(o_first.is_same_object(ctx, &g_first));
+        assert!(g_first.is_same_object(ctx, &o_first.clone()));
+        assert!(!o_first.is_same_object(ctx, &o_second));
+
+        let objects = [&o_first, &o_second, &o_first.clone()];
+        let mut unique: Vec<&LocalObject<JString>> = Vec::new();
+        for object in objects {
+            if !unique.iter().any(|u| u.is_same_object(ctx, object)) {
+                unique.push(object);
+            }
+        }
+        assert_eq!(unique.len(), 2);
+
+        assert!(ctx.is_same_object(None::<&Local>, None::<&Local>));
+        assert!(!ctx.is_same_object(Some(o_first.as_raw()), None::<&Local>));
+
+        let w_second = o_second.downgrade_weak();
+        assert!(w_second.is_same_object(ctx, &o_second));
+        assert!(!ctx.is_same_object(Some(w_second.as_raw()), None::<&Local>));
+    })
+}
+
+#[test]
+fn test_boolean_array_normalized() {
+    define_java_class!(JavaArrays, "java.util.Arrays");
+
+    with_java_vm(|ctx| {
+        let o_array = LocalObject::<JBooleanArray>::new_primitive(ctx, 4).unwrap();
+        o_array.set_region(ctx, 0, &[true, false, true, true]).unwrap();
+
+        let mut raw = [0xFFu8; 4];
+        o_array.get_jboolean_region(ctx, 0, &mut raw).unwrap();
+        assert_eq!(raw, [1, 0, 1, 1]);
+
+        unsafe { o_array.set_jboolean_region(ctx, 1, &[2, 0x80]).unwrap() };
+        o_array.get_jboolean_region(ctx, 0, &mut raw).unwrap();
+        assert_eq!(raw, [1, 2, 0x80, 1]);
+
+        let mut bools = [false; 4];
+        o_array.get_region(ctx, 0, &mut bools).unwrap();
+        assert_eq!(bools, [true, true, true, true]);
+        assert_eq!(*o_array.get_elements(ctx), [true, true, true, true]);
+
+        // read-only access leaves the raw bytes alone, a commit writes back normalized values
+        o_array.get_jboolean_region(ctx, 0, &mut raw).unwrap();
+        assert_eq!(raw, [1, 2, 0x80, 1]);
+        let mut elements = o_array.get_elements(ctx);
+        elements[0] = false;
+        elements.commit();
+        o_array.get_jboolean_region(ctx, 0, &mut raw).unwrap();
+        assert_eq!(raw, [0, 1, 1, 1]);
+        unsafe { o_array.set_jboolean_region(ctx, 0, &[1, 2, 0x80]).unwrap() };
+
+        let o_text: LocalObject<JString> = JavaArrays::call_static(ctx, "toString", &o_array).unwrap();
+        assert_eq!(o_text.get_string(ctx), "[true, true, true, true]");
+
+        assert!(o_array.get_jboolean_region(ctx, 2, &mut raw).is_err());
+    })
+}
+
+#[test]
+fn test_copy_utf_region() {
+    define_java_class!(
+        JavaStringIndexOutOfBoundsException,
+        "java.lang.StringIndexOutOfBoundsException"
+    );
+
+    with_java_vm(|ctx| {
+        let o_str = JString::new(ctx, "héllo😀!");
+
+        let mut buf = [0u8; 64];
+        let written = o_str.copy_utf_region(ctx, 0, &mut buf).unwrap();
+        assert_eq!(&buf[..written], b"h\xC3\xA9llo\xED\xA0\xBD\xED\xB8\x80!");
+        assert_eq!(buf[written], 0);
+
+        let mut buf = [0xFFu8; 4];
+        let written = o_str.copy_utf_region(ctx, 0, &mut buf).unwrap();
+        assert_eq!(&buf[..written], "hé".as_bytes());
+
+        // the surrogate pair is not split
+        let mut buf = [0u8; 10];
+        let written = o_str.copy_utf_region(ctx, 0, &mut buf).unwrap();
+        assert_eq!(&buf[..written], "héllo".as_bytes());
+
+        let mut buf = [0u8; 8];
+        let written = o_str.copy_utf_region(ctx, 5, &mut buf).unwrap();
+        assert_eq!(&buf[..written], b"\xED\xA0\xBD\xED\xB8\x80!");
+
+        assert_eq!(o_str.copy_utf_region(ctx, 8, &mut buf).unwrap(), 0);
+        assert_eq!(o_str.copy_utf_region(ctx, 0, &mut []).unwrap(), 0);
+
+        let c_out_of_bounds = LocalClass::<JavaStringIndexOutOfBoundsException>::find_class(ctx).unwrap();
+        for start in [-1, 9] {
+            let err = o_str.copy_utf_region(ctx, start, &mut buf).unwrap_err();
+            assert!(err.is_instance_of(ctx, &c_out_of_bounds));
+        }
+    })
+}
+
+#[test]
+fn test_constructor_overloads() {
+    define_java_class!(JavaRustOverloads, "RustOverloads");
+    define_java_class!(JavaNoSuchMethodError, "java.lang.NoSuchMethodError");
+
+    with_java_vm(|ctx| {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustOverloads",
+            r#"
+                public class RustOverloads {
+                    public final String picked;
+
+                    public RustOverloads(int value) {
+                        picked = "int " + value;
+                    }
+
+                    public RustOverloads(String value) {
+                        picked = "String " + value;
+                    }
+                }
+            "#,
+        );
+
+        let c_overloads: LocalClass<JavaRustOverloads> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustOverloads"))
+            .unwrap();
+
+        let picked =
+            |o: LocalObject<JavaRustOverloads>| o.get_field::<LocalObject<JString>>(ctx, "picked").unwrap().get_string(ctx);
+
+        assert_eq!(picked(c_overloads.new_object(ctx, 7).unwrap()), "int 7");
+        assert_eq!(picked(c_overloads.new_object(ctx, "seven").unwrap()), "String seven");
+        assert_eq!(picked(c_overloads.new_object(ctx, 8).unwrap()), "int 8");
+
+        let c_no_such_method = LocalClass::<JavaNoSuchMethodError>::find_class(ctx).unwrap();
+        for err in [
+            c_overloads.new_object(ctx, 7i64).unwrap_err(),
+            c_overloads.new_object(ctx, ()).unwrap_err(),
+        ] {
+            assert!(err.throwable().unwrap().is_instance_of(ctx, &c_no_such_method));
+        }
+    })
+}
+
+#[test]
+fn test_array_bounds_helpers() {
+    with_java_vm(|ctx| {
+        let o_ints = LocalObject::<Array<i32>>::new_primitive(ctx, 3).unwrap();
+        o_ints.set_region(ctx, 0, &[10, 20, 30]).unwrap();
+
+        assert!(!o_ints.is_empty(ctx));
+        assert!(LocalObject::<Array<i32>>::new_primitive(ctx, 0).unwrap().is_empty(ctx));
+
+        assert_eq!(o_ints.get_primitive(ctx, 2).unwrap(), 30);
+        assert_eq!(o_ints.get_primitive_checked(ctx, 1), Ok(20));
+        assert!(o_ints.get_primitive(ctx, 3).is_err());
+        assert_eq!(
+            o_ints.get_primitive_checked(ctx, 3),
+            Err(IndexOutOfBounds { index: 3, length: 3 })
+        );
+        assert_eq!(
+            o_ints.get_primitive_checked(ctx, -1).unwrap_err().to_string(),
+            "index -1 out of bounds for length 3"
+        );
+
+        let c_string = LocalClass::<JString>::find_class(ctx).unwrap();
+        let o_strings = LocalObject::<Array<JString>>::new(ctx, 2, &c_string).unwrap();
+        o_strings.set_element(ctx, 0, Some(&JString::new(ctx, "first"))).unwrap();
+
+        assert_eq!(
+            o_strings.get_element_checked(ctx, 0).unwrap().unwrap().get_string(ctx),
+            "first"
+        );
+        assert!(o_strings.get_element_checked(ctx, 1).unwrap().is_none());
+        assert_eq!(
+            o_strings.get_element_checked(ctx, 2).unwrap_err(),
+            IndexOutOfBounds { index: 2, length: 2 }
+        );
+    })
+}
+
+#[test]
+fn test_trampoline_returns() {
+    define_java_class!(JavaRustReturns, "RustReturns");
+
+    with_java_vm(|ctx| {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustReturns",
+            r#"
+                public class RustReturns {
+                    public static int calls;
+
+                    public static native void touch();
+                    public static native boolean isEven(int value);
+                    public static native char upper(char value);
+                    public static native long twice(long value);
+                    public static native String describe(int value);
+                    public static native RustReturns self(RustReturns value);
+
+                    public static String run() {
+                        touch();
+                        RustReturns r = new RustReturns();
+                        return calls + " " + isEven(4) + " " + isEven(3) + " " + upper('q') + " " + twice(21)
+                            + " " + describe(1) + " " + describe(-1) + " " + (self(r) == r) + " " + self(null);
+                    }
+                }
+            "#,
+        );
+
+        extern "C" fn touch<'ctx>(
+            ctx: &'ctx Context,
+            class: TrampolineClass<'ctx, JavaRustReturns>,
+        ) -> <() as TrampolineReturn>::Raw {
+            let calls: i32 = class.get_field(ctx, "calls").unwrap();
+            class.set_field(ctx, "calls", calls + 1).unwrap();
+
+            ().into_return()
+        }
+
+        extern "C" fn is_even<'ctx>(
+            _: &'ctx Context,
+            _: TrampolineClass<'ctx, JavaRustReturns>,
+            value: i32,
+        ) -> <bool as TrampolineReturn>::Raw {
+            (value % 2 == 0).into_return()
+        }
+
+        extern "C" fn upper<'ctx>(
+            _: &'ctx Context,
+            _: TrampolineClass<'ctx, JavaRustReturns>,
+            value: Jchar,
+        ) -> <Jchar as TrampolineReturn>::Raw {
+            let upper = char::try_from(value).unwrap().to_ascii_uppercase();
+
+            Jchar::try_from(upper).unwrap().into_return()
+        }
+
+        extern "C" fn twice<'ctx>(
+            _: &'ctx Context,
+            _: TrampolineClass<'ctx, JavaRustReturns>,
+            value: i64,
+        ) -> <i64 as TrampolineReturn>::Raw {
+            (value * 2).into_return()
+        }
+
+        extern "C" fn describe<'ctx>(
+            ctx: &'ctx Context,
+            _: TrampolineClass<'ctx, JavaRustReturns>,
+            value: i32,
+        ) -> <Option<LocalObject<'ctx, JString>> as TrampolineReturn>::Raw {
+            (value > 0).then(|| JString::new(ctx, "positive")).into_return()
+        }
+
+        extern "C" fn this<'ctx>(
+            _: &'ctx Context,
+            _: TrampolineClass<'ctx, JavaRustReturns>,
+            value: Option<TrampolineObject<'ctx, JavaRustReturns>>,
+        ) -> <Option<TrampolineObject<'ctx, JavaRustReturns>> as TrampolineReturn>::Raw {
+            value.into_return()
+        }
+
+        let c_returns: LocalClass<JavaRustReturns> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustReturns"))
+            .unwrap();
+
+        unsafe {
+            ctx.register_natives(
+                c_returns.as_raw(),
+                [
+                    (c"touch", c"()V", touch as *const ()),
+                    (c"isEven", c"(I)Z", is_even as *const ()),
+                    (c"upper", c"(C)C", upper as *const ()),
+                    (c"twice", c"(J)J", twice as *const ()),
+                    (c"describe", c"(I)Ljava/lang/String;", describe as *const ()),
+                    (c"self", c"(LRustReturns;)LRustReturns;", this as *const ()),
+                ],
+            )
+            .unwrap()
+        }
+
+        let result: LocalObject<JString> = c_returns.call_method(ctx, "run", ()).unwrap();
+        assert_eq!(result.get_string(ctx), "1 true false Q 42 positive null true null");
+    })
+}
+
+#[test]
+fn test_cached_class() {
+    define_java_class!(JavaStringBuilder, "java.lang.StringBuilder");
+
+    with_java_vm(|ctx| {
+        let c_first = JavaStringBuilder::class(ctx).unwrap();
+        let c_second = JavaStringBuilder::class(ctx).unwrap();
+        assert_eq!(c_first, c_second);
+        assert_eq!(c_first, LocalClass::<JavaStringBuilder>::find_class(ctx).unwrap());
+
+        let c_from_thread =
+            std::thread::spawn(|| Context::with_attached(|ctx| JavaStringBuilder::class(ctx).unwrap().to_global().unwrap()))
+                .join()
+                .unwrap();
+        assert_eq!(c_from_thread.to_local(ctx), c_first);
+
+        unsafe { JavaStringBuilder::invalidate_class() };
+        unsafe { JavaStringBuilder::invalidate_class() };
+
+        let o_builder = JavaStringBuilder::class(ctx).unwrap().new_object(ctx, "cached").unwrap();
+        assert_eq!(o_builder.to_string(), "cached");
+    })
+}
+
+#[test]
+fn test_custom_class_returns() {
+    define_java_class!(JavaRustPoint, "RustPoint");
+
+    with_java_vm(|ctx| {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustPoint",
+            r#"
+                public class RustPoint {
+                    public final int x;
+                    public final int y;
+
+                    private RustPoint(int x, int y) {
+                        this.x = x;
+                        this.y = y;
+                    }
+
+                    public static RustPoint of(int x, int y) {
+                        return new RustPoint(x, y);
+                    }
+
+                    public static RustPoint parse(String s) {
+                        String[] parts = s.split(",");
+                        return parts.length == 2 ? of(Integer.parseInt(parts[0]), Integer.parseInt(parts[1])) : null;
+                    }
+
+                    public RustPoint translate(int dx, int dy) {
+                        return of(x + dx, y + dy);
+                    }
+                }
+            "#,
+        );
+
+        let c_point: LocalClass<JavaRustPoint> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustPoint"))
+            .unwrap();
+
+        let o_point: LocalObject<JavaRustPoint> = c_point.call_method(ctx, "of", (1, 2)).unwrap();
+        let o_moved: LocalObject<JavaRustPoint> = o_point.call_method(ctx, "translate", (10, 20)).unwrap();
+        assert_eq!(o_moved.get_field::<i32>(ctx, "x").unwrap(), 11);
+        assert_eq!(o_moved.get_field::<i32>(ctx, "y").unwrap(), 22);
+
+        let o_parsed: Option<LocalObject<JavaRustPoint>> = c_point.call_method(ctx, "parse", "3,4").unwrap();
+        assert_eq!(o_parsed.unwrap().get_field::<i32>(ctx, "y").unwrap(), 4);
+
+        let o_parsed: Option<LocalObject<JavaRustPoint>> = c_point.call_method(ctx, "parse", "invalid").unwrap();
+        assert!(o_parsed.is_none());
+
+        // the return type is part of the signature
+        assert!(c_point.call_method::<LocalObject<JavaObject>, _>(ctx, "of", (1, 2)).is_err());
+    })
+}
+
+#[test]
+fn test_java_bindings() {
+    java_bindings! {
+        class JavaPoint = "java.awt.Point" {
+            fn translate(dx: i32, dy: i32);
+            fn getLocation() -> LocalObject<'ctx, JavaPoint>;
+            fn setLocation(other: Option<&LocalObject<'ctx, JavaPoint>>);
+            field x: i32 => set_x;
+            field y: i32;
+        }
+
+        class JavaInteger = "java.lang.Integer" {
+            static fn parseInt(s: &str) -> i32;
+            static fn valueOf(value: i32) -> LocalObject<'ctx, JavaInteger>;
+            static fn getInteger(name: &str) -> Option<LocalObject<'ctx, JavaInteger>>;
+            static field MAX_VALUE: i32;
+            fn intValue() -> i32;
+        }
+    }
+
+    with_java_vm(|ctx| {
+        let o_point = JavaPoint::class(ctx).unwrap().new_object(ctx, (1, 2)).unwrap();
+
+        JavaPoint::translate(ctx, &o_point, 10, 20).unwrap();
+        assert_eq!(JavaPoint::x(ctx, &o_point).unwrap(), 11);
+        assert_eq!(JavaPoint::y(ctx, &o_point).unwrap(), 22);
+
+        JavaPoint::set_x(ctx, &o_point, 5).unwrap();
+        let o_copy = JavaPoint::getLocation(ctx, &o_point).unwrap();
+        assert_eq!(JavaPoint::x(ctx, &o_copy).unwrap(), 5);
+        JavaPoint::translate(ctx, &o_copy, 1, 1).unwrap();
+        JavaPoint::setLocation(ctx, &o_point, Some(&o_copy)).unwrap();
+        assert_eq!(JavaPoint::y(ctx, &o_point).unwrap(), 23);
+        assert!(JavaPoint::setLocation(ctx, &o_point, None).is_err());
+
+        let from_thread = std::thread::spawn(|| {
+            Context::with_attached(|ctx| {
+                let o_value = JavaInteger::valueOf(ctx, 42).unwrap();
+
+                JavaInteger::intValue(ctx, &o_value).unwrap()
+            })
+        })
+        .join()
+        .unwrap();
+        assert_eq!(from_thread, 42);
+
+        assert_eq!(JavaInteger::parseInt(ctx, "1234").unwrap(), 1234);
+        assert!(JavaInteger::parseInt(ctx, "not a number").is_err());
+        assert!(JavaInteger::getInteger(ctx, "typed.jni.missing").unwrap().is_none());
+        assert_eq!(JavaInteger::MAX_VALUE(ctx).unwrap(), i32::MAX);
+    })
+}
+
+#[test]
+fn test_monitor_reentrant() {
+    define_java_class!(JavaThread, "java.lang.Thread");
+
+    with_java_vm(|ctx| {
+        let c_object = LocalClass::<JavaObject>::find_class(ctx).unwrap();
+        let o_object = c_object.new_object(ctx, NoArgs).unwrap();
+
+        let holds_lock = |ctx: &Context| -> bool { JavaThread::call_static(ctx, "holdsLock", &o_object).unwrap() };
+
+        let outer = o_object.lock(ctx).unwrap();
+        let inner = o_object.lock(ctx).unwrap();
+        assert!(holds_lock(ctx));
+
+        drop(inner);
+        assert!(holds_lock(ctx));
+
+        drop(outer);
+        assert!(!holds_lock(ctx));
+
+        let o_class = c_object.lock(ctx).unwrap();
+        drop(o_class);
+
+        assert!(unsafe { ctx.monitor_exit(*o_object.as_raw().as_raw()) }.is_err());
+    })
+}
+
+#[test]
+fn test_declared_methods() {
+    define_java_class!(JavaInteger, "java.lang.Integer");
+
+    with_java_vm(|ctx| {
+        let c_integer = LocalClass::<JavaInteger>::find_class(ctx).unwrap();
+        let c_string = LocalClass::<JString>::find_class(ctx).unwrap();
+
+        let methods = ctx.get_declared_methods(&c_integer).unwrap();
+        assert!(methods
+            .iter()
+            .any(|m| m.name(ctx).unwrap() == "intValue" && !m.is_static(ctx)));
+
+        let parse_int = methods
+            .iter()
+            .find(|m| {
+                let types = m.parameter_types(ctx).unwrap();
+
+                m.name(ctx).unwrap() == "parseInt" && types.len() == 1 && types[0].is_same_object(ctx, &c_string)
+            })
+            .unwrap();
+        assert!(parse_int.is_static(ctx));
+
+        unsafe {
+            let method = ctx.from_reflected_method::<true, _>(parse_int.as_raw()).unwrap();
+            let parsed: i32 = ctx.call_method(c_integer.as_raw(), method, [CallArg::from("42")]).unwrap();
+            assert_eq!(parsed, 42);
+        }
+    })
+}
+
+#[test]
+fn test_array_copy() {
+    with_java_vm(|ctx| {
+        let o_src = LocalObject::<Array<i32>>::new_primitive(ctx, 6).unwrap();
+        o_src.set_region(ctx, 0, &[1, 2, 3, 4, 5, 6]).unwrap();
+
+        let o_dst = LocalObject::<Array<i32>>::new_primitive(ctx, 4).unwrap();
+        ctx.array_copy(&o_src, 1, &o_dst, 0, 4).unwrap();
+
+        let mut values = [0; 4];
+        o_dst.get_region(ctx, 0, &mut values).unwrap();
+        assert_eq!(values, [2, 3, 4, 5]);
+
+        ctx.array_copy(&o_src, 0, &o_src, 2, 4).unwrap();
+
+        let mut values = [0; 6];
+        o_src.get_region(ctx, 0, &mut values).unwrap();
+        assert_eq!(values, [1, 2, 1, 2, 3, 4]);
+
+        assert!(matches!(ctx.array_copy(&o_src, 4, &o_dst, 0, 4), Err(Error::Throwable(_))));
+
+        let o_longs = LocalObject::<Array<i64>>::new_primitive(ctx, 4).unwrap();
+        assert!(matches!(ctx.array_copy(&o_src, 0, &o_longs, 0, 4), Err(Error::Throwable(_))));
+    })
+}
+
+#[test]
+fn test_enum_constant() {
+    define_java_class!(JavaTimeUnit, "java.util.concurrent.TimeUnit");
+
+    with_java_vm(|ctx| {
+        let c_time_unit = LocalClass::<JavaTimeUnit>::find_class(ctx).unwrap();
+
+        let o_seconds = c_time_unit.get_enum_constant(ctx, "SECONDS").unwrap();
+        let millis: i64 = o_seconds.call_method(ctx, "toMillis", 2i64).unwrap();
+        assert_eq!(millis, 2000);
+
+        let o_name: LocalObject<JString> = o_seconds.call_method(ctx, "name", NoArgs).unwrap();
+        assert_eq!(o_name.get_string(ctx), "SECONDS");
+
+        let o_again: LocalObject<JavaTimeUnit> = c_time_unit.get_field(ctx, "SECONDS").unwrap();
+        assert!(o_again.is_same_object(ctx, &o_seconds));
+
+        assert!(matches!(
+            c_time_unit.get_enum_constant(ctx, "FORTNIGHTS"),
+            Err(Error::MemberNotFound { .. })
+        ));
+    })
+}
+
+#[test]
+fn test_debug_format() {
+    with_java_vm(|ctx| {
+        let o_string = JString::new(ctx, "debug");
+        let debug = format!("{:?}", o_string);
+        assert!(debug.starts_with("Object<Ljava/lang/String;>(0x"), "{debug}");
+        // only the debug-to-string feature calls toString()
+        #[cfg(not(feature = "debug-to-string"))]
+        assert!(!debug.contains("debug"));
+        #[cfg(feature = "debug-to-string")]
+        assert!(debug.ends_with(": debug)"), "{debug}");
+
+        let o_weak = o_string.downgrade_weak();
+        let debug = format!("{:?}", o_weak);
+        assert!(debug.starts_with("Object<Ljava/lang/String;>(0x"), "{debug}");
+        #[cfg(feature = "debug-to-string")]
+        assert!(debug.ends_with(": debug)"), "{debug}");
+
+        let c_string = LocalClass::<JString>::find_class(ctx).unwrap();
+        assert!(format!("{:?}", c_string).starts_with("Class<Ljava/lang/String;>(0x"));
+
+        assert_eq!(o_string.to_string(), "debug");
+    })
+}
+
+#[test]
+fn test_context_from_raw_env() {
+    with_java_vm(|ctx| {
+        let env_ctx = unsafe { Context::from_raw_env(ctx.as_raw()) };
+        assert_eq!(env_ctx.as_raw(), ctx.as_raw());
+
+        let o_string = JString::new(env_ctx, "env");
+        assert_eq!(o_string.get_string(ctx), "env");
+    })
+}
+
+#[test]
+fn test_box_int() {
+    with_java_vm(|ctx| {
+        let o_small = ctx.box_int(42).unwrap();
+        assert_eq!(o_small.int_value(ctx), 42);
+        assert!(o_small.is_same_object(ctx, &ctx.box_int(42).unwrap()));
+
+        let o_large = ctx.box_int(100_000).unwrap();
+        assert_eq!(o_large.int_value(ctx), 100_000);
+        assert_eq!(o_large.to_string(), "100000");
+
+        let from_thread = std::thread::spawn(|| Context::with_attached(|ctx| ctx.box_int(-7).unwrap().int_value(ctx)))
+            .join()
+            .unwrap();
+        assert_eq!(from_thread, -7);
+    })
+}
+
+#[test]
+fn test_class_reflection() {
+    define_java_class!(JavaInteger, "java.lang.Integer");
+
+    with_java_vm(|ctx| {
+        let class_of = |name: &std::ffi::CStr| unsafe { LocalObject::<JClass>::from_raw(ctx.find_class(name).unwrap()) };
+
+        let o_string = class_of(c"java/lang/String");
+        assert_eq!(o_string.get_name(ctx), "java.lang.String");
+        assert_eq!(o_string.get_simple_name(ctx), "String");
+        assert!(!o_string.is_interface(ctx));
+        assert!(!o_string.is_array(ctx));
+        assert!(!o_string.is_primitive(ctx));
+        assert_eq!(o_string.get_modifiers(ctx) & 0x0011, 0x0011);
+
+        let o_runnable = class_of(c"java/lang/Runnable");
+        assert!(o_runnable.is_interface(ctx));
+
+        let o_ints = class_of(c"[I");
+        assert_eq!(o_ints.get_name(ctx), "[I");
+        assert_eq!(o_ints.get_simple_name(ctx), "int[]");
+        assert!(o_ints.is_array(ctx));
+
+        let c_integer = LocalClass::<JavaInteger>::find_class(ctx).unwrap();
+        let o_int: LocalObject<JClass> = c_integer.get_field(ctx, "TYPE").unwrap();
+        assert_eq!(o_int.get_name(ctx), "int");
+        assert!(o_int.is_primitive(ctx));
+    })
+}
+
+#[test]
+fn test_invalid_member_name() {
+    with_java_vm(|ctx| {
+        let o_string = JString::new(ctx, "name");
+
+        let ret: Result<i32, _> = o_string.call_method(ctx, "len\0gth", NoArgs);
+        assert!(matches!(ret, Err(Error::InvalidName)));
+
+        let ret: Result<i32, _> = o_string.get_field(ctx, "has\0h");
+        assert!(matches!(ret, Err(Error::InvalidName)));
+
+        let ret: i32 = o_string.call_method(ctx, "length", NoArgs).unwrap();
+        assert_eq!(ret, 4);
+    })
+}
+
+#[test]
+fn test_future_get() {
+    define_java_class!(JavaRustFutures, "RustFutures");
+    define_java_class!(JavaIllegalStateException, "java.lang.IllegalStateException");
+    define_java_class!(JavaCancellationException, "java.util.concurrent.CancellationException");
+
+    with_java_vm(|ctx| {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustFutures",
+            r#"
+                import java.util.concurrent.CompletableFuture;
+                import java.util.concurrent.Future;
+
+                public class RustFutures {
+                    public static Future<String> done(String value) {
+                        return CompletableFuture.completedFuture(value);
+                    }
+
+                    public static Future<String> failed() {
+                        return CompletableFuture.failedFuture(new IllegalStateException("failed"));
+                    }
+
+                    public static Future<String> cancelled() {
+                        CompletableFuture<String> future = new CompletableFuture<>();
+                        future.cancel(false);
+                        return future;
+                    }
+
+                    public static Future<String> later() {
+                        return CompletableFuture.supplyAsync(() -> {
+                            try {
+                                Thread.sleep(50);
+                            } catch (InterruptedException e) {
+                                throw new RuntimeException(e);
+                            }
+                            return "later";
+                        });
+                    }
+                }
+            "#,
+        );
+
+        let c_futures: LocalClass<JavaRustFutures> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustFutures"))
+            .unwrap();
+
+        let o_done: LocalObject<JFuture<JString>> = c_futures.call_method(ctx, "done", "value").unwrap();
+        assert!(o_done.is_done(ctx));
+        assert_eq!(o_done.get(ctx).unwrap().unwrap().get_string(ctx), "value");
+
+        let o_null: LocalObject<JFuture<JString>> = c_futures.call_method(ctx, "done", Null::<JString>::new()).unwrap();
+        assert!(o_null.get(ctx).unwrap().is_none());
+
+        let o_later: LocalObject<JFuture<JString>> = c_futures.call_method(ctx, "later", NoArgs).unwrap();
+        assert_eq!(o_later.get(ctx).unwrap().unwrap().get_string(ctx), "later");
+        assert!(o_later.is_done(ctx));
+
+        let c_illegal_state = LocalClass::<JavaIllegalStateException>::find_class(ctx).unwrap();
+        let o_failed: LocalObject<JFuture<JString>> = c_futures.call_method(ctx, "failed", NoArgs).unwrap();
+        let err = o_failed.get(ctx).unwrap_err().into_throwable().unwrap();
+        assert!(err.is_instance_of(ctx, &c_illegal_state));
+
+        let c_cancellation = LocalClass::<JavaCancellationException>::find_class(ctx).unwrap();
+        let o_cancelled: LocalObject<JFuture<JString>> = c_futures.call_method(ctx, "cancelled", NoArgs).unwrap();
+        let err = o_cancelled.get(ctx).unwrap_err().into_throwable().unwrap();
+        assert!(err.is_instance_of(ctx, &c_cancellation));
+    })
+}
+
+#[test]
+fn test_list_element_type() {
+    define_java_class!(JavaArrayList, "java.util.ArrayList");
+    define_java_class!(JavaCollections, "java.util.Collections");
+
+    with_java_vm(|ctx| {
+        let c_list = LocalClass::<JList<JString>>::find_class(ctx).unwrap();
+        let o_array_list = LocalClass::<JavaArrayList>::find_class(ctx)
+            .unwrap()
+            .new_object(ctx, NoArgs)
+            .unwrap();
+        let o_list = unsafe { o_array_list.cast(ctx, &c_list).unwrap() };
+        assert!(o_list.is_empty(ctx));
+
+        assert!(o_list.add(ctx, Some(&JString::new(ctx, "first"))).unwrap());
+        assert!(o_list.add(ctx, None::<&LocalObject<JString>>).unwrap());
+        assert_eq!(o_list.size(ctx), 2);
+
+        let first: LocalObject<JString> = o_list.get(ctx, 0).unwrap().unwrap();
+        assert_eq!(first.get_string(ctx), "first");
+        assert!(o_list.get(ctx, 1).unwrap().is_none());
+        assert!(matches!(o_list.get(ctx, 2), Err(Error::Throwable(_))));
+
+        let o_empty: LocalObject<JList<JString>> = JavaCollections::call_static(ctx, "emptyList", NoArgs).unwrap();
+        assert!(o_empty.is_empty(ctx));
+        assert!(o_empty.add(ctx, Some(&first)).is_err());
+    })
+}
+
+#[test]
+fn test_primitive_array_args() {
+    define_java_class!(JavaRustArrayArgs, "RustArrayArgs");
+
+    with_java_vm(|ctx| {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustArrayArgs",
+            r#"
+                public class RustArrayArgs {
+                    public static String written = "";
+
+                    public static void write(byte[] data) {
+                        written = new String(data, java.nio.charset.StandardCharsets.UTF_8);
+                    }
+
+                    public static int sum(int[] values, int offset) {
+                        int sum = offset;
+                        for (int v : values) sum += v;
+                        return sum;
+                    }
+                }
+            "#,
+        );
+
+        let c_args: LocalClass<JavaRustArrayArgs> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustArrayArgs"))
+            .unwrap();
+
+        let o_bytes = LocalObject::<JByteArray>::new_primitive(ctx, 5).unwrap();
+        o_bytes.set_bytes_region(ctx, 0, b"bytes").unwrap();
+        c_args.call_method::<(), _>(ctx, "write", &o_bytes).unwrap();
+
+        let written: LocalObject<JString> = c_args.get_field(ctx, "written").unwrap();
+        assert_eq!(written.get_string(ctx), "bytes");
+
+        let o_ints = LocalObject::<JIntArray>::new_primitive(ctx, 3).unwrap();
+        o_ints.set_region(ctx, 0, &[1, 2, 3]).unwrap();
+        let sum: i32 = c_args.call_method(ctx, "sum", (&o_ints, 10)).unwrap();
+        assert_eq!(sum, 16);
+    })
+}
+
+#[test]
+fn test_panic_reporter() {
+    use std::sync::Mutex;
+
+    define_java_class!(JavaRustPanics, "RustPanics");
+
+    static REPORTED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    with_java_vm(|ctx| {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustPanics",
+            r#"
+                public class RustPanics {
+                    public static native int toByte(int value);
+
+                    public static String run(int value) {
+                        try {
+                            return "ok " + toByte(value);
+                        } catch (RuntimeException e) {
+                            return "thrown " + e.getMessage();
+                        }
+                    }
+                }
+            "#,
+        );
+
+        extern "C" fn to_byte<'ctx>(ctx: &'ctx Context, _: TrampolineClass<'ctx, JavaRustPanics>, value: i32) -> i32 {
+            typed_jni::catch_panic(ctx, -1, || {
+                if value < 0 {
+                    panic!("negative {value}");
+                }
+
+                u8::try_from(value).map(i32::from).expect("too large")
+            })
+        }
+
+        let c_panics: LocalClass<JavaRustPanics> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustPanics"))
+            .unwrap();
+
+        unsafe {
+            ctx.register_natives(c_panics.as_raw(), [(c"toByte", c"(I)I", to_byte as *const ())])
+                .unwrap()
+        }
+
+        let run = |value: i32| -> String {
+            let ret: LocalObject<JString> = c_panics.call_method(ctx, "run", value).unwrap();
+
+            ret.get_string(ctx)
+        };
+
+        typed_jni::set_panic_reporter(|ctx, message| {
+            // a reporter has a context to call into Java, e.g. a logger
+            let o_message = JString::new(ctx, message);
+
+            REPORTED.lock().unwrap().push(o_message.get_string(ctx));
+        });
+
+        assert_eq!(run(7), "ok 7");
+        assert_eq!(run(-3), "thrown negative -3");
+        assert!(run(300).starts_with("thrown too large"));
+        assert_eq!(REPORTED.lock().unwrap()[0], "negative -3");
+
+        // a panicking reporter must not prevent the panic from being thrown
+        typed_jni::set_panic_reporter(|_, _| panic!("reporter"));
+        assert_eq!(run(-1), "thrown negative -1");
+    })
+}
+
+#[test]
+fn test_global_object_args() {
+    with_java_vm(|ctx| {
+        let g_text = JString::new(ctx, "global").to_global().unwrap();
+        let o_prefix = JString::new(ctx, "a ");
+
+        let local_refs = typed_jni::debug_refs::outstanding_local_refs();
+        let compared: i32 = o_prefix.call_method(ctx, "compareTo", &g_text).unwrap();
+        assert!(compared < 0);
+        assert_eq!(typed_jni::debug_refs::outstanding_local_refs(), local_refs);
+
+        let o_joined: LocalObject<JString> = o_prefix.call_method(ctx, "concat", &g_text).unwrap();
+        assert_eq!(o_joined.get_string(ctx), "a global");
+
+        let w_text = g_text.downgrade_weak();
+        let upgraded = w_text.upgrade_global().unwrap();
+        let o_joined: LocalObject<JString> = o_prefix.call_method(ctx, "concat", &upgraded).unwrap();
+        assert_eq!(o_joined.get_string(ctx), "a global");
+    })
+}
+
+#[test]
+fn test_try_block() {
+    define_java_class!(JavaInteger, "java.lang.Integer");
+
+    with_java_vm(|ctx| {
+        let sum = |a: &'static str, b: &'static str| {
+            ctx.try_block(|ctx| {
+                let a: i32 = JavaInteger::call_static(ctx, "parseInt", a)?;
+                let b: i32 = JavaInteger::call_static(ctx, "parseInt", b)?;
+
+                Ok(a + b)
+            })
+        };
+
+        assert_eq!(sum("1", "2").unwrap(), 3);
+
+        let err = sum("1", "two").unwrap_err();
+        assert!(err.to_string().contains("NumberFormatException"));
+        assert!(!ctx.exception_check());
+    })
+}
+
+#[test]
+fn test_raw_function_table() {
+    with_java_vm(|ctx| {
+        let env = ctx.as_raw();
+
+        let version = unsafe { (**env).GetVersion.unwrap()(env) };
+        assert!(version >= typed_jni::sys::JNI_VERSION_1_8 as i32);
+
+        // exceptions raised through the raw table are left for the caller
+        let class = ctx.find_class(c"java/lang/IllegalStateException").unwrap();
+        unsafe { (**env).ThrowNew.unwrap()(env, *class.as_raw(), c"raw".as_ptr()) };
+        assert!(ctx.exception_check());
+        ctx.exception_clear();
+        assert!(!ctx.exception_check());
+    })
+}
+
+#[test]
+fn test_number_values() {
+    with_java_vm(|ctx| {
+        let c_number = LocalClass::<JNumber>::find_class(ctx).unwrap();
+
+        define_java_class!(JavaLong, "java.lang.Long");
+
+        let c_long = LocalClass::<JavaLong>::find_class(ctx).unwrap();
+        let o_long: LocalObject<JavaLong> = c_long.call_method(ctx, "valueOf", 1i64 << 40).unwrap();
+        let o_long = unsafe { o_long.cast(ctx, &c_number).unwrap() };
+        assert!(o_long.is_instance_of(ctx, &c_number));
+
+        assert_eq!(o_long.double_value(ctx), (1i64 << 40) as f64);
+        assert_eq!(o_long.long_value(ctx), 1i64 << 40);
+        assert_eq!(o_long.float_value(ctx), (1i64 << 40) as f32);
+        assert_eq!(o_long.int_value(ctx), 0);
+
+        let o_int = ctx.box_int(-7).unwrap();
+        let o_int = unsafe { o_int.cast(ctx, &c_number).unwrap() };
+        assert_eq!(o_int.double_value(ctx), -7.0);
+        assert_eq!(o_int.long_value(ctx), -7);
+    })
+}
+
+#[test]
+fn test_runnable_run() {
+    with_java_vm(|ctx| {
+        let c_thread = LocalClass::<JThread>::find_class(ctx).unwrap();
+        let c_runnable = LocalClass::<JRunnable>::find_class(ctx).unwrap();
+
+        // a thread without a target runs nothing
+        let o_thread = c_thread.new_object(ctx, NoArgs).unwrap();
+        let o_runnable = unsafe { o_thread.cast(ctx, &c_runnable).unwrap() };
+
+        o_runnable.run(ctx).unwrap();
+    })
+}
+
+#[test]
+fn test_member_cache_concurrent_lookup() {
+    define_java_class!(JavaStringBuilder, "java.lang.StringBuilder");
+    define_java_class!(JavaStringBuffer, "java.lang.StringBuffer");
+
+    with_java_vm(|_| {
+        // the member cache is per thread, so classes sharing method names and signatures must
+        // never observe each other's ids no matter how many threads resolve them at once
+        let workers = (0..8)
+            .map(|n| {
+                std::thread::spawn(move || {
+                    Context::with_attached(|ctx| {
+                        let c_builder = LocalClass::<JavaStringBuilder>::find_class(ctx).unwrap();
+                        let c_buffer = LocalClass::<JavaStringBuffer>::find_class(ctx).unwrap();
+
+                        for i in 0..200 {
+                            let o_string = JString::new(ctx, &"x".repeat(n + i % 7));
+                            let o_builder = c_builder.new_object(ctx, "ab").unwrap();
+                            let o_buffer = c_buffer.new_object(ctx, "abcd").unwrap();
+
+                            assert_eq!(
+                                o_string.call_method::<i32, _>(ctx, "length", NoArgs).unwrap(),
+                                (n + i % 7) as i32
+                            );
+                            assert_eq!(o_builder.call_method::<i32, _>(ctx, "length", NoArgs).unwrap(), 2);
+                            assert_eq!(o_buffer.call_method::<i32, _>(ctx, "length", NoArgs).unwrap(), 4);
+                        }
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for worker in workers {
+            worker.join().unwrap();
+        }
+    })
+}
+
+#[test]
+fn test_find_member_uncached() {
+    with_java_vm(|ctx| {
+        let c_string = LocalClass::<JString>::find_class(ctx).unwrap();
+        let o_string = JString::new(ctx, "uncached");
+
+        let m_length = c_string.find_method_uncached::<false, NoArgs, i32>(ctx, "length").unwrap();
+        assert_eq!(
+            unsafe { ctx.call_method::<false, _, _, i32>(o_string.as_raw(), m_length, []).unwrap() },
+            8
+        );
+        assert_eq!(o_string.call_method::<i32, _>(ctx, "length", NoArgs).unwrap(), 8);
+
+        define_java_class!(JavaInteger, "java.lang.Integer");
+        let c_integer = LocalClass::<JavaInteger>::find_class(ctx).unwrap();
+        let f_max = c_integer.find_field_uncached::<true, i32>(ctx, "MAX_VALUE").unwrap();
+        assert_eq!(unsafe { ctx.get_field::<true, _, i32>(c_integer.as_raw(), f_max) }, i32::MAX);
+
+        assert!(c_integer.find_field_uncached::<true, i64>(ctx, "MAX_VALUE").is_err());
+    })
+}
+
+#[test]
+fn test_string_region_checked() {
+    with_java_vm(|ctx| {
+        let o_str = JString::new(ctx, "h\u{e9}llo");
+        assert_eq!(o_str.length(ctx), 5);
+
+        let mut buf = [0u16; 3];
+        o_str.get_region_checked(ctx, 1, &mut buf).unwrap();
+        assert_eq!(String::from_utf16(&buf).unwrap(), "\u{e9}ll");
+
+        let mut buf = [0u16; 2];
+        o_str.get_region_checked(ctx, 3, &mut buf).unwrap();
+        assert_eq!(String::from_utf16(&buf).unwrap(), "lo");
+        o_str.get_region_checked(ctx, 5, &mut []).unwrap();
+
+        assert_eq!(
+            o_str.get_region_checked(ctx, 4, &mut buf),
+            Err(IndexOutOfBounds { index: 6, length: 5 })
+        );
+        assert_eq!(
+            o_str.get_region_checked(ctx, -1, &mut buf),
+            Err(IndexOutOfBounds { index: -1, length: 5 })
+        );
+        assert_eq!(
+            o_str.get_region_checked(ctx, 6, &mut []),
+            Err(IndexOutOfBounds { index: 6, length: 5 })
+        );
+
+        // the unchecked variant leaves the check to the vm
+        let thrown = o_str.get_region(ctx, 4, &mut buf).unwrap_err().into_throwable().unwrap();
+        define_java_class!(JavaStringIndexOutOfBounds, "java.lang.StringIndexOutOfBoundsException");
+        let c_bounds = LocalClass::<JavaStringIndexOutOfBounds>::find_class(ctx).unwrap();
+        assert!(thrown.is_instance_of(ctx, &c_bounds));
+    })
+}
+
+#[test]
+fn test_raw_round_trip() {
+    with_java_vm(|ctx| {
+        let o_str = JString::new(ctx, "raw");
+        let raw = o_str.into_raw().into_raw();
+        let o_str = unsafe { LocalObject::<JString>::from_raw(Local::from_raw(raw)) };
+        assert_eq!(o_str.get_string(ctx), "raw");
+
+        let o_bytes = LocalObject::<JByteArray>::new_primitive(ctx, 3).unwrap();
+        let o_bytes = unsafe { LocalObject::<JByteArray>::from_raw(o_bytes.into_raw()) };
+        assert_eq!(o_bytes.length(ctx), 3);
+
+        let g_str = o_str.to_global().unwrap();
+        let raw = g_str.into_raw().into_raw();
+        let g_str = unsafe { Object::<JString, typed_jni::Global>::from_raw(typed_jni::Global::from_raw(raw)) };
+        assert_eq!(g_str.get_string(ctx), "raw");
+
+        // a trampoline reference borrows the handle without owning it
+        let t_str = unsafe { TrampolineObject::<JString>::from_raw(typed_jni::Trampoline::from_raw(*o_str.as_raw().as_raw())) };
+        assert_eq!(t_str.get_string(ctx), "raw");
+        assert_eq!(o_str.get_string(ctx), "raw");
+    })
+}
+
+#[test]
+fn test_trampoline_result_return() {
+    with_java_vm(|ctx| {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustFallible",
+            r#"
+                public class RustFallible {
+                    private static native int utfLength(String value, int start);
+                    private static native String suffix(String value, int start);
+                    private static native void lookup(String name);
+
+                    public static String call() {
+                        String ret = utfLength("h\u00e9llo", 1) + " " + suffix("hello", 3);
+                        try {
+                            utfLength("hello", 9);
+                            return ret + " not thrown";
+                        } catch (StringIndexOutOfBoundsException e) {
+                            ret += " thrown";
+                        }
+                        try {
+                            lookup("a\0b");
+                            ret += " not thrown";
+                        } catch (RuntimeException e) {
+                            ret += " " + e.getMessage();
+                        }
+                        try {
+                            return ret + " " + suffix("hello", -1);
+                        } catch (StringIndexOutOfBoundsException e) {
+                            return ret + " thrown";
+                        }
+                    }
+                }
+            "#,
+        );
+
+        define_java_class!(JavaRustFallible, "RustFallible");
+
+        extern "C" fn utf_length<'ctx>(
+            ctx: &'ctx Context,
+            _: TrampolineClass<'ctx, JavaRustFallible>,
+            value: TrampolineObject<'ctx, JString>,
+            start: i32,
+        ) -> <Result<i32, LocalObject<'ctx, Throwable>> as TrampolineReturn>::Raw {
+            let length = || -> Result<i32, LocalObject<'ctx, Throwable>> {
+                let mut buf = [0u8; 16];
+
+                Ok(value.copy_utf_region(ctx, start, &mut buf)? as i32)
+            };
+
+            length().into_return()
+        }
+
+        extern "C" fn suffix<'ctx>(
+            ctx: &'ctx Context,
+            _: TrampolineClass<'ctx, JavaRustFallible>,
+            value: TrampolineObject<'ctx, JString>,
+            start: i32,
+        ) -> <Result<Option<LocalObject<'ctx, JString>>, Error<'ctx>> as TrampolineReturn>::Raw {
+            let suffix = || -> Result<Option<LocalObject<'ctx, JString>>, Error<'ctx>> {
+                let mut buf = vec![0u16; (value.length(ctx) - start.max(0)) as usize];
+                value.get_region(ctx, start, &mut buf)?;
+
+                Ok(Some(JString::new(ctx, &String::from_utf16(&buf).unwrap())))
+            };
+
+            suffix().into_return()
+        }
+
+        extern "C" fn lookup<'ctx>(
+            ctx: &'ctx Context,
+            _: TrampolineClass<'ctx, JavaRustFallible>,
+            name: TrampolineObject<'ctx, JString>,
+        ) -> <Result<(), Error<'ctx>> as TrampolineReturn>::Raw {
+            static CLASSES: ClassCache = ClassCache::new();
+
+            CLASSES.get_or_load(ctx, &name.get_string(ctx)).map(|_| ()).into_return()
+        }
+
+        let c_fallible: LocalClass<JavaRustFallible> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustFallible"))
+            .unwrap();
+
+        unsafe {
+            ctx.register_natives(
+                c_fallible.as_raw(),
+                [
+                    (c"utfLength", c"(Ljava/lang/String;I)I", utf_length as *const ()),
+                    (c"suffix", c"(Ljava/lang/String;I)Ljava/lang/String;", suffix as *const ()),
+                    (c"lookup", c"(Ljava/lang/String;)V", lookup as *const ()),
+                ],
+            )
+            .unwrap()
+        }
+
+        let ret: LocalObject<JString> = c_fallible.call_method(ctx, "call", NoArgs).unwrap();
+        assert_eq!(ret.get_string(ctx), "5 lo thrown name contains an interior NUL thrown");
+    })
+}
+
+#[test]
+fn test_object_upcast() {
+    with_java_vm(|ctx| {
+        let c_object = LocalClass::<JObject>::find_class(ctx).unwrap();
+
+        let o_first = JString::new(ctx, "same").upcast::<JObject>();
+        let o_second = JString::new(ctx, "same");
+        assert!(o_first.is_instance_of(ctx, &c_object));
+
+        assert!(!o_first.is_same_object(ctx, o_second.as_object()));
+        assert!(o_first.equals(ctx, Some(o_second.as_object())));
+        assert!(!o_first.equals(ctx, None::<&LocalObject<JObject>>));
+        assert_eq!(o_first.hash_code(ctx), o_second.as_object().hash_code(ctx));
+
+        // elements of an Object[] can be of any type
+        let o_three = ctx.box_int(3).unwrap().upcast::<JObject>();
+        let o_array = LocalObject::<Array<JObject>>::new(ctx, 2, &c_object).unwrap();
+        o_array.set_element(ctx, 0, Some(&o_first)).unwrap();
+        o_array.set_element(ctx, 1, Some(&o_three)).unwrap();
+
+        let o_element = o_array.get_element(ctx, 1).unwrap().unwrap();
+        assert!(o_element.equals(ctx, Some(ctx.box_int(3).unwrap().as_object())));
+        assert!(!o_element.equals(ctx, Some(&o_first)));
+    })
+}
+
+#[test]
+fn test_with_current_detached() {
+    with_java_vm(|_| {
+        assert_eq!(
+            Context::with_current(|ctx| JString::new(ctx, "attached").get_string(ctx)).unwrap(),
+            "attached"
+        );
+        assert_eq!(
+            unsafe { Context::with_current_unchecked(|ctx| JString::new(ctx, "unchecked").get_string(ctx)) },
+            "unchecked"
+        );
+
+        let detached = std::thread::spawn(|| Context::with_current(|_| ()).map_err(|e| e.to_string()))
+            .join()
+            .unwrap();
+        assert_eq!(detached, Err("current thread is not attached to a vm".to_string()));
+    })
+}
+
+#[test]
+fn test_time_conversion() {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    with_java_vm(|ctx| {
+        let now = SystemTime::now();
+        let o_now = ctx.new_instant(now).unwrap();
+        assert_eq!(o_now.to_system_time(ctx), Some(now));
+
+        // before the epoch the nanoseconds still count forward from the second
+        let before = UNIX_EPOCH - Duration::new(1, 250_000_000);
+        let o_before = ctx.new_instant(before).unwrap();
+        assert_eq!(o_before.call_method::<i64, _>(ctx, "getEpochSecond", NoArgs).unwrap(), -2);
+        assert_eq!(o_before.call_method::<i32, _>(ctx, "getNano", NoArgs).unwrap(), 750_000_000);
+        assert_eq!(o_before.to_system_time(ctx), Some(before));
+
+        let o_string: LocalObject<JString> = o_before.call_method(ctx, "toString", NoArgs).unwrap();
+        assert_eq!(o_string.get_string(ctx), "1969-12-31T23:59:58.750Z");
+
+        let duration = Duration::new(90, 1);
+        let o_duration = ctx.new_duration(duration).unwrap();
+        assert_eq!(o_duration.to_duration(ctx), Some(duration));
+
+        let o_negated: LocalObject<JDuration> = o_duration.call_method(ctx, "negated", NoArgs).unwrap();
+        assert_eq!(o_negated.to_duration(ctx), None);
+
+        let o_max = ctx.new_duration(Duration::MAX).unwrap();
+        assert_eq!(o_max.to_duration(ctx), Some(Duration::new(i64::MAX as u64, 999_999_999)));
+
+        assert!(ctx.new_instant(UNIX_EPOCH + Duration::from_secs(i64::MAX as u64)).is_err());
+    })
+}
+
+#[test]
+fn test_read_fields_with_conversion() {
+    const FIELDS: usize = 50;
+
+    struct Wide {
+        names: Vec<&'static str>,
+        values: Vec<Option<String>>,
+        max_local_refs: usize,
+    }
+
+    impl FieldLayout for Wide {
+        fn read_fields<'ctx, R: StrongRef>(&mut self, fields: &mut FieldReader<'_, 'ctx, R>) -> Result<(), Error<'ctx>> {
+            let local_refs = typed_jni::debug_refs::outstanding_local_refs();
+
+            for name in &self.names {
+                let value = fields.read_with(name, |ctx, s: Option<LocalObject<JString>>| s.map(|s| s.get_string(ctx)))?;
+                self.values.push(value);
+
+                let outstanding = typed_jni::debug_refs::outstanding_local_refs() - local_refs;
+                self.max_local_refs = self.max_local_refs.max(outstanding);
+            }
+
+            Ok(())
+        }
+    }
+
+    with_java_vm(|ctx| {
+        let declarations = (0..FIELDS)
+            .map(|i| {
+                format!(
+                    "public String f{i} = {};",
+                    if i % 7 == 0 { "null".to_string() } else { format!("\"v{i}\"") }
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let loader = compile_file_and_load_classes(ctx, "RustWide", &format!("public class RustWide {{ {declarations} }}"));
+
+        let c_wide: LocalClass<JavaObject> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustWide"))
+            .unwrap();
+        let o_wide = c_wide.new_object(ctx, NoArgs).unwrap();
+
+        let mut wide = Wide {
+            names: (0..FIELDS).map(|i| &*format!("f{i}").leak()).collect(),
+            values: Vec::new(),
+            max_local_refs: 0,
+        };
+        o_wide.read_fields_into(ctx, &mut wide).unwrap();
+
+        assert_eq!(wide.max_local_refs, 0);
+        assert_eq!(wide.values.len(), FIELDS);
+        for (i, value) in wide.values.iter().enumerate() {
+            assert_eq!(value.as_deref(), (i % 7 != 0).then(|| format!("v{i}")).as_deref());
+        }
+    })
+}
+
+#[test]
+fn test_builtin_exceptions() {
+    use typed_jni::exceptions::{JIllegalStateException, JRuntimeException};
+
+    with_java_vm(|ctx| {
+        let c_state = JIllegalStateException::class(ctx).unwrap();
+        let c_runtime = JRuntimeException::class(ctx).unwrap();
+
+        unsafe { ctx.throw_new(c_state.as_raw(), "closed") };
+        assert!(ctx.exception_check());
+        ctx.exception_clear();
+
+        let o_state = c_state.new_object(ctx, "state").unwrap();
+        let o_runtime: LocalObject<JRuntimeException> = o_state.upcast();
+        assert!(o_runtime.is_instance_of(ctx, &c_runtime));
+
+        let o_throwable: LocalObject<Throwable> = o_runtime.upcast();
+        assert_eq!(o_throwable.to_string(), "java.lang.IllegalStateException: state");
+    })
+}
+
+#[test]
+fn test_static_field_by_type() {
+    define_java_class!(JavaInteger, "java.lang.Integer");
+    define_java_class!(JavaComparator, "java.util.Comparator");
+    define_java_class!(JavaString, "java.lang.String");
+
+    with_java_vm(|ctx| {
+        let max: i32 = JavaInteger::get_static(ctx, "MAX_VALUE").unwrap();
+        assert_eq!(max, i32::MAX);
+
+        let size: i32 = JavaInteger::get_static(ctx, "SIZE").unwrap();
+        assert_eq!(size, 32);
+
+        let order: Option<LocalObject<JavaComparator>> = JavaString::get_static(ctx, "CASE_INSENSITIVE_ORDER").unwrap();
+        assert!(order.is_some());
+
+        assert!(JavaInteger::get_static::<i64>(ctx, "MAX_VALUE").is_err());
+        assert!(JavaInteger::set_static(ctx, "MISSING", 1).is_err());
+    })
+}
+
+#[test]
+fn test_iterator_adapter() {
+    define_java_class!(JavaArrayList, "java.util.ArrayList");
+
+    with_java_vm(|ctx| {
+        let c_list = LocalClass::<JList<JString>>::find_class(ctx).unwrap();
+        let o_array_list = LocalClass::<JavaArrayList>::find_class(ctx)
+            .unwrap()
+            .new_object(ctx, NoArgs)
+            .unwrap();
+        let o_list = unsafe { o_array_list.cast(ctx, &c_list).unwrap() };
+
+        for value in ["a", "b", "c"] {
+            o_list.add(ctx, Some(&JString::new(ctx, value))).unwrap();
+        }
+        o_list.add(ctx, None::<&LocalObject<JString>>).unwrap();
+
+        let o_iterator: LocalObject<JIterator<JString>> = o_list.call_method(ctx, "iterator", NoArgs).unwrap();
+        let local_refs = typed_jni::debug_refs::outstanding_local_refs();
+        let values = o_iterator
+            .iter(ctx)
+            .map(|e| e.unwrap().map(|s| s.get_string(ctx)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            [Some("a".to_string()), Some("b".to_string()), Some("c".to_string()), None]
+        );
+        assert_eq!(typed_jni::debug_refs::outstanding_local_refs(), local_refs);
+
+        assert!(!o_iterator.has_next(ctx).unwrap());
+        assert!(o_iterator.next(ctx).is_err());
+
+        // modifying the list while iterating is thrown from next
+        let o_iterator: LocalObject<JIterator<JString>> = o_list.call_method(ctx, "iterator", NoArgs).unwrap();
+        let mut iter = o_iterator.iter(ctx);
+        assert!(iter.next().unwrap().is_ok());
+        o_list.add(ctx, Some(&JString::new(ctx, "d"))).unwrap();
+
+        let thrown = iter.next().unwrap().unwrap_err().into_throwable().unwrap();
+        assert!(thrown.to_string().starts_with("java.util.ConcurrentModificationException"));
+        assert!(iter.next().is_none());
+    })
+}
+
+#[test]
+fn test_warm_members() {
+    with_java_vm(|ctx| {
+        define_java_class!(JavaInteger, "java.lang.Integer");
+        let c_integer = LocalClass::<JavaInteger>::find_class(ctx).unwrap();
+
+        c_integer
+            .warm_method::<true, i32, LocalObject<JavaInteger>>(ctx, "valueOf")
+            .unwrap();
+        c_integer.warm_method::<false, NoArgs, i32>(ctx, "intValue").unwrap();
+        c_integer.warm_field::<true, i32>(ctx, "MAX_VALUE").unwrap();
+
+        let o_integer: LocalObject<JavaInteger> = c_integer.call_method(ctx, "valueOf", 42i32).unwrap();
+        assert!(typed_jni::debug_cache::last_lookup_was_hit());
+        assert_eq!(o_integer.call_method::<i32, _>(ctx, "intValue", NoArgs).unwrap(), 42);
+        assert!(typed_jni::debug_cache::last_lookup_was_hit());
+        assert_eq!(c_integer.get_field::<i32>(ctx, "MAX_VALUE").unwrap(), i32::MAX);
+        assert!(typed_jni::debug_cache::last_lookup_was_hit());
+
+        // a member missing at startup is reported by the warm-up instead of the first call
+        assert!(c_integer.warm_method::<false, NoArgs, i32>(ctx, "missing").is_err());
+        assert!(c_integer.warm_field::<true, i64>(ctx, "MAX_VALUE").is_err());
+    })
+}
+
+#[test]
+fn test_cache_warm() {
+    use typed_jni::{
+        cache::{self, MemberKind, WarmEntry},
+        Signature, Type,
+    };
+
+    define_java_class!(JavaInteger, "java.lang.Integer");
+
+    const HOT: &[WarmEntry] = &[
+        WarmEntry {
+            class: "java.lang.Integer",
+            name: "valueOf",
+            kind: MemberKind::StaticMethod,
+            args: &[Signature::Int],
+            ret: JavaInteger::SIGNATURE,
+        },
+        WarmEntry {
+            class: "java.lang.String",
+            name: "concat",
+            kind: MemberKind::Method,
+            args: &[JString::SIGNATURE],
+            ret: JString::SIGNATURE,
+        },
+        WarmEntry {
+            class: "java.lang.Integer",
+            name: "MAX_VALUE",
+            kind: MemberKind::StaticField,
+            args: &[],
+            ret: Signature::Int,
+        },
+    ];
+
+    with_java_vm(|_| {
+        // a fresh thread starts with an empty cache
+        std::thread::spawn(|| {
+            Context::with_attached(|ctx| {
+                cache::warm(ctx, HOT).unwrap();
+
+                let c_integer = LocalClass::<JavaInteger>::find_class(ctx).unwrap();
+                let o_integer: Option<LocalObject<JavaInteger>> = c_integer.call_method(ctx, "valueOf", 42i32).unwrap();
+                assert!(typed_jni::debug_cache::last_lookup_was_hit());
+                assert!(o_integer.is_some());
+
+                let o_hello = JString::new(ctx, "hello");
+                let o_world = JString::new(ctx, " world").to_global().unwrap();
+                let concat: LocalObject<JString> = o_hello.call_method(ctx, "concat", &o_world).unwrap();
+                assert!(typed_jni::debug_cache::last_lookup_was_hit());
+                assert_eq!(concat.get_string(ctx), "hello world");
+
+                assert_eq!(c_integer.get_field::<i32>(ctx, "MAX_VALUE").unwrap(), i32::MAX);
+                assert!(typed_jni::debug_cache::last_lookup_was_hit());
+
+                let missing = WarmEntry {
+                    class: "java.lang.Integer",
+                    name: "missing",
+                    kind: MemberKind::Method,
+                    args: &[],
+                    ret: Signature::Void,
+                };
+                assert!(cache::warm(ctx, &[missing]).is_err());
+            })
+        })
+        .join()
+        .unwrap();
+    })
+}
+
+#[test]
+fn test_attach_vm_twice() {
+    with_java_vm(|ctx| {
+        let env = ctx.as_raw();
+        let mut vm = core::ptr::null_mut();
+        unsafe { (**env).GetJavaVM.unwrap()(env, &mut vm) };
+
+        typed_jni::attach_vm(vm).unwrap();
+        typed_jni::attach_vm(vm).unwrap();
+
+        let other = core::ptr::NonNull::<typed_jni::sys::JavaVM>::dangling().as_ptr();
+        assert_eq!(typed_jni::attach_vm(other), Err(VmError(typed_jni::sys::JNI_EEXIST)));
+
+        // the attached vm is kept, references still drop through it
+        let _ = JString::new(ctx, "still attached");
+    })
+}
+
+#[test]
+fn test_byte_array_read_all_fast() {
+    with_java_vm(|ctx| {
+        let data = (0..4 * 1024 * 1024).map(|i| (i * 31 % 251) as u8).collect::<Vec<_>>();
+
+        let array = LocalObject::<JByteArray>::new_primitive(ctx, data.len() as _).unwrap();
+        array.set_bytes_region(ctx, 0, &data).unwrap();
+
+        assert_eq!(array.read_all_fast(ctx), data);
+
+        // the region fallback reads the same bytes
+        let mut region = vec![0u8; data.len()];
+        array.get_bytes_region(ctx, 0, &mut region).unwrap();
+        assert_eq!(region, data);
+
+        let empty = LocalObject::<JByteArray>::new_primitive(ctx, 0).unwrap();
+        assert!(empty.read_all_fast(ctx).is_empty());
+    })
+}
+
+#[test]
+fn test_member_cache_hit() {
+    with_java_vm(|ctx| {
+        let o_string = JString::new(ctx, "cached");
+
+        o_string.call_method::<bool, _>(ctx, "isBlank", NoArgs).unwrap();
+        assert!(!typed_jni::debug_cache::last_lookup_was_hit());
+
+        o_string.call_method::<bool, _>(ctx, "isBlank", NoArgs).unwrap();
+        assert!(typed_jni::debug_cache::last_lookup_was_hit());
+
+        // a different return type is a different signature and a separate entry
+        assert!(o_string.call_method::<i32, _>(ctx, "isBlank", NoArgs).is_err());
+        assert!(!typed_jni::debug_cache::last_lookup_was_hit());
+    })
+}
+
+#[test]
+fn test_array_clone() {
+    with_java_vm(|ctx| {
+        let original = LocalObject::<JByteArray>::new_primitive(ctx, 4).unwrap();
+        original.set_bytes_region(ctx, 0, &[1, 2, 3, 4]).unwrap();
+
+        let cloned = original.clone_array(ctx).unwrap();
+        assert!(!ctx.is_same_object(Some(original.as_raw()), Some(cloned.as_raw())));
+        cloned.set_bytes_region(ctx, 0, &[9, 9]).unwrap();
+
+        assert_eq!(original.read_all_fast(ctx), [1, 2, 3, 4]);
+        assert_eq!(cloned.read_all_fast(ctx), [9, 9, 3, 4]);
+
+        // arrays are objects and pass where one is expected
+        let o_array = cloned.upcast::<JObject>();
+        assert!(o_array.equals(ctx, Some(&o_array)));
+        assert!(!o_array.equals(ctx, Some(original.as_object())));
+    })
+}
+
+#[test]
+fn test_member_not_found_diagnostics() {
+    with_java_vm(|ctx| {
+        let o_string = JString::new(ctx, "diagnostics");
+
+        match o_string.call_method::<(), _>(ctx, "missing", 1i32) {
+            Err(Error::MemberNotFound {
+                class,
+                name,
+                signature,
+                throwable,
+            }) => {
+                assert_eq!(class, "java.lang.String");
+                assert_eq!(name, "missing");
+                assert_eq!(signature, "(I)V");
+                assert!(throwable.to_string().contains("NoSuchMethodError"));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        define_java_class!(JavaInteger, "java.lang.Integer");
+        let c_integer = LocalClass::<JavaInteger>::find_class(ctx).unwrap();
+        let err = c_integer.get_field::<i64>(ctx, "MAX_VALUE").unwrap_err();
+        assert_eq!(err.to_string(), "java.lang.Integer has no member MAX_VALUE J");
+        assert!(err.throwable().is_some());
+    })
+}
+
+#[test]
+fn test_string_array_round_trip() {
+    with_java_vm(|ctx| {
+        let o_array = ctx.new_string_array(&["a", "", "\u{4f60}\u{597d}", "nul\0inside"]).unwrap();
+        assert_eq!(o_array.length(ctx), 4);
+
+        define_java_class!(JavaArrays, "java.util.Arrays");
+        let c_arrays = LocalClass::<JavaArrays>::find_class(ctx).unwrap();
+        // String[] is an Object[] in Java
+        let o_objects = unsafe { LocalObject::<Array<JObject>>::from_raw(o_array.clone().into_raw()) };
+        let o_joined: LocalObject<JString> = c_arrays.call_method(ctx, "toString", &o_objects).unwrap();
+        assert_eq!(o_joined.get_string(ctx), "[a, , \u{4f60}\u{597d}, nul\0inside]");
+
+        o_array.set_element::<Local>(ctx, 1, None).unwrap();
+        assert_eq!(
+            o_array.to_string_vec(ctx),
+            [
+                Some("a".to_string()),
+                None,
+                Some("\u{4f60}\u{597d}".to_string()),
+                Some("nul\0inside".to_string())
+            ]
+        );
+
+        // one element at a time stays well within the local reference table
+        let strings = (0..10_000).map(|i| i.to_string()).collect::<Vec<_>>();
+        let o_large = ctx
+            .new_string_array(&strings.iter().map(String::as_str).collect::<Vec<_>>())
+            .unwrap();
+        assert_eq!(
+            o_large.to_string_vec(ctx).into_iter().map(Option::unwrap).collect::<Vec<_>>(),
+            strings
+        );
+    })
+}
+
+#[test]
+fn test_call_method_id() {
+    with_java_vm(|ctx| {
+        define_java_class!(JavaInteger, "java.lang.Integer");
+        let c_integer = LocalClass::<JavaInteger>::find_class(ctx).unwrap();
+
+        let m_value_of = c_integer
+            .find_method_uncached::<true, i32, LocalObject<JavaInteger>>(ctx, "valueOf")
+            .unwrap();
+        let m_int_value = c_integer.find_method_uncached::<false, NoArgs, i32>(ctx, "intValue").unwrap();
+
+        let o_integer: LocalObject<JavaInteger> = unsafe { c_integer.call_method_id(ctx, m_value_of, 7i32) }.unwrap();
+        let value: i32 = unsafe { o_integer.call_method_id(ctx, m_int_value, NoArgs) }.unwrap();
+        assert_eq!(value, 7);
+    })
+}
+
+#[test]
+fn test_char_sequence_upcast() {
+    with_java_vm(|ctx| {
+        let o_string = JString::new(ctx, "sequence");
+        let o_other = JString::new(ctx, "sequence");
+
+        let o_sequence: LocalObject<JCharSequence> = o_other.upcast();
+        assert_eq!(o_sequence.get_string(ctx).unwrap(), "sequence");
+
+        // String.contentEquals(CharSequence)
+        assert!(o_string.call_method::<bool, _>(ctx, "contentEquals", &o_sequence).unwrap());
+
+        define_java_class!(JavaStringBuilder, "java.lang.StringBuilder");
+        unsafe impl Extends<JCharSequence> for JavaStringBuilder {}
+
+        let o_builder = LocalClass::<JavaStringBuilder>::find_class(ctx)
+            .unwrap()
+            .new_object(ctx, "built")
+            .unwrap();
+        assert_eq!(o_builder.upcast::<JCharSequence>().get_string(ctx).unwrap(), "built");
+    })
+}
+
+#[test]
+fn test_object_get_class() {
+    with_java_vm(|ctx| {
+        let c_string = LocalClass::<JString>::find_class(ctx).unwrap();
+
+        let o_string = JString::new(ctx, "runtime");
+        let c_runtime: LocalClass<JString> = o_string.get_class(ctx);
+        assert!(c_runtime.is_same_object(ctx, &c_string));
+
+        // the runtime class, not the static type
+        let o_object = JString::new(ctx, "erased").upcast::<JObject>();
+        let c_object: LocalClass<JObject> = o_object.get_class(ctx);
+        assert!(c_object.is_same_object(ctx, &c_string));
+        assert!(o_object.is_instance_of(ctx, &c_object));
+    })
+}
+
+#[test]
+fn test_set_contains() {
+    with_java_vm(|ctx| {
+        define_java_class!(JavaHashSet, "java.util.HashSet");
+        let o_set = LocalClass::<JavaHashSet>::find_class(ctx)
+            .unwrap()
+            .new_object(ctx, NoArgs)
+            .unwrap();
+        let o_set = unsafe { LocalObject::<JSet<JString>>::from_raw(o_set.into_raw()) };
+        assert!(o_set.is_empty(ctx));
+
+        for value in ["read", "write", "read"] {
+            o_set.add(ctx, Some(&JString::new(ctx, value))).unwrap();
+        }
+        assert_eq!(o_set.size(ctx), 2);
+
+        // equals, not identity
+        assert!(o_set.contains(ctx, Some(&JString::new(ctx, "read"))).unwrap());
+        assert!(!o_set.contains(ctx, Some(&JString::new(ctx, "exec"))).unwrap());
+        assert!(!o_set.contains::<Local>(ctx, None).unwrap());
+
+        let o_iterator = o_set.iterator(ctx).unwrap();
+        let mut values = o_iterator
+            .iter(ctx)
+            .map(|e| e.unwrap().unwrap().get_string(ctx))
+            .collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, ["read", "write"]);
+    })
+}
+
+#[test]
+fn test_rethrow_original() {
+    with_java_vm(|ctx| {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustRethrow",
+            r#"
+                public class RustRethrow {
+                    static final IllegalStateException ORIGINAL = new IllegalStateException("original");
+
+                    private static native void relay(Runnable runnable);
+
+                    public static String call() {
+                        try {
+                            relay(() -> { throw ORIGINAL; });
+                            return "not thrown";
+                        } catch (IllegalStateException e) {
+                            return e == ORIGINAL ? "same" : "different";
+                        }
+                    }
+                }
+            "#,
+        );
+
+        define_java_class!(JavaRustRethrow, "RustRethrow");
+
+        extern "C" fn relay<'ctx>(
+            ctx: &'ctx Context,
+            _: TrampolineClass<'ctx, JavaRustRethrow>,
+            runnable: TrampolineObject<'ctx, JRunnable>,
+        ) {
+            if let Some(throwable) = runnable.run(ctx).err().and_then(Error::into_throwable) {
+                assert!(throwable.to_string().contains("original"));
+
+                ctx.rethrow(throwable);
+            }
+        }
+
+        let c_rethrow: LocalClass<JavaRustRethrow> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustRethrow"))
+            .unwrap();
+
+        unsafe {
+            ctx.register_natives(
+                c_rethrow.as_raw(),
+                [(c"relay", c"(Ljava/lang/Runnable;)V", relay as *const ())],
+            )
+            .unwrap()
+        }
+
+        let ret: LocalObject<JString> = c_rethrow.call_method(ctx, "call", NoArgs).unwrap();
+        assert_eq!(ret.get_string(ctx), "same");
+    })
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "called with a pending exception")]
+fn test_assert_pending_exception() {
+    with_java_vm(|ctx| {
+        let class = ctx.find_class(c"java/lang/IllegalStateException").unwrap();
+        unsafe { ctx.throw_new(&class, "ignored") };
+
+        // deleting references is allowed with an exception pending
+        drop(class);
+
+        JString::new(ctx, "after an ignored exception");
+    })
+}
+
+#[test]
+fn test_direct_buffer_argument() {
+    with_java_vm(|ctx| {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustBuffers",
+            r#"
+                import java.nio.ByteBuffer;
+
+                public class RustBuffers {
+                    public static int increment(ByteBuffer buffer) {
+                        if (!buffer.isDirect()) {
+                            return -1;
+                        }
+                        int n = buffer.remaining();
+                        for (int i = 0; i < n; i++) {
+                            buffer.put(i, (byte) (buffer.get(i) + 1));
+                        }
+                        return n;
+                    }
+                }
+            "#,
+        );
+
+        define_java_class!(JavaRustBuffers, "RustBuffers");
+
+        let c_buffers: LocalClass<JavaRustBuffers> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustBuffers"))
+            .unwrap();
+
+        let mut buf = [1u8, 2, 3, 255];
+        let n: i32 = c_buffers
+            .call_method(ctx, "increment", unsafe { AsDirectBuffer::new(ctx, &mut buf) }.unwrap())
+            .unwrap();
+        assert_eq!(n, 4);
+        assert_eq!(buf, [2, 3, 4, 0]);
+
+        let mut empty = [];
+        let n: i32 = c_buffers
+            .call_method(ctx, "increment", unsafe { AsDirectBuffer::new(ctx, &mut empty) }.unwrap())
+            .unwrap();
+        assert_eq!(n, 0);
+    })
+}
+
+#[test]
+fn test_atomic_counters() {
+    with_java_vm(|ctx| {
+        let o_progress = JAtomicLong::new(ctx, 10).unwrap();
+        assert_eq!(o_progress.get(ctx), 10);
+
+        assert!(o_progress.compare_and_set(ctx, 10, 20));
+        assert!(!o_progress.compare_and_set(ctx, 10, 30));
+        assert_eq!(o_progress.get(ctx), 20);
+
+        assert_eq!(o_progress.increment_and_get(ctx), 21);
+        assert_eq!(o_progress.get_and_add(ctx, 4), 21);
+        assert_eq!(o_progress.add_and_get(ctx, -5), 20);
+        assert_eq!(o_progress.get_and_set(ctx, i64::MAX), 20);
+        assert_eq!(o_progress.get(ctx), i64::MAX);
+
+        let o_count = JAtomicInteger::new(ctx, 0).unwrap();
+        o_count.set(ctx, 5);
+        assert_eq!(o_count.decrement_and_get(ctx), 4);
+        assert_eq!(o_count.upcast::<JNumber>().long_value(ctx), 4);
+    })
+}
+
+#[test]
+fn test_explicit_attach_detach() {
+    with_java_vm(|_| {
+        std::thread::spawn(|| {
+            assert!(Context::with_current(|_| ()).is_err());
+
+            Context::attach_current_thread().unwrap();
+            Context::attach_current_thread().unwrap();
+
+            // bursts of calls share the attachment instead of attaching each time
+            for burst in 0..3 {
+                let s = Context::with_attached(|ctx| JString::new(ctx, &burst.to_string()).get_string(ctx));
+                assert_eq!(s, burst.to_string());
+                assert!(Context::with_current(|_| ()).is_ok());
+            }
+
+            unsafe { Context::detach_current_thread() }.unwrap();
+            assert!(Context::with_current(|_| ()).is_err());
+        })
+        .join()
+        .unwrap();
+    })
+}
+
+#[test]
+fn test_system_properties() {
+    with_java_vm(|ctx| {
+        assert!(!ctx.get_property("java.io.tmpdir").unwrap().unwrap().is_empty());
+        assert_eq!(ctx.get_property("typed.jni.absent").unwrap(), None);
+
+        assert_eq!(ctx.set_property("typed.jni.property", "first").unwrap(), None);
+        assert_eq!(
+            ctx.set_property("typed.jni.property", "\u{4f60}\u{597d}").unwrap().as_deref(),
+            Some("first")
+        );
+        assert_eq!(
+            ctx.get_property("typed.jni.property").unwrap().as_deref(),
+            Some("\u{4f60}\u{597d}")
+        );
+        assert_eq!(
+            ctx.clear_property("typed.jni.property").unwrap().as_deref(),
+            Some("\u{4f60}\u{597d}")
+        );
+        assert_eq!(ctx.get_property("typed.jni.property").unwrap(), None);
+
+        assert!(ctx
+            .get_property("")
+            .unwrap_err()
+            .to_string()
+            .contains("IllegalArgumentException"));
+    })
+}
+
+#[test]
+fn test_runtime_resources() {
+    with_java_vm(|ctx| {
+        let o_runtime = JRuntime::get(ctx);
+
+        assert!(o_runtime.available_processors(ctx) > 0);
+        assert!(o_runtime.max_memory(ctx) > 0);
+        assert!(o_runtime.total_memory(ctx) >= o_runtime.free_memory(ctx));
+        assert!(JRuntime::get(ctx).is_same_object(ctx, &o_runtime));
+    })
+}
+
+#[test]
+fn test_varargs_argument() {
+    with_java_vm(|ctx| {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustVarArgs",
+            r#"
+                public class RustVarArgs {
+                    public static String join(String sep, Object... values) {
+                        StringBuilder sb = new StringBuilder();
+                        for (int i = 0; i < values.length; i++) {
+                            if (i > 0) {
+                                sb.append(sep);
+                            }
+                            sb.append(values[i]);
+                        }
+                        return values.getClass().getName() + ":" + sb;
+                    }
+                }
+            "#,
+        );
+
+        define_java_class!(JavaRustVarArgs, "RustVarArgs");
+
+        let c_varargs: LocalClass<JavaRustVarArgs> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustVarArgs"))
+            .unwrap();
+
+        let values = [
+            LocalObject::<JString>::new_string(ctx, "a"),
+            LocalObject::<JString>::new_string(ctx, "b"),
+        ];
+        let s: LocalObject<JString> = c_varargs
+            .call_method(ctx, "join", ("-", VarArgs::new(ctx, &values).unwrap()))
+            .unwrap();
+        assert_eq!(s.get_string(ctx), "[Ljava.lang.Object;:a-b");
+
+        let none: [LocalObject<JString>; 0] = [];
+        let s: LocalObject<JString> = c_varargs
+            .call_method(ctx, "join", ("-", VarArgs::new(ctx, &none).unwrap()))
+            .unwrap();
+        assert_eq!(s.get_string(ctx), "[Ljava.lang.Object;:");
+
+        let c_string = LocalClass::<JString>::find_class(ctx).unwrap();
+        let s: LocalObject<JString> = c_string
+            .call_method(ctx, "format", ("%s/%s", VarArgs::new(ctx, &values).unwrap()))
+            .unwrap();
+        assert_eq!(s.get_string(ctx), "a/b");
+    })
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "object of class java.lang.String used as Ljava/util/ArrayList;")]
+fn test_typecheck_mismatch() {
+    with_java_vm(|ctx| {
+        define_java_class!(JavaArrayList, "java.util.ArrayList");
+
+        let s = LocalObject::<JString>::new_string(ctx, "not a list");
+
+        let _list = unsafe { LocalObject::<JavaArrayList>::from_raw(s.into_raw()) };
+    })
+}
+
+#[test]
+fn test_stream_read_write() {
+    use std::io::{Read, Write};
+
+    with_java_vm(|ctx| {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustStreams",
+            r#"
+                import java.io.*;
+
+                public class RustStreams {
+                    public static InputStream input(int n) {
+                        byte[] data = new byte[n];
+                        for (int i = 0; i < n; i++) {
+                            data[i] = (byte) i;
+                        }
+                        return new ByteArrayInputStream(data);
+                    }
+
+                    public static OutputStream output() {
+                        return new ByteArrayOutputStream();
+                    }
+
+                    public static int checksum(OutputStream stream) {
+                        int sum = 0;
+                        for (byte b : ((ByteArrayOutputStream) stream).toByteArray()) {
+                            sum = sum * 31 + b;
+                        }
+                        return sum;
+                    }
+
+                    public static InputStream failing() {
+                        return new InputStream() {
+                            public int read() throws IOException {
+                                throw new IOException("broken pipe");
+                            }
+                        };
+                    }
+                }
+            "#,
+        );
+
+        define_java_class!(JavaRustStreams, "RustStreams");
+
+        let c_streams: LocalClass<JavaRustStreams> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustStreams"))
+            .unwrap();
+
+        // more than one chunk
+        let input: LocalObject<JInputStream> = c_streams.call_method(ctx, "input", 20000).unwrap();
+        let mut data = Vec::new();
+        input.reader(ctx).unwrap().read_to_end(&mut data).unwrap();
+        assert_eq!(data.len(), 20000);
+        assert!(data.iter().enumerate().all(|(i, b)| *b == i as u8));
+
+        let output: LocalObject<JOutputStream> = c_streams.call_method(ctx, "output", NoArgs).unwrap();
+        let mut writer = output.writer(ctx).unwrap();
+        writer.write_all(&data).unwrap();
+        writer.flush().unwrap();
+
+        let expected = data
+            .iter()
+            .fold(0i32, |sum, b| sum.wrapping_mul(31).wrapping_add(*b as i8 as i32));
+        let checksum: i32 = c_streams.call_method(ctx, "checksum", &output).unwrap();
+        assert_eq!(checksum, expected);
+
+        let failing: LocalObject<JInputStream> = c_streams.call_method(ctx, "failing", NoArgs).unwrap();
+        let err = failing.reader(ctx).unwrap().read(&mut [0u8; 16]).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Other);
+        assert!(err.to_string().contains("broken pipe"), "{err}");
+    })
+}
+
+#[test]
+fn test_exception_occurred_keeps_pending() {
+    with_java_vm(|ctx| {
+        assert!(ctx.exception_occurred().is_none());
+
+        let class = ctx.find_class(c"java/lang/IllegalStateException").unwrap();
+        unsafe { ctx.throw_new(&class, "inspect me") };
+
+        let throwable = ctx.exception_occurred().unwrap();
+        assert!(ctx.exception_check());
+
+        // deleting references is legal while it is pending
+        drop(class);
+        assert!(ctx.exception_check());
+
+        ctx.exception_clear();
+        assert!(ctx.exception_occurred().is_none());
+        assert_eq!(throwable.to_string(), "java.lang.IllegalStateException: inspect me");
+    })
+}
+
+#[test]
+fn test_class_name_remapper() {
+    with_java_vm(|ctx| {
+        typed_jni::set_class_name_remapper(|name| match name {
+            "com.example.FancyList" => Some("java.util.ArrayList".to_string()),
+            "com.example.FancyCollection" => Some("java.util.Collection".to_string()),
+            _ => None,
+        });
+
+        define_java_class!(JavaFancyList, "com.example.FancyList");
+        define_java_class!(JavaFancyCollection, "com.example.FancyCollection");
+
+        let c_list = LocalClass::<JavaFancyList>::find_class(ctx).unwrap();
+        let list = c_list.new_object(ctx, NoArgs).unwrap();
+        let size: i32 = list.call_method(ctx, "size", NoArgs).unwrap();
+        assert_eq!(size, 0);
+
+        let cache = ClassCache::new();
+        let class = cache.get_or_load(ctx, "com.example.FancyList").unwrap();
+        assert_eq!(class.get_name(ctx), "java.util.ArrayList");
+
+        // class names in member descriptors are remapped too, e.g. addAll(Ljava/util/Collection;)Z
+        let c_collection = LocalClass::<JavaFancyCollection>::find_class(ctx).unwrap();
+        let o_other = c_list.new_object(ctx, NoArgs).unwrap();
+        let _: bool = o_other
+            .call_method(ctx, "add", &JString::new(ctx, "item").upcast::<JObject>())
+            .unwrap();
+        let o_collection = unsafe { o_other.cast(ctx, &c_collection).unwrap() };
+        assert!(list.call_method::<bool, _>(ctx, "addAll", &o_collection).unwrap());
+        assert_eq!(list.call_method::<i32, _>(ctx, "size", NoArgs).unwrap(), 1);
+
+        assert!(LocalClass::<Array<JavaFancyCollection>>::find_class(ctx).is_ok());
+
+        // other names resolve as they are
+        assert!(LocalClass::<JString>::find_class(ctx).is_ok());
+
+        // the remapper runs without the lock held, so it may replace itself
+        typed_jni::set_class_name_remapper(|name| match name {
+            "com.example.Once" => {
+                typed_jni::set_class_name_remapper(|_| None);
+
+                Some("java.lang.Object".to_string())
+            }
+            _ => None,
+        });
+        define_java_class!(JavaOnce, "com.example.Once");
+        assert!(LocalClass::<JavaOnce>::find_class(ctx).is_ok());
+
+        typed_jni::clear_class_name_remapper();
+        assert!(LocalClass::<JavaFancyCollection>::find_class(ctx).is_err());
+        ctx.exception_clear();
+    })
+}
+
+#[test]
+fn test_proxy_handler_class_file() {
+    let sources = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../jni/java/com/github/kr328/typedjni");
+    let temp = tempdir::TempDir::new("classes").unwrap();
+
+    let javac_ret = std::process::Command::new("javac")
+        .arg("-J-Duser.language=en")
+        .args(["--release", "8", "-d"])
+        .arg(temp.path())
+        .arg("NativeInvocationHandler.java")
+        .current_dir(&sources)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap()
+        .wait()
+        .unwrap();
+    if !javac_ret.success() {
+        panic!("compile java failed");
+    }
+
+    let compiled = std::fs::read(temp.path().join("com/github/kr328/typedjni/NativeInvocationHandler.class")).unwrap();
+    let checked_in = std::fs::read(sources.join("NativeInvocationHandler.class")).unwrap();
+
+    assert!(
+        compiled == checked_in,
+        "NativeInvocationHandler.class is out of date, recompile it with `javac --release 8 NativeInvocationHandler.java`"
+    );
+}
+
+#[test]
+fn test_new_proxy() {
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    define_java_class!(JavaRustProxies, "RustProxies");
+    define_java_class!(JavaBiFunction, "java.util.function.BiFunction");
+
+    struct SetOnDrop(Arc<AtomicBool>);
+
+    impl Drop for SetOnDrop {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    with_java_vm(|ctx| {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustProxies",
+            r#"
+                import java.util.function.BiFunction;
+
+                public class RustProxies {
+                    public static String apply(BiFunction<Object, Object, Object> f) {
+                        return f.apply(3, "x") + " " + f.equals(f) + " " + (f.hashCode() == System.identityHashCode(f));
+                    }
+
+                    public static String fail(BiFunction<Object, Object, Object> f) {
+                        try {
+                            f.apply(null, null);
+                            return "returned";
+                        } catch (RuntimeException e) {
+                            return e.getMessage();
+                        }
+                    }
+
+                    public static void collect() throws InterruptedException {
+                        System.gc();
+                        System.runFinalization();
+                        Thread.sleep(10);
+                    }
+                }
+            "#,
+        );
+
+        let c_proxies: LocalClass<JavaRustProxies> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustProxies"))
+            .unwrap();
+
+        let dropped = Arc::new(AtomicBool::new(false));
+        let guard = SetOnDrop(dropped.clone());
+
+        let o_proxy = ctx
+            .new_proxy::<JavaBiFunction, _>(move |ctx, method, args| {
+                let _ = &guard;
+
+                let args = args.expect("apply has arguments");
+                let Some(first) = args.get_element(ctx, 0)? else {
+                    panic!("null arguments");
+                };
+                let first: LocalObject<JString> = first.call_method(ctx, "toString", NoArgs)?;
+                let second: LocalObject<JString> = args.get_element(ctx, 1)?.unwrap().call_method(ctx, "toString", NoArgs)?;
+
+                let result = format!("{} {} {}", method.name(ctx)?, first.get_string(ctx), second.get_string(ctx));
+
+                Ok(Some(JString::new(ctx, &result).upcast()))
+            })
+            .unwrap();
+
+        let ret: LocalObject<JString> = c_proxies.call_method(ctx, "apply", &o_proxy).unwrap();
+        assert_eq!(ret.get_string(ctx), "apply 3 x true true");
+
+        let ret: LocalObject<JString> = c_proxies.call_method(ctx, "fail", &o_proxy).unwrap();
+        assert_eq!(ret.get_string(ctx), "null arguments");
+
+        // the handler is dropped once the proxy is collected
+        drop(o_proxy);
+        for _ in 0..100 {
+            if dropped.load(Ordering::SeqCst) {
+                break;
+            }
+
+            let _: () = c_proxies.call_method(ctx, "collect", NoArgs).unwrap();
+        }
+        assert!(dropped.load(Ordering::SeqCst));
+    })
+}
+
+#[test]
+fn test_new_runnable() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    define_java_class!(JavaRustRunnables, "RustRunnables");
+
+    with_java_vm(|ctx| {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustRunnables",
+            r#"
+                public class RustRunnables {
+                    public static void runTwice(Runnable r) throws InterruptedException {
+                        r.run();
+
+                        Thread thread = new Thread(r);
+                        thread.start();
+                        thread.join();
+                    }
+                }
+            "#,
+        );
+
+        let c_runnables: LocalClass<JavaRustRunnables> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustRunnables"))
+            .unwrap();
+
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = runs.clone();
+        let mut local_runs = 0;
+
+        let o_runnable = ctx
+            .new_runnable(move || {
+                local_runs += 1;
+                counter.store(local_runs, Ordering::SeqCst);
+            })
+            .unwrap();
+
+        // once on this thread and once on a thread started by Java
+        let _: () = c_runnables.call_method(ctx, "runTwice", &o_runnable).unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        o_runnable.run(ctx).unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    })
+}
+
+#[test]
+fn test_future_when_complete() {
+    use std::sync::mpsc;
+
+    define_java_class!(JavaRustCompletions, "RustCompletions");
+
+    with_java_vm(|ctx| {
+        let loader = compile_file_and_load_classes(
+            ctx,
+            "RustCompletions",
+            r#"
+                import java.util.concurrent.CompletableFuture;
+
+                public class RustCompletions {
+                    public static CompletableFuture<String> pending() {
+                        return new CompletableFuture<>();
+                    }
+
+                    public static CompletableFuture<String> failedLater(CompletableFuture<String> future) {
+                        return future.thenApply(value -> {
+                            throw new IllegalStateException(value);
+                        });
+                    }
+
+                    public static void complete(CompletableFuture<String> future, String value) {
+                        future.complete(value);
+                    }
+                }
+            "#,
+        );
+
+        let c_completions: LocalClass<JavaRustCompletions> = loader
+            .loader
+            .call_method(ctx, "loadClass", &LocalObject::<JString>::new_string(ctx, "RustCompletions"))
+            .unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let report = move |tx: mpsc::Sender<String>| {
+            move |ctx: &Context, result: Result<Option<LocalObject<JString>>, LocalObject<Throwable>>| {
+                let message = match result {
+                    Ok(value) => format!("ok {:?}", value.map(|v| v.get_string(ctx))),
+                    Err(thrown) => {
+                        let message: LocalObject<JString> = thrown.call_method(ctx, "getMessage", NoArgs).unwrap();
+
+                        format!("err {}", message.get_string(ctx))
+                    }
+                };
+
+                tx.send(message).unwrap();
+            }
+        };
+
+        let o_future: LocalObject<JCompletableFuture<JString>> = c_completions.call_method(ctx, "pending", NoArgs).unwrap();
+        let o_failed: LocalObject<JCompletableFuture<JString>> =
+            c_completions.call_method(ctx, "failedLater", &o_future).unwrap();
+
+        let o_next = o_future.when_complete(ctx, report(tx.clone())).unwrap();
+        o_failed.when_complete(ctx, report(tx.clone())).unwrap();
+        assert!(rx.try_recv().is_err());
+
+        let _: () = c_completions.call_method(ctx, "complete", (&o_future, "value")).unwrap();
+        assert_eq!(rx.try_recv().unwrap(), "ok Some(\"value\")");
+        assert_eq!(rx.try_recv().unwrap(), "err value");
+
+        // the returned future completes like the original, and a done future calls back right away
+        assert_eq!(
+            o_next.upcast::<JFuture<JString>>().get(ctx).unwrap().unwrap().get_string(ctx),
+            "value"
+        );
+        o_future.when_complete(ctx, report(tx)).unwrap();
+        assert_eq!(rx.try_recv().unwrap(), "ok Some(\"value\")");
+    })
+}
@@ -0,0 +1,48 @@
+use typed_jni::{create_java_vm, Context, JString, LocalClass, LocalObject, VmError};
+
+typed_jni::define_java_class!(JavaSystem, "java.lang.System");
+
+#[test]
+fn test_create_java_vm() {
+    let vm = create_java_vm(&["-Xmx64m", "-Dtyped.jni.test=created from rust"]).unwrap();
+    assert!(!vm.as_raw().is_null());
+
+    let ctx = vm.context();
+
+    let c_system = LocalClass::<JavaSystem>::find_class(ctx).unwrap();
+    let property: LocalObject<JString> = c_system.call_method(ctx, "getProperty", "typed.jni.test").unwrap();
+    assert_eq!(property.get_string(ctx), "created from rust");
+
+    let from_thread = std::thread::spawn(|| Context::with_attached(|ctx| JString::new(ctx, "attached").get_string(ctx)))
+        .join()
+        .unwrap();
+    assert_eq!(from_thread, "attached");
+
+    drop(property);
+    drop(c_system);
+
+    assert!(matches!(create_java_vm(&[]), Err(VmError(_))));
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let attached = std::sync::Arc::new(std::sync::Barrier::new(2));
+    let worker = std::thread::spawn({
+        let attached = attached.clone();
+
+        move || {
+            Context::with_attached(|_| {
+                attached.wait();
+
+                std::thread::sleep(std::time::Duration::from_millis(200));
+
+                sender.send(()).unwrap();
+            })
+        }
+    });
+
+    attached.wait();
+
+    vm.destroy().unwrap();
+    assert!(receiver.try_recv().is_ok());
+
+    worker.join().unwrap();
+}
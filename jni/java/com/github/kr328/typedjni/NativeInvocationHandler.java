@@ -0,0 +1,48 @@
+package com.github.kr328.typedjni;
+
+import java.lang.reflect.InvocationHandler;
+import java.lang.reflect.Method;
+
+/**
+ * The invocation handler of the proxies created by typed-jni's {@code Context::new_proxy}. The
+ * handle owns the Rust handler, which is dropped when this object is finalized.
+ *
+ * <p>The compiled class next to this file is embedded into the crate and defined at runtime where
+ * the class is not found. On Android, which cannot define classes from class files, ship this file
+ * with the application instead. Rebuild the class after changing this file with
+ * {@code javac --release 8 NativeInvocationHandler.java}.
+ */
+final class NativeInvocationHandler implements InvocationHandler {
+    private final long handle;
+
+    private NativeInvocationHandler(long handle) {
+        this.handle = handle;
+    }
+
+    @Override
+    public Object invoke(Object proxy, Method method, Object[] args) {
+        if (method.getDeclaringClass() == Object.class) {
+            switch (method.getName()) {
+                case "equals":
+                    return proxy == args[0];
+                case "hashCode":
+                    return System.identityHashCode(proxy);
+                case "toString":
+                    return proxy.getClass().getName() + "@" + Integer.toHexString(System.identityHashCode(proxy));
+            }
+        }
+
+        // an instance method, so this handler and its handle stay alive during the call
+        return invokeNative(handle, method, args);
+    }
+
+    @Override
+    @SuppressWarnings("deprecation")
+    protected void finalize() {
+        releaseNative(handle);
+    }
+
+    private native Object invokeNative(long handle, Method method, Object[] args);
+
+    private static native void releaseNative(long handle);
+}
@@ -1,4 +1,9 @@
-use crate::{Args, CallArg, IntoRaw, Signature, Type};
+use core::marker::PhantomData;
+
+use crate::{
+    typed::__CachedClass, Args, AsRaw, CallArg, Context, Error, Global, IntoRaw, JByteBuffer, JObject, Local, Object, ObjectType,
+    Raw, Ref, Signature, Type,
+};
 
 #[derive(Copy, Clone)]
 pub struct NoArgs;
@@ -15,6 +20,171 @@ impl<'a> Args<'a> for NoArgs {
     }
 }
 
+impl<'a> Args<'a> for () {
+    type Array<T: 'a> = [T; 0];
+
+    fn signatures() -> [Signature; 0] {
+        []
+    }
+
+    fn into_raw(self) -> [CallArg<'a>; 0] {
+        []
+    }
+}
+
+/// A typed `null` argument, passed with the object signature of `T`.
+pub struct Null<T: ObjectType>(PhantomData<T>);
+
+impl<T: ObjectType> Null<T> {
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: ObjectType> Default for Null<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ObjectType> Clone for Null<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ObjectType> Copy for Null<T> {}
+
+impl<T: ObjectType> Type for Null<T> {
+    const SIGNATURE: Signature = T::SIGNATURE;
+}
+
+impl<T: ObjectType> Raw for Null<T> {
+    type Raw = Self;
+}
+
+impl<T: ObjectType> IntoRaw for Null<T> {
+    fn into_raw(self) -> Self::Raw {
+        self
+    }
+}
+
+impl<'a, T: ObjectType> From<Null<T>> for CallArg<'a> {
+    fn from(_: Null<T>) -> Self {
+        CallArg::Object(None)
+    }
+}
+
+/// A `&mut [u8]` passed as a direct `ByteBuffer` argument, which Java reads and writes in place
+/// without a copy. The buffer is created by [`new`](Self::new) with `NewDirectByteBuffer`:
+///
+/// ```no_run
+/// # use typed_jni::{AsDirectBuffer, Context, LocalObject};
+/// # typed_jni::define_java_class!(JavaChannel, "java.nio.channels.ReadableByteChannel");
+/// # fn read(ctx: &Context, channel: &LocalObject<JavaChannel>) {
+/// let mut buf = [0u8; 4096];
+///
+/// // the channel only uses the buffer for the duration of read()
+/// let n: i32 = channel
+///     .call_method(ctx, "read", unsafe { AsDirectBuffer::new(ctx, &mut buf) }.unwrap())
+///     .unwrap();
+/// # }
+/// ```
+pub struct AsDirectBuffer<'b> {
+    buffer: Local<'b>,
+    _buf: PhantomData<&'b mut [u8]>,
+}
+
+impl<'b> AsDirectBuffer<'b> {
+    /// Fails if the vm does not support direct buffers.
+    ///
+    /// # Safety
+    ///
+    /// The callee must not keep the `ByteBuffer`, e.g. in a field or another thread, past the
+    /// call. Java accessing it after `buf` is gone reads freed memory.
+    pub unsafe fn new<'ctx: 'b>(ctx: &'ctx Context, buf: &'b mut [u8]) -> Result<Self, Error<'ctx>> {
+        Ok(Self {
+            buffer: unsafe { ctx.new_direct_byte_buffer(buf.as_mut_ptr(), buf.len())? },
+            _buf: PhantomData,
+        })
+    }
+}
+
+impl Type for AsDirectBuffer<'_> {
+    const SIGNATURE: Signature = JByteBuffer::SIGNATURE;
+}
+
+impl<'b> Raw for AsDirectBuffer<'b> {
+    type Raw = Self;
+}
+
+impl IntoRaw for AsDirectBuffer<'_> {
+    fn into_raw(self) -> Self::Raw {
+        self
+    }
+}
+
+impl<'a, 'b: 'a> From<AsDirectBuffer<'b>> for CallArg<'a> {
+    fn from(value: AsDirectBuffer<'b>) -> Self {
+        Self::OwnedObject(value.buffer)
+    }
+}
+
+/// Objects passed as a trailing `Object...` parameter, collected into an `Object[]` by
+/// [`new`](Self::new):
+///
+/// ```no_run
+/// # use typed_jni::{Context, JString, LocalClass, LocalObject, VarArgs};
+/// # fn format(ctx: &Context, name: &LocalObject<JString>) {
+/// let c_string = LocalClass::<JString>::find_class(ctx).unwrap();
+/// let s: LocalObject<JString> = c_string
+///     .call_method(ctx, "format", ("hello %s", VarArgs::new(ctx, core::slice::from_ref(name)).unwrap()))
+///     .unwrap();
+/// # }
+/// ```
+pub struct VarArgs<'ctx>(Local<'ctx>);
+
+static C_OBJECT: __CachedClass = __CachedClass::new();
+
+impl<'ctx> VarArgs<'ctx> {
+    /// Fails with [`TooLong`](Error::TooLong) for more than `i32::MAX` objects, or with the
+    /// `OutOfMemoryError` of a failed allocation.
+    pub fn new<T: ObjectType, R: Ref>(ctx: &'ctx Context, objects: &[Object<T, R>]) -> Result<Self, Error<'ctx>> {
+        let length = i32::try_from(objects.len()).map_err(|_| Error::TooLong)?;
+        let class = C_OBJECT.get::<JObject>(ctx)?;
+
+        unsafe {
+            let array = ctx.new_object_array::<_, Global>(length, class.as_raw(), None)?;
+
+            for (index, object) in objects.iter().enumerate() {
+                ctx.set_object_array_element(&array, index as i32, Some(object.as_raw()))?;
+            }
+
+            Ok(Self(array))
+        }
+    }
+}
+
+impl Type for VarArgs<'_> {
+    const SIGNATURE: Signature = Signature::Array(&JObject::SIGNATURE);
+}
+
+impl<'ctx> Raw for VarArgs<'ctx> {
+    type Raw = Self;
+}
+
+impl IntoRaw for VarArgs<'_> {
+    fn into_raw(self) -> Self::Raw {
+        self
+    }
+}
+
+impl<'a, 'ctx: 'a> From<VarArgs<'ctx>> for CallArg<'a> {
+    fn from(value: VarArgs<'ctx>) -> Self {
+        Self::OwnedObject(value.0)
+    }
+}
+
 macro_rules! impl_args {
     ($n:literal, $($args:ident),*) => {
         #[allow(unused_parens)]
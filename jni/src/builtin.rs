@@ -1,13 +1,21 @@
-use alloc::string::String;
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 use core::{
+    ffi::CStr,
+    fmt::{Debug, Display, Formatter},
     marker::PhantomData,
     ops::{Deref, DerefMut},
 };
 
 use crate::{
-    typed::{Signature, Type},
-    AsRaw, Class, Context, FromRaw, Global, IntoRaw, Local, LocalObject, Object, ObjectType, PrimitiveArrayElement,
-    PrimitiveType, Ref, StrongRef,
+    context::{to_modified_utf8, CallResult, Method},
+    typed::{__CachedClass, __CachedMethod, Signature, Type},
+    AsRaw, Class, Context, Error, FromRaw, Global, GlobalObject, IntoRaw, Local, LocalObject, NoArgs, Object, ObjectType,
+    PrimitiveArrayElement, PrimitiveType, Raw, Ref, StrongRef,
 };
 
 pub struct Throwable;
@@ -16,23 +24,345 @@ impl Type for Throwable {
     const SIGNATURE: Signature = Signature::Object("java/lang/Throwable");
 }
 
-impl ObjectType for Throwable {}
+impl ObjectType for Throwable {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/lang/Throwable");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/lang/Throwable;");
+}
 
 #[cfg(feature = "std")]
 impl<R: super::StrongRef> std::error::Error for Object<Throwable, R> {}
 
+impl<R: StrongRef> Object<Throwable, R> {
+    /// `getCause()`, `None` if there is no cause or the throwable is its own cause.
+    pub fn cause<'ctx>(&self, ctx: &'ctx Context) -> Result<Option<LocalObject<'ctx, Throwable>>, Error<'ctx>> {
+        let cause = self.call_method::<Option<LocalObject<Throwable>>, _>(ctx, "getCause", NoArgs)?;
+
+        Ok(cause.filter(|cause| !ctx.is_same_object(Some(cause.as_raw()), Some(self.as_raw()))))
+    }
+
+    /// The causes from the direct one to the root. Ends after a throwable that was already visited,
+    /// so cyclic chains terminate, and after the first error.
+    pub fn causes<'ctx>(&self, ctx: &'ctx Context) -> Causes<'ctx> {
+        Causes {
+            ctx,
+            visited: vec![self.to_local(ctx)],
+            next: self.cause(ctx).transpose(),
+        }
+    }
+}
+
+pub struct Causes<'ctx> {
+    ctx: &'ctx Context,
+    visited: Vec<LocalObject<'ctx, Throwable>>,
+    next: Option<Result<LocalObject<'ctx, Throwable>, Error<'ctx>>>,
+}
+
+impl<'ctx> Iterator for Causes<'ctx> {
+    type Item = Result<LocalObject<'ctx, Throwable>, Error<'ctx>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = match self.next.take()? {
+            Ok(current) => current,
+            Err(err) => return Some(Err(err)),
+        };
+
+        let ctx = self.ctx;
+        if self
+            .visited
+            .iter()
+            .any(|visited| ctx.is_same_object(Some(visited.as_raw()), Some(current.as_raw())))
+        {
+            return None;
+        }
+
+        self.next = current.cause(ctx).transpose();
+        self.visited.push(current.clone());
+
+        Some(Ok(current))
+    }
+}
+
+pub struct JavaError {
+    throwable: GlobalObject<Throwable>,
+    message: String,
+    cause: Option<Box<JavaError>>,
+}
+
+impl JavaError {
+    pub fn throwable(&self) -> &GlobalObject<Throwable> {
+        &self.throwable
+    }
+
+    pub fn cause(&self) -> Option<&JavaError> {
+        self.cause.as_deref()
+    }
+}
+
+impl Debug for JavaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Display for JavaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JavaError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.cause.as_deref().map(|c| c as _)
+    }
+}
+
+impl<R: StrongRef> Object<Throwable, R> {
+    /// Converts the throwable and its [`causes`](Self::causes) into Rust errors holding global
+    /// references.
+    pub fn into_error<'ctx>(self, ctx: &'ctx Context) -> Result<JavaError, Error<'ctx>> {
+        fn error_of<R: StrongRef>(
+            throwable: &Object<Throwable, R>,
+            cause: Option<Box<JavaError>>,
+        ) -> Result<JavaError, Error<'static>> {
+            Ok(JavaError {
+                throwable: throwable.to_global()?,
+                message: throwable.to_string(),
+                cause,
+            })
+        }
+
+        let causes = self.causes(ctx).collect::<Result<Vec<_>, _>>()?;
+
+        let cause = causes
+            .iter()
+            .rev()
+            .try_fold(None, |cause, throwable| error_of(throwable, cause).map(|e| Some(Box::new(e))))?;
+
+        error_of(&self, cause)
+    }
+}
+
+/// `java.lang.Object`, every object can be viewed as one. It is also the erased type in the
+/// signatures of generic methods.
+pub struct JObject;
+
+impl Type for JObject {
+    const SIGNATURE: Signature = Signature::Object("java/lang/Object");
+}
+
+impl ObjectType for JObject {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/lang/Object");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/lang/Object;");
+}
+
+impl<T: ObjectType, R: Ref> Object<T, R> {
+    pub fn as_object(&self) -> &Object<JObject, R> {
+        // Object is repr(transparent) over R for every T
+        unsafe { &*(self as *const Self as *const Object<JObject, R>) }
+    }
+}
+
+/// Declares `Self` a Java subtype of `S`, so its objects convert with [`upcast`](Object::upcast)
+/// without a check in the vm.
+///
+/// # Safety
+///
+/// `Self` must be a subtype of `S`. Declaring a type that is not lets JNI calls receive objects of
+/// the wrong class, which is undefined behavior.
+pub unsafe trait Extends<S: ObjectType>: ObjectType {}
+
+unsafe impl<T: ObjectType> Extends<JObject> for T {}
+
+impl<T: ObjectType, R: Ref> Object<T, R> {
+    pub fn upcast<S: ObjectType>(self) -> Object<S, R>
+    where
+        T: Extends<S>,
+    {
+        unsafe { Object::from_raw(self.into_raw()) }
+    }
+}
+
+impl<R: StrongRef> Object<JObject, R> {
+    /// `Object.equals`, which may be overridden, unlike [`is_same_object`](Self::is_same_object).
+    pub fn equals<OR: StrongRef>(&self, ctx: &Context, other: Option<&Object<JObject, OR>>) -> bool {
+        self.call_method(ctx, "equals", other)
+            .expect("BROKEN: Object.equals() throws.")
+    }
+
+    pub fn hash_code(&self, ctx: &Context) -> i32 {
+        self.call_method(ctx, "hashCode", NoArgs)
+            .expect("BROKEN: Object.hashCode() throws.")
+    }
+}
+
+/// `java.lang.String`. Conversions are lossless without any setting: [`JString::new`] encodes NUL
+/// and supplementary characters as modified UTF-8 the way the vm expects, and
+/// [`get_string`](Object::get_string) decodes the UTF-16 units. Only strings with unpaired
+/// surrogates, which `str` cannot hold, need [`JString::new_utf16`] and
+/// [`get_region`](Object::get_region); `get_string` panics on them.
 pub struct JString;
 
 impl Type for JString {
     const SIGNATURE: Signature = Signature::Object("java/lang/String");
 }
 
-impl ObjectType for JString {}
+impl ObjectType for JString {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/lang/String");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/lang/String;");
+}
+
+impl Type for &str {
+    const SIGNATURE: Signature = JString::SIGNATURE;
+}
+
+impl<'s> Raw for &'s str {
+    type Raw = &'s str;
+}
+
+impl IntoRaw for &str {
+    fn into_raw(self) -> Self::Raw {
+        self
+    }
+}
+
+impl Type for String {
+    const SIGNATURE: Signature = JString::SIGNATURE;
+}
+
+impl Raw for String {
+    type Raw = String;
+}
+
+impl IntoRaw for String {
+    fn into_raw(self) -> Self::Raw {
+        self
+    }
+}
+
+impl JString {
+    /// Goes through `NewStringUTF`, embedded NULs are encoded as modified UTF-8 so they survive.
+    pub fn new<'ctx>(ctx: &'ctx Context, s: &str) -> LocalObject<'ctx, JString> {
+        unsafe { LocalObject::from_raw(ctx.new_string_utf(&to_modified_utf8(s))) }
+    }
+
+    /// Accepts any UTF-16 sequence, including unpaired surrogates.
+    pub fn new_utf16<'ctx>(ctx: &'ctx Context, s: &[u16]) -> LocalObject<'ctx, JString> {
+        unsafe { LocalObject::from_raw(ctx.new_string_utf16(s)) }
+    }
+}
 
 impl<R: StrongRef> Object<JString, R> {
     pub fn get_string(&self, ctx: &Context) -> String {
         unsafe { ctx.get_string(self.as_raw()) }
     }
+
+    /// The number of UTF-16 units.
+    pub fn length(&self, ctx: &Context) -> i32 {
+        unsafe { ctx.get_string_length(self.as_raw()) }
+    }
+
+    /// Copies `buf.len()` UTF-16 units from `start`, an out of bounds range is `Err` with the thrown
+    /// `StringIndexOutOfBoundsException`.
+    pub fn get_region<'ctx>(&self, ctx: &'ctx Context, start: i32, buf: &mut [u16]) -> Result<(), Error<'ctx>> {
+        unsafe { Ok(ctx.get_string_region(self.as_raw(), start, buf)?) }
+    }
+
+    /// Like [`get_region`](Self::get_region), but the range is checked against the length first,
+    /// which is cheaper than catching a `StringIndexOutOfBoundsException`. The error holds `start`
+    /// if it is out of bounds, or else the end of the range.
+    pub fn get_region_checked(&self, ctx: &Context, start: i32, buf: &mut [u16]) -> Result<(), IndexOutOfBounds> {
+        let length = self.length(ctx);
+        let end = i32::try_from(buf.len()).ok().and_then(|len| start.checked_add(len));
+
+        match end {
+            _ if start < 0 || start > length => Err(IndexOutOfBounds { index: start, length }),
+            Some(end) if end <= length => {
+                self.get_region(ctx, start, buf)
+                    .expect("BROKEN: get string region in bounds failed.");
+
+                Ok(())
+            }
+            end => Err(IndexOutOfBounds {
+                index: end.unwrap_or(i32::MAX),
+                length,
+            }),
+        }
+    }
+
+    /// Copies the modified UTF-8 encoding of the string from UTF-16 index `start` into `buf`
+    /// without allocating, returns the bytes written. Stops at the last whole character (or
+    /// surrogate pair) that fits, one byte of `buf` is kept for the NUL the vm appends.
+    pub fn copy_utf_region<'ctx>(
+        &self,
+        ctx: &'ctx Context,
+        start: i32,
+        buf: &mut [u8],
+    ) -> Result<usize, LocalObject<'ctx, Throwable>> {
+        let length = unsafe { ctx.get_string_length(self.as_raw()) };
+        if start < 0 || start > length {
+            // lets the vm throw its StringIndexOutOfBoundsException
+            let mut nul = 0u8;
+
+            return unsafe { ctx.get_string_utf_region(self.as_raw(), start, 0, &mut nul).map(|_| 0) };
+        }
+
+        let capacity = buf.len().saturating_sub(1);
+
+        let mut chars = [0u16; 64];
+        let mut count = 0;
+        let mut written = 0;
+        let mut last = 0u16;
+
+        'fill: while start + count < length {
+            let n = chars.len().min((length - start - count) as usize);
+            unsafe { ctx.get_string_region(self.as_raw(), start + count, &mut chars[..n])? };
+
+            for &c in &chars[..n] {
+                let width = match c {
+                    0x01..=0x7F => 1,
+                    0 | 0x80..=0x7FF => 2,
+                    _ => 3,
+                };
+                if written + width > capacity {
+                    break 'fill;
+                }
+
+                written += width;
+                count += 1;
+                last = c;
+            }
+        }
+
+        if start + count < length && (0xD800..0xDC00).contains(&last) {
+            count -= 1;
+            written -= 3;
+        }
+
+        if count > 0 {
+            unsafe { ctx.get_string_utf_region(self.as_raw(), start, count, buf.as_mut_ptr())? };
+        }
+
+        Ok(written)
+    }
+
+    pub fn get_bytes<'ctx>(&self, ctx: &'ctx Context, charset: &str) -> Result<Vec<u8>, Error<'ctx>> {
+        let array: LocalObject<Array<i8>> = self.call_method(ctx, "getBytes", charset)?;
+
+        let mut bytes = vec![0u8; array.length(ctx) as usize];
+        array.get_bytes_region(ctx, 0, &mut bytes)?;
+
+        Ok(bytes)
+    }
+}
+
+impl JString {
+    pub fn from_bytes<'ctx>(ctx: &'ctx Context, bytes: &[u8], charset: &str) -> Result<LocalObject<'ctx, JString>, Error<'ctx>> {
+        let array = LocalObject::<Array<i8>>::new_primitive(ctx, i32::try_from(bytes.len()).map_err(|_| Error::TooLong)?)?;
+        array.set_bytes_region(ctx, 0, bytes)?;
+
+        Class::<JString, Local>::find_class(ctx)?.new_object(ctx, (&array, charset))
+    }
 }
 
 impl<'ctx> Object<JString, Local<'ctx>> {
@@ -41,6 +371,42 @@ impl<'ctx> Object<JString, Local<'ctx>> {
     }
 }
 
+/// `java.lang.CharSequence`, which [`JString`] upcasts to with [`upcast`](Object::upcast).
+pub struct JCharSequence;
+
+impl Type for JCharSequence {
+    const SIGNATURE: Signature = Signature::Object("java/lang/CharSequence");
+}
+
+impl ObjectType for JCharSequence {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/lang/CharSequence");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/lang/CharSequence;");
+}
+
+unsafe impl Extends<JCharSequence> for JString {}
+
+impl<R: StrongRef> Object<JCharSequence, R> {
+    /// `CharSequence.toString`, which implementations other than `String` may throw from.
+    pub fn get_string<'ctx>(&self, ctx: &'ctx Context) -> Result<String, Error<'ctx>> {
+        let s: LocalObject<JString> = self.call_method(ctx, "toString", NoArgs)?;
+
+        Ok(s.get_string(ctx))
+    }
+}
+
+/// `java.nio.ByteBuffer`, e.g. a direct buffer over Rust memory passed with
+/// [`AsDirectBuffer`](crate::AsDirectBuffer).
+pub struct JByteBuffer;
+
+impl Type for JByteBuffer {
+    const SIGNATURE: Signature = Signature::Object("java/nio/ByteBuffer");
+}
+
+impl ObjectType for JByteBuffer {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/nio/ByteBuffer");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/nio/ByteBuffer;");
+}
+
 pub struct Array<T: Type>(PhantomData<T>);
 
 impl<T: Type> Type for Array<T> {
@@ -57,7 +423,7 @@ impl<'ctx, T: Type + ObjectType> Object<Array<T>, Local<'ctx>> {
         }
     }
 
-    pub fn new_with_initial<'a, CR: StrongRef, OR: StrongRef>(
+    pub fn new_with_initial<CR: StrongRef, OR: StrongRef>(
         ctx: &'ctx Context,
         size: i32,
         class: &Class<T, CR>,
@@ -80,9 +446,100 @@ impl<T: Type, R: StrongRef> Object<Array<T>, R> {
     pub fn length(&self, ctx: &Context) -> i32 {
         unsafe { ctx.get_array_length(self.as_raw()) }
     }
+
+    pub fn is_empty(&self, ctx: &Context) -> bool {
+        self.length(ctx) == 0
+    }
+
+    /// `array.clone()`, a shallow copy of the same length and element type. Named apart from
+    /// [`Clone::clone`], which only clones the reference.
+    pub fn clone_array<'ctx>(&self, ctx: &'ctx Context) -> Result<LocalObject<'ctx, Array<T>>, Error<'ctx>> {
+        let cloned: LocalObject<JObject> = self.call_method(ctx, "clone", NoArgs)?;
+
+        unsafe { Ok(Object::from_raw(cloned.into_raw())) }
+    }
+
+    fn check_index(&self, ctx: &Context, index: i32) -> Result<(), IndexOutOfBounds> {
+        let length = self.length(ctx);
+
+        if (0..length).contains(&index) {
+            Ok(())
+        } else {
+            Err(IndexOutOfBounds { index, length })
+        }
+    }
+}
+
+struct JSystem;
+
+impl Type for JSystem {
+    const SIGNATURE: Signature = Signature::Object("java/lang/System");
+}
+
+impl ObjectType for JSystem {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/lang/System");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/lang/System;");
+}
+
+static C_SYSTEM: __CachedClass = __CachedClass::new();
+
+impl Context {
+    /// `System.arraycopy`, the copy stays inside the vm with a single JNI call. Overlapping ranges of
+    /// the same array are copied as if through a temporary array. Bounds and element type mismatches
+    /// are thrown as `IndexOutOfBoundsException` and `ArrayStoreException`.
+    pub fn array_copy<'ctx, S: Type, D: Type, SR: StrongRef, DR: StrongRef>(
+        &'ctx self,
+        src: &Object<Array<S>, SR>,
+        src_pos: i32,
+        dst: &Object<Array<D>, DR>,
+        dst_pos: i32,
+        length: i32,
+    ) -> Result<(), Error<'ctx>> {
+        static M_ARRAYCOPY: __CachedMethod<true> = __CachedMethod::new();
+
+        let class = C_SYSTEM.get::<JSystem>(self)?;
+        let method = M_ARRAYCOPY.get::<_, (&LocalObject<JObject>, i32, &LocalObject<JObject>, i32, i32), ()>(
+            self,
+            class.as_raw(),
+            "arraycopy",
+        )?;
+
+        unsafe {
+            self.call_method::<true, _, _, ()>(
+                class.as_raw(),
+                method,
+                [
+                    Some(src.as_raw()).into(),
+                    src_pos.into(),
+                    Some(dst.as_raw()).into(),
+                    dst_pos.into(),
+                    length.into(),
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
 }
 
+/// An index checked on the Rust side, before any exception is thrown in the vm.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct IndexOutOfBounds {
+    pub index: i32,
+    pub length: i32,
+}
+
+impl Display for IndexOutOfBounds {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("index {} out of bounds for length {}", self.index, self.length))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IndexOutOfBounds {}
+
 impl<T: Type + ObjectType, R: StrongRef> Object<Array<T>, R> {
+    /// A null element is `Ok(None)`, an out of bounds index is `Err` with the thrown exception.
     pub fn get_element<'ctx>(
         &self,
         ctx: &'ctx Context,
@@ -91,6 +548,18 @@ impl<T: Type + ObjectType, R: StrongRef> Object<Array<T>, R> {
         unsafe { Ok(Option::from_raw(ctx.get_object_array_element(self.as_raw(), index)?)) }
     }
 
+    /// Like [`get_element`](Self::get_element), but the index is checked against the length
+    /// first, which is cheaper than catching an `ArrayIndexOutOfBoundsException`.
+    pub fn get_element_checked<'ctx>(
+        &self,
+        ctx: &'ctx Context,
+        index: i32,
+    ) -> Result<Option<LocalObject<'ctx, T>>, IndexOutOfBounds> {
+        self.check_index(ctx, index)?;
+
+        Ok(self.get_element(ctx, index).expect("BROKEN: get element in bounds failed."))
+    }
+
     pub fn set_element<'ctx, 'a, RV: Ref + 'a>(
         &self,
         ctx: &'ctx Context,
@@ -101,6 +570,36 @@ impl<T: Type + ObjectType, R: StrongRef> Object<Array<T>, R> {
     }
 }
 
+static C_STRING: __CachedClass = __CachedClass::new();
+
+impl Context {
+    /// `String[]`, the local reference of each string is deleted once it is stored.
+    pub fn new_string_array(&self, strings: &[&str]) -> Result<LocalObject<'_, Array<JString>>, Error<'_>> {
+        let class = C_STRING.get::<JString>(self)?;
+        let array = LocalObject::<Array<JString>>::new(self, i32::try_from(strings.len()).map_err(|_| Error::TooLong)?, &class)?;
+
+        for (index, s) in strings.iter().enumerate() {
+            array.set_element(self, index as i32, Some(&JString::new(self, s)))?;
+        }
+
+        Ok(array)
+    }
+}
+
+impl<R: StrongRef> Object<Array<JString>, R> {
+    /// `null` elements are `None`. Only one element is held as a local reference at a time, so
+    /// large arrays do not fill the local reference table.
+    pub fn to_string_vec(&self, ctx: &Context) -> Vec<Option<String>> {
+        (0..self.length(ctx))
+            .map(|index| {
+                self.get_element(ctx, index)
+                    .expect("BROKEN: get element in bounds failed.")
+                    .map(|s| s.get_string(ctx))
+            })
+            .collect()
+    }
+}
+
 pub struct PrimitiveArrayElements<'a, T: PrimitiveType + PrimitiveArrayElement, R: StrongRef> {
     array: &'a Object<Array<T>, R>,
     buf: &'a mut [T],
@@ -143,6 +642,25 @@ impl<T: Type + PrimitiveType + PrimitiveArrayElement, R: StrongRef> Object<Array
         }
     }
 
+    /// An out of bounds index is `Err` with the thrown exception.
+    pub fn get_primitive<'ctx>(&self, ctx: &'ctx Context, index: i32) -> Result<T, LocalObject<'ctx, Throwable>> {
+        let mut value = [unsafe { core::mem::zeroed::<T>() }];
+
+        self.get_region(ctx, index, &mut value)?;
+
+        let [value] = value;
+
+        Ok(value)
+    }
+
+    /// Like [`get_primitive`](Self::get_primitive), but the index is checked against the length
+    /// first, which is cheaper than catching an `ArrayIndexOutOfBoundsException`.
+    pub fn get_primitive_checked(&self, ctx: &Context, index: i32) -> Result<T, IndexOutOfBounds> {
+        self.check_index(ctx, index)?;
+
+        Ok(self.get_primitive(ctx, index).expect("BROKEN: get element in bounds failed."))
+    }
+
     pub fn get_region<'ctx>(&self, ctx: &'ctx Context, offset: i32, buf: &mut [T]) -> Result<(), LocalObject<'ctx, Throwable>> {
         unsafe { ctx.get_primitive_array_region(self.as_raw(), offset, buf) }
     }
@@ -224,6 +742,22 @@ impl<R: StrongRef> Object<Array<i8>, R> {
         }
     }
 
+    /// Copies the whole array through `GetPrimitiveArrayCritical`, falling back to
+    /// `GetByteArrayRegion` if the vm cannot pin or copy it. The `Vec` is allocated before the
+    /// critical region is entered.
+    pub fn read_all_fast(&self, ctx: &Context) -> Vec<u8> {
+        let mut buf = vec![0u8; self.length(ctx) as usize];
+
+        if unsafe { ctx.copy_primitive_array_critical(self.as_raw(), &mut buf) } {
+            return buf;
+        }
+
+        self.get_bytes_region(ctx, 0, &mut buf)
+            .expect("BROKEN: read whole array failed.");
+
+        buf
+    }
+
     pub fn set_bytes_region<'ctx>(
         &self,
         ctx: &'ctx Context,
@@ -239,3 +773,742 @@ impl<R: StrongRef> Object<Array<i8>, R> {
         }
     }
 }
+
+pub type JBooleanArray = Array<bool>;
+pub type JByteArray = Array<i8>;
+pub type JCharArray = Array<u16>;
+pub type JShortArray = Array<i16>;
+pub type JIntArray = Array<i32>;
+pub type JLongArray = Array<i64>;
+pub type JFloatArray = Array<f32>;
+pub type JDoubleArray = Array<f64>;
+
+impl<R: StrongRef> Object<JBooleanArray, R> {
+    /// The raw `jboolean` bytes, values other than 0/1 are returned as is. [`get_region`] and
+    /// [`get_elements`] read any nonzero value as `true`.
+    ///
+    /// [`get_region`]: Object::get_region
+    /// [`get_elements`]: Object::get_elements
+    pub fn get_jboolean_region<'ctx>(
+        &self,
+        ctx: &'ctx Context,
+        offset: i32,
+        buf: &mut [u8],
+    ) -> Result<(), LocalObject<'ctx, Throwable>> {
+        unsafe { ctx.get_boolean_array_region_raw(self.as_raw(), offset, buf) }
+    }
+
+    /// Stores the bytes without normalizing them.
+    ///
+    /// # Safety
+    ///
+    /// Values other than 0/1 are stored as is, how Java code treats them is up to the vm.
+    pub unsafe fn set_jboolean_region<'ctx>(
+        &self,
+        ctx: &'ctx Context,
+        offset: i32,
+        buf: &[u8],
+    ) -> Result<(), LocalObject<'ctx, Throwable>> {
+        unsafe { ctx.set_boolean_array_region_raw(self.as_raw(), offset, buf) }
+    }
+}
+
+impl Context {
+    /// `System.getProperty`, `None` if the property is not set. An empty key is thrown as
+    /// `IllegalArgumentException`.
+    pub fn get_property(&self, key: &str) -> Result<Option<String>, Error<'_>> {
+        static M_GET_PROPERTY: __CachedMethod<true> = __CachedMethod::new();
+
+        self.call_property(&M_GET_PROPERTY, "getProperty", key)
+    }
+
+    /// `System.setProperty`, returns the previous value.
+    pub fn set_property(&self, key: &str, value: &str) -> Result<Option<String>, Error<'_>> {
+        static M_SET_PROPERTY: __CachedMethod<true> = __CachedMethod::new();
+
+        self.call_property(&M_SET_PROPERTY, "setProperty", (key, value))
+    }
+
+    /// `System.clearProperty`, returns the removed value.
+    pub fn clear_property(&self, key: &str) -> Result<Option<String>, Error<'_>> {
+        static M_CLEAR_PROPERTY: __CachedMethod<true> = __CachedMethod::new();
+
+        self.call_property(&M_CLEAR_PROPERTY, "clearProperty", key)
+    }
+
+    fn call_property<'ctx, 'a, A>(
+        &'ctx self,
+        method: &__CachedMethod<true>,
+        name: &'static str,
+        args: A,
+    ) -> Result<Option<String>, Error<'ctx>>
+    where
+        A: crate::Args<'a>,
+        A::Array<Signature>: AsRef<[Signature]>,
+        A::Array<crate::CallArg<'a>>: crate::CallArgs,
+    {
+        let class = C_SYSTEM.get::<JSystem>(self)?;
+        let method = method.get::<_, A, JString>(self, class.as_raw(), name)?;
+
+        let value = unsafe { self.call_method::<true, _, _, Option<Local>>(class.as_raw(), method, args.into_raw())? };
+
+        Ok(value.map(|v| unsafe { LocalObject::<JString>::from_raw(v) }.get_string(self)))
+    }
+}
+
+/// `java.lang.Runtime`, the resources of the vm, e.g. to size native thread pools.
+pub struct JRuntime;
+
+impl Type for JRuntime {
+    const SIGNATURE: Signature = Signature::Object("java/lang/Runtime");
+}
+
+impl ObjectType for JRuntime {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/lang/Runtime");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/lang/Runtime;");
+}
+
+static C_RUNTIME: __CachedClass = __CachedClass::new();
+
+impl JRuntime {
+    /// `Runtime.getRuntime()`.
+    pub fn get<'ctx>(ctx: &'ctx Context) -> LocalObject<'ctx, JRuntime> {
+        static M_GET_RUNTIME: __CachedMethod<true> = __CachedMethod::new();
+
+        let class = C_RUNTIME
+            .get::<JRuntime>(ctx)
+            .expect("BROKEN: unable to find java/lang/Runtime.");
+        let method = M_GET_RUNTIME
+            .get::<_, NoArgs, JRuntime>(ctx, class.as_raw(), "getRuntime")
+            .expect("BROKEN: unable to find Runtime.getRuntime().");
+
+        unsafe {
+            LocalObject::from_raw(
+                ctx.call_method::<true, _, _, Local>(class.as_raw(), method, [])
+                    .expect("BROKEN: Runtime.getRuntime() throws."),
+            )
+        }
+    }
+}
+
+impl<R: StrongRef> Object<JRuntime, R> {
+    /// At least 1, may change while the vm runs.
+    pub fn available_processors(&self, ctx: &Context) -> i32 {
+        static M_AVAILABLE_PROCESSORS: __CachedMethod<false> = __CachedMethod::new();
+
+        self.call_value(ctx, &M_AVAILABLE_PROCESSORS, "availableProcessors")
+    }
+
+    /// In bytes, `i64::MAX` without a limit.
+    pub fn max_memory(&self, ctx: &Context) -> i64 {
+        static M_MAX_MEMORY: __CachedMethod<false> = __CachedMethod::new();
+
+        self.call_value(ctx, &M_MAX_MEMORY, "maxMemory")
+    }
+
+    pub fn total_memory(&self, ctx: &Context) -> i64 {
+        static M_TOTAL_MEMORY: __CachedMethod<false> = __CachedMethod::new();
+
+        self.call_value(ctx, &M_TOTAL_MEMORY, "totalMemory")
+    }
+
+    pub fn free_memory(&self, ctx: &Context) -> i64 {
+        static M_FREE_MEMORY: __CachedMethod<false> = __CachedMethod::new();
+
+        self.call_value(ctx, &M_FREE_MEMORY, "freeMemory")
+    }
+
+    fn call_value<'ctx, V: Type + CallResult<'ctx>>(
+        &self,
+        ctx: &'ctx Context,
+        method: &__CachedMethod<false>,
+        name: &'static str,
+    ) -> V {
+        let method = C_RUNTIME
+            .get::<JRuntime>(ctx)
+            .and_then(|class| method.get::<_, NoArgs, V>(ctx, class.as_raw(), name))
+            .expect("BROKEN: unable to find java/lang/Runtime method.");
+
+        unsafe {
+            ctx.call_method(self.as_raw(), method, [])
+                .expect("BROKEN: java/lang/Runtime method throws.")
+        }
+    }
+}
+
+pub struct JThread;
+
+impl Type for JThread {
+    const SIGNATURE: Signature = Signature::Object("java/lang/Thread");
+}
+
+impl ObjectType for JThread {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/lang/Thread");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/lang/Thread;");
+}
+
+impl Context {
+    pub fn current_thread(&self) -> Result<LocalObject<'_, JThread>, Error<'_>> {
+        Class::<JThread, Local>::find_class(self)?.call_method(self, "currentThread", NoArgs)
+    }
+}
+
+impl<R: StrongRef> Object<JThread, R> {
+    pub fn interrupt<'ctx>(&self, ctx: &'ctx Context) -> Result<(), Error<'ctx>> {
+        self.call_method(ctx, "interrupt", NoArgs)
+    }
+
+    pub fn is_interrupted<'ctx>(&self, ctx: &'ctx Context) -> Result<bool, Error<'ctx>> {
+        self.call_method(ctx, "isInterrupted", NoArgs)
+    }
+}
+
+/// `java.lang.Runnable`. One backed by a Rust closure is created with `Context::new_runnable` of the
+/// `proxy` feature.
+pub struct JRunnable;
+
+impl Type for JRunnable {
+    const SIGNATURE: Signature = Signature::Object("java/lang/Runnable");
+}
+
+impl ObjectType for JRunnable {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/lang/Runnable");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/lang/Runnable;");
+}
+
+impl<R: StrongRef> Object<JRunnable, R> {
+    pub fn run<'ctx>(&self, ctx: &'ctx Context) -> Result<(), Error<'ctx>> {
+        self.call_method(ctx, "run", NoArgs)
+    }
+}
+
+pub struct JClass;
+
+impl Type for JClass {
+    const SIGNATURE: Signature = Signature::Object("java/lang/Class");
+}
+
+impl ObjectType for JClass {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/lang/Class");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/lang/Class;");
+}
+
+static C_CLASS: __CachedClass = __CachedClass::new();
+
+fn class_method<'ctx, 'a, A: crate::Args<'a>, V: Type>(
+    ctx: &'ctx Context,
+    method: &__CachedMethod<false>,
+    name: &'static str,
+) -> Method<false>
+where
+    A::Array<Signature>: AsRef<[Signature]>,
+{
+    C_CLASS
+        .get::<JClass>(ctx)
+        .and_then(|class| method.get::<_, A, V>(ctx, class.as_raw(), name))
+        .expect("BROKEN: unable to find java/lang/Class method.")
+}
+
+impl<R: StrongRef> Object<JClass, R> {
+    /// The binary name, `java.lang.String`, `[I` or `com.example.Outer$Inner`.
+    pub fn get_name(&self, ctx: &Context) -> String {
+        static M_GET_NAME: __CachedMethod<false> = __CachedMethod::new();
+
+        self.call_string(ctx, class_method::<NoArgs, JString>(ctx, &M_GET_NAME, "getName"))
+    }
+
+    /// Empty for anonymous classes.
+    pub fn get_simple_name(&self, ctx: &Context) -> String {
+        static M_GET_SIMPLE_NAME: __CachedMethod<false> = __CachedMethod::new();
+
+        self.call_string(ctx, class_method::<NoArgs, JString>(ctx, &M_GET_SIMPLE_NAME, "getSimpleName"))
+    }
+
+    pub fn is_interface(&self, ctx: &Context) -> bool {
+        static M_IS_INTERFACE: __CachedMethod<false> = __CachedMethod::new();
+
+        self.call_primitive(ctx, class_method::<NoArgs, bool>(ctx, &M_IS_INTERFACE, "isInterface"))
+    }
+
+    pub fn is_array(&self, ctx: &Context) -> bool {
+        static M_IS_ARRAY: __CachedMethod<false> = __CachedMethod::new();
+
+        self.call_primitive(ctx, class_method::<NoArgs, bool>(ctx, &M_IS_ARRAY, "isArray"))
+    }
+
+    pub fn is_primitive(&self, ctx: &Context) -> bool {
+        static M_IS_PRIMITIVE: __CachedMethod<false> = __CachedMethod::new();
+
+        self.call_primitive(ctx, class_method::<NoArgs, bool>(ctx, &M_IS_PRIMITIVE, "isPrimitive"))
+    }
+
+    /// The `java.lang.reflect.Modifier` bits, e.g. `0x0001` public, `0x0010` final.
+    pub fn get_modifiers(&self, ctx: &Context) -> i32 {
+        static M_GET_MODIFIERS: __CachedMethod<false> = __CachedMethod::new();
+
+        self.call_primitive(ctx, class_method::<NoArgs, i32>(ctx, &M_GET_MODIFIERS, "getModifiers"))
+    }
+
+    fn call_primitive<'ctx, V: CallResult<'ctx>>(&self, ctx: &'ctx Context, method: Method<false>) -> V {
+        unsafe {
+            ctx.call_method(self.as_raw(), method, [])
+                .expect("BROKEN: java/lang/Class method throws.")
+        }
+    }
+
+    fn call_string(&self, ctx: &Context, method: Method<false>) -> String {
+        let name: Option<Local> = self.call_primitive(ctx, method);
+
+        name.map(|name| unsafe { ctx.get_string(&name) }).unwrap_or_default()
+    }
+}
+
+pub struct JClassLoader;
+
+impl Type for JClassLoader {
+    const SIGNATURE: Signature = Signature::Object("java/lang/ClassLoader");
+}
+
+impl ObjectType for JClassLoader {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/lang/ClassLoader");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/lang/ClassLoader;");
+}
+
+pub struct JMethod;
+
+impl Type for JMethod {
+    const SIGNATURE: Signature = Signature::Object("java/lang/reflect/Method");
+}
+
+impl ObjectType for JMethod {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/lang/reflect/Method");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/lang/reflect/Method;");
+}
+
+impl Context {
+    /// `Class.getDeclaredMethods`, all methods declared by `class` itself in no particular order,
+    /// without inherited ones.
+    pub fn get_declared_methods<'ctx, T: ObjectType, R: StrongRef>(
+        &'ctx self,
+        class: &Class<T, R>,
+    ) -> Result<Vec<LocalObject<'ctx, JMethod>>, Error<'ctx>> {
+        let class = unsafe { LocalObject::<JClass>::from_raw(class.as_raw().to_local(self)) };
+        let methods: LocalObject<Array<JMethod>> = class.call_method(self, "getDeclaredMethods", NoArgs)?;
+
+        collect_elements(self, &methods)
+    }
+}
+
+impl<R: StrongRef> Object<JMethod, R> {
+    pub fn name<'ctx>(&self, ctx: &'ctx Context) -> Result<String, Error<'ctx>> {
+        let name: LocalObject<JString> = self.call_method(ctx, "getName", NoArgs)?;
+
+        Ok(name.get_string(ctx))
+    }
+
+    pub fn parameter_types<'ctx>(&self, ctx: &'ctx Context) -> Result<Vec<LocalObject<'ctx, JClass>>, Error<'ctx>> {
+        let types: LocalObject<Array<JClass>> = self.call_method(ctx, "getParameterTypes", NoArgs)?;
+
+        collect_elements(ctx, &types)
+    }
+
+    /// Whether the method has to be resolved with `STATIC = true`, see [`Context::from_reflected_method`].
+    pub fn is_static(&self, ctx: &Context) -> bool {
+        const STATIC: i32 = 0x0008;
+
+        let modifiers: i32 = self
+            .call_method(ctx, "getModifiers", NoArgs)
+            .expect("BROKEN: unable to get method modifiers.");
+
+        modifiers & STATIC != 0
+    }
+}
+
+fn collect_elements<'ctx, T: ObjectType>(
+    ctx: &'ctx Context,
+    array: &LocalObject<'ctx, Array<T>>,
+) -> Result<Vec<LocalObject<'ctx, T>>, Error<'ctx>> {
+    let length = array.length(ctx);
+    ctx.ensure_local_capacity(length);
+
+    (0..length)
+        .map(|index| {
+            Ok(array
+                .get_element(ctx, index)?
+                .expect("BROKEN: unexpected null element in reflection result."))
+        })
+        .collect()
+}
+
+pub struct JInteger;
+
+impl Type for JInteger {
+    const SIGNATURE: Signature = Signature::Object("java/lang/Integer");
+}
+
+impl ObjectType for JInteger {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/lang/Integer");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/lang/Integer;");
+}
+
+static C_INTEGER: __CachedClass = __CachedClass::new();
+static M_VALUE_OF: __CachedMethod<true> = __CachedMethod::new();
+static M_INT_VALUE: __CachedMethod<false> = __CachedMethod::new();
+
+impl Context {
+    /// `Integer.valueOf`, so values in `-128..=127` are the instances cached by the vm and boxing
+    /// the same small value twice gives the same object. The class and method ids are resolved
+    /// once per process.
+    pub fn box_int(&self, value: i32) -> Result<LocalObject<'_, JInteger>, Error<'_>> {
+        let class = C_INTEGER.get::<JInteger>(self)?;
+        let method = M_VALUE_OF.get::<_, i32, JInteger>(self, class.as_raw(), "valueOf")?;
+
+        unsafe {
+            Ok(LocalObject::from_raw(self.call_method::<true, _, _, Local>(
+                class.as_raw(),
+                method,
+                [value.into()],
+            )?))
+        }
+    }
+}
+
+impl<R: StrongRef> Object<JInteger, R> {
+    pub fn int_value(&self, ctx: &Context) -> i32 {
+        let method = C_INTEGER
+            .get::<JInteger>(ctx)
+            .and_then(|class| M_INT_VALUE.get::<_, NoArgs, i32>(ctx, class.as_raw(), "intValue"))
+            .expect("BROKEN: unable to find Integer.intValue().");
+
+        unsafe {
+            ctx.call_method(self.as_raw(), method, [])
+                .expect("BROKEN: Integer.intValue() throws.")
+        }
+    }
+}
+
+/// `java.lang.Number`, the supertype of the boxed numeric types. The value is read through the
+/// virtual `Number` methods, so an `Integer`, `Long` or `Double` converts like it does in Java.
+pub struct JNumber;
+
+impl Type for JNumber {
+    const SIGNATURE: Signature = Signature::Object("java/lang/Number");
+}
+
+impl ObjectType for JNumber {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/lang/Number");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/lang/Number;");
+}
+
+static C_NUMBER: __CachedClass = __CachedClass::new();
+
+impl<R: StrongRef> Object<JNumber, R> {
+    pub fn int_value(&self, ctx: &Context) -> i32 {
+        static M_INT_VALUE: __CachedMethod<false> = __CachedMethod::new();
+
+        self.call_value(ctx, &M_INT_VALUE, "intValue")
+    }
+
+    pub fn long_value(&self, ctx: &Context) -> i64 {
+        static M_LONG_VALUE: __CachedMethod<false> = __CachedMethod::new();
+
+        self.call_value(ctx, &M_LONG_VALUE, "longValue")
+    }
+
+    pub fn float_value(&self, ctx: &Context) -> f32 {
+        static M_FLOAT_VALUE: __CachedMethod<false> = __CachedMethod::new();
+
+        self.call_value(ctx, &M_FLOAT_VALUE, "floatValue")
+    }
+
+    pub fn double_value(&self, ctx: &Context) -> f64 {
+        static M_DOUBLE_VALUE: __CachedMethod<false> = __CachedMethod::new();
+
+        self.call_value(ctx, &M_DOUBLE_VALUE, "doubleValue")
+    }
+
+    fn call_value<'ctx, V: Type + CallResult<'ctx>>(
+        &self,
+        ctx: &'ctx Context,
+        method: &__CachedMethod<false>,
+        name: &'static str,
+    ) -> V {
+        let method = C_NUMBER
+            .get::<JNumber>(ctx)
+            .and_then(|class| method.get::<_, NoArgs, V>(ctx, class.as_raw(), name))
+            .expect("BROKEN: unable to find java/lang/Number method.");
+
+        unsafe {
+            ctx.call_method(self.as_raw(), method, [])
+                .expect("BROKEN: java/lang/Number method throws.")
+        }
+    }
+}
+
+macro_rules! impl_atomic {
+    ($name:ident, $cached:ident, $value:ty, $class:literal, $internal_name:literal, $class_name:literal, $descriptor:literal) => {
+        #[doc = concat!("`", $class, "`, a counter shared with Java without a lock.")]
+        pub struct $name;
+
+        impl Type for $name {
+            const SIGNATURE: Signature = Signature::Object($internal_name);
+        }
+
+        impl ObjectType for $name {
+            const CLASS_NAME: Option<&'static CStr> = Some($class_name);
+            const DESCRIPTOR: Option<&'static CStr> = Some($descriptor);
+        }
+
+        unsafe impl Extends<JNumber> for $name {}
+
+        static $cached: __CachedClass = __CachedClass::new();
+
+        impl $name {
+            pub fn new<'ctx>(ctx: &'ctx Context, initial: $value) -> Result<LocalObject<'ctx, $name>, Error<'ctx>> {
+                $cached.get::<$name>(ctx)?.new_object(ctx, initial)
+            }
+        }
+
+        impl<R: StrongRef> Object<$name, R> {
+            pub fn get(&self, ctx: &Context) -> $value {
+                static M_GET: __CachedMethod<false> = __CachedMethod::new();
+
+                self.call_atomic(ctx, &M_GET, "get", NoArgs)
+            }
+
+            pub fn set(&self, ctx: &Context, value: $value) {
+                static M_SET: __CachedMethod<false> = __CachedMethod::new();
+
+                self.call_atomic(ctx, &M_SET, "set", value)
+            }
+
+            pub fn get_and_set(&self, ctx: &Context, value: $value) -> $value {
+                static M_GET_AND_SET: __CachedMethod<false> = __CachedMethod::new();
+
+                self.call_atomic(ctx, &M_GET_AND_SET, "getAndSet", value)
+            }
+
+            pub fn increment_and_get(&self, ctx: &Context) -> $value {
+                static M_INCREMENT_AND_GET: __CachedMethod<false> = __CachedMethod::new();
+
+                self.call_atomic(ctx, &M_INCREMENT_AND_GET, "incrementAndGet", NoArgs)
+            }
+
+            pub fn decrement_and_get(&self, ctx: &Context) -> $value {
+                static M_DECREMENT_AND_GET: __CachedMethod<false> = __CachedMethod::new();
+
+                self.call_atomic(ctx, &M_DECREMENT_AND_GET, "decrementAndGet", NoArgs)
+            }
+
+            pub fn add_and_get(&self, ctx: &Context, delta: $value) -> $value {
+                static M_ADD_AND_GET: __CachedMethod<false> = __CachedMethod::new();
+
+                self.call_atomic(ctx, &M_ADD_AND_GET, "addAndGet", delta)
+            }
+
+            pub fn get_and_add(&self, ctx: &Context, delta: $value) -> $value {
+                static M_GET_AND_ADD: __CachedMethod<false> = __CachedMethod::new();
+
+                self.call_atomic(ctx, &M_GET_AND_ADD, "getAndAdd", delta)
+            }
+
+            /// Sets `new` only if the current value is `expected`, `false` if it was not.
+            pub fn compare_and_set(&self, ctx: &Context, expected: $value, new: $value) -> bool {
+                static M_COMPARE_AND_SET: __CachedMethod<false> = __CachedMethod::new();
+
+                self.call_atomic(ctx, &M_COMPARE_AND_SET, "compareAndSet", (expected, new))
+            }
+
+            fn call_atomic<'ctx, 'a, A, V>(
+                &self,
+                ctx: &'ctx Context,
+                method: &__CachedMethod<false>,
+                name: &'static str,
+                args: A,
+            ) -> V
+            where
+                A: crate::Args<'a>,
+                A::Array<Signature>: AsRef<[Signature]>,
+                A::Array<crate::CallArg<'a>>: crate::CallArgs,
+                V: Type + CallResult<'ctx>,
+            {
+                let method = $cached
+                    .get::<$name>(ctx)
+                    .and_then(|class| method.get::<_, A, V>(ctx, class.as_raw(), name))
+                    .expect(concat!("BROKEN: unable to find ", $class, " method."));
+
+                unsafe {
+                    ctx.call_method(self.as_raw(), method, args.into_raw())
+                        .expect(concat!("BROKEN: ", $class, " method throws."))
+                }
+            }
+        }
+    };
+}
+
+impl_atomic!(
+    JAtomicInteger,
+    C_ATOMIC_INTEGER,
+    i32,
+    "java.util.concurrent.atomic.AtomicInteger",
+    "java/util/concurrent/atomic/AtomicInteger",
+    c"java/util/concurrent/atomic/AtomicInteger",
+    c"Ljava/util/concurrent/atomic/AtomicInteger;"
+);
+impl_atomic!(
+    JAtomicLong,
+    C_ATOMIC_LONG,
+    i64,
+    "java.util.concurrent.atomic.AtomicLong",
+    "java/util/concurrent/atomic/AtomicLong",
+    c"java/util/concurrent/atomic/AtomicLong",
+    c"Ljava/util/concurrent/atomic/AtomicLong;"
+);
+
+/// `java.util.concurrent.Future<T>`. Like in Java the element type is erased, `T` is what the
+/// caller expects the result to be.
+pub struct JFuture<T: ObjectType>(PhantomData<T>);
+
+impl<T: ObjectType> Type for JFuture<T> {
+    const SIGNATURE: Signature = Signature::Object("java/util/concurrent/Future");
+}
+
+impl<T: ObjectType> ObjectType for JFuture<T> {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/util/concurrent/Future");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/util/concurrent/Future;");
+}
+
+static C_FUTURE: __CachedClass = __CachedClass::new();
+static C_EXECUTION_EXCEPTION: __CachedClass = __CachedClass::new();
+static M_FUTURE_GET: __CachedMethod<false> = __CachedMethod::new();
+static M_FUTURE_IS_DONE: __CachedMethod<false> = __CachedMethod::new();
+
+impl<T: ObjectType, R: StrongRef> Object<JFuture<T>, R> {
+    /// Blocks the current thread until the result is available. A failed computation is thrown as
+    /// an `ExecutionException`, which is unwrapped to its cause, cancellation and interruption are
+    /// returned as thrown.
+    pub fn get<'ctx>(&self, ctx: &'ctx Context) -> Result<Option<LocalObject<'ctx, T>>, Error<'ctx>> {
+        let class = C_FUTURE.get::<JFuture<T>>(ctx)?;
+        let method = M_FUTURE_GET.get::<_, NoArgs, JObject>(ctx, class.as_raw(), "get")?;
+
+        match unsafe { ctx.call_method::<false, _, _, Option<Local>>(self.as_raw(), method, []) } {
+            Ok(result) => Ok(result.map(|r| unsafe { LocalObject::from_raw(r) })),
+            Err(thrown) => {
+                let c_execution = C_EXECUTION_EXCEPTION.get::<ExecutionException>(ctx)?;
+
+                if thrown.is_instance_of(ctx, &c_execution) {
+                    Err(thrown.cause(ctx)?.unwrap_or(thrown).into())
+                } else {
+                    Err(thrown.into())
+                }
+            }
+        }
+    }
+
+    pub fn is_done(&self, ctx: &Context) -> bool {
+        let method = C_FUTURE
+            .get::<JFuture<T>>(ctx)
+            .and_then(|class| M_FUTURE_IS_DONE.get::<_, NoArgs, bool>(ctx, class.as_raw(), "isDone"))
+            .expect("BROKEN: unable to find Future.isDone().");
+
+        unsafe {
+            ctx.call_method(self.as_raw(), method, [])
+                .expect("BROKEN: Future.isDone() throws.")
+        }
+    }
+}
+
+/// `java.util.concurrent.CompletableFuture<T>`, a [`JFuture`] whose completion can also be
+/// observed with `when_complete` of the `proxy` feature.
+pub struct JCompletableFuture<T: ObjectType>(PhantomData<T>);
+
+impl<T: ObjectType> Type for JCompletableFuture<T> {
+    const SIGNATURE: Signature = Signature::Object("java/util/concurrent/CompletableFuture");
+}
+
+impl<T: ObjectType> ObjectType for JCompletableFuture<T> {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/util/concurrent/CompletableFuture");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/util/concurrent/CompletableFuture;");
+}
+
+unsafe impl<T: ObjectType> Extends<JFuture<T>> for JCompletableFuture<T> {}
+
+struct ExecutionException;
+
+impl Type for ExecutionException {
+    const SIGNATURE: Signature = Signature::Object("java/util/concurrent/ExecutionException");
+}
+
+impl ObjectType for ExecutionException {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/util/concurrent/ExecutionException");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/util/concurrent/ExecutionException;");
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use core::{
+        ffi::c_void,
+        ptr::null_mut,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    use crate::{
+        mock::{dangling_local, MockEnv},
+        sys::{jarray, jboolean, jbyte, jbyteArray, jint, jsize, JNIEnv},
+        FromRaw, JByteArray, LocalObject,
+    };
+
+    const DATA: [u8; 4] = [1, 2, 0x80, 0xFF];
+
+    unsafe extern "C" fn get_array_length(_: *mut JNIEnv, _: jarray) -> jsize {
+        DATA.len() as jsize
+    }
+
+    unsafe extern "C" fn get_byte_array_region(_: *mut JNIEnv, _: jbyteArray, start: jsize, len: jsize, buf: *mut jbyte) {
+        unsafe { core::ptr::copy_nonoverlapping(DATA.as_ptr().add(start as usize).cast(), buf, len as usize) };
+    }
+
+    unsafe extern "C" fn release_critical(_: *mut JNIEnv, _: jarray, _: *mut c_void, _: jint) {}
+
+    fn read_all_fast(critical: unsafe extern "C" fn(*mut JNIEnv, jarray, *mut jboolean) -> *mut c_void) -> Vec<u8> {
+        let env = MockEnv::new(|functions| {
+            functions.GetArrayLength = Some(get_array_length);
+            functions.GetByteArrayRegion = Some(get_byte_array_region);
+            functions.GetPrimitiveArrayCritical = Some(critical);
+            functions.ReleasePrimitiveArrayCritical = Some(release_critical);
+        });
+
+        let array = unsafe { LocalObject::<JByteArray>::from_raw(dangling_local()) };
+        let bytes = array.read_all_fast(env.ctx());
+
+        // dropping a local reference needs an attached vm
+        core::mem::forget(array);
+
+        bytes
+    }
+
+    #[test]
+    fn test_read_all_fast_critical() {
+        unsafe extern "C" fn critical(_: *mut JNIEnv, _: jarray, _: *mut jboolean) -> *mut c_void {
+            DATA.as_ptr() as *mut c_void
+        }
+
+        assert_eq!(read_all_fast(critical), DATA);
+    }
+
+    #[test]
+    fn test_read_all_fast_falls_back_to_region() {
+        static CRITICAL_CALLED: AtomicBool = AtomicBool::new(false);
+
+        // the vm can neither pin nor copy the array
+        unsafe extern "C" fn critical(_: *mut JNIEnv, _: jarray, _: *mut jboolean) -> *mut c_void {
+            CRITICAL_CALLED.store(true, Ordering::Relaxed);
+
+            null_mut()
+        }
+
+        assert_eq!(read_all_fast(critical), DATA);
+        assert!(CRITICAL_CALLED.load(Ordering::Relaxed));
+    }
+}
@@ -0,0 +1,81 @@
+use alloc::ffi::CString;
+
+use crate::{class_cache::remap_class_name, resolver, Context, Error, MethodSignature, Signature};
+
+/// The lookup a [`WarmEntry`] is resolved with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MemberKind {
+    Method,
+    StaticMethod,
+    Field,
+    StaticField,
+}
+
+/// A member named by strings and signatures instead of Rust types, e.g. from a list of hot
+/// methods. A later call hits the entry when its arguments and return type have these signatures,
+/// whatever reference types they are passed as. A field has no `args` and `ret` is its type.
+///
+/// ```no_run
+/// # use typed_jni::{cache::{MemberKind, WarmEntry}, JString, Signature, Type};
+/// const HOT: &[WarmEntry] = &[
+///     WarmEntry {
+///         class: "java.lang.Integer",
+///         name: "valueOf",
+///         kind: MemberKind::StaticMethod,
+///         args: &[Signature::Int],
+///         ret: Signature::Object("java/lang/Integer"),
+///     },
+///     WarmEntry {
+///         class: "java.lang.String",
+///         name: "concat",
+///         kind: MemberKind::Method,
+///         args: &[JString::SIGNATURE],
+///         ret: JString::SIGNATURE,
+///     },
+/// ];
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct WarmEntry {
+    /// The binary name, e.g. `java.lang.String`, resolved after the
+    /// [remapper](crate::set_class_name_remapper).
+    pub class: &'static str,
+    pub name: &'static str,
+    pub kind: MemberKind,
+    pub args: &'static [Signature],
+    pub ret: Signature,
+}
+
+/// Resolves `entries` into the member cache ahead of their first calls, so the lookups cost at
+/// startup instead of on a hot path, and a missing member fails there. Stops at the first entry
+/// that cannot be resolved.
+///
+/// The cache is per thread, so warming only helps calls on the current thread. Classes are found
+/// with `FindClass`, which uses the class loader of the calling native method.
+pub fn warm<'ctx>(ctx: &'ctx Context, entries: &[WarmEntry]) -> Result<(), Error<'ctx>> {
+    for entry in entries {
+        let name = CString::new(remap_class_name(entry.class).replace('.', "/")).map_err(|_| Error::InvalidName)?;
+        let class = ctx.find_class(&name)?;
+
+        let signature = MethodSignature {
+            args: entry.args,
+            ret: entry.ret,
+        };
+
+        match entry.kind {
+            MemberKind::Method => {
+                resolver::find_method_by_signature::<false, _>(ctx, &class, entry.name, signature)?;
+            }
+            MemberKind::StaticMethod => {
+                resolver::find_method_by_signature::<true, _>(ctx, &class, entry.name, signature)?;
+            }
+            MemberKind::Field => {
+                resolver::find_field_by_signature::<false, _>(ctx, &class, entry.name, entry.ret)?;
+            }
+            MemberKind::StaticField => {
+                resolver::find_field_by_signature::<true, _>(ctx, &class, entry.name, entry.ret)?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use alloc::{
+    borrow::Cow,
+    collections::BTreeMap,
+    ffi::CString,
+    string::{String, ToString},
+    sync::Arc,
+};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex, RwLock,
+};
+
+use crate::{Context, Error, FromRaw, GlobalObject, JClass, JClassLoader, LocalObject};
+
+type Remapper = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+static REMAPPER: RwLock<Option<Remapper>> = RwLock::new(None);
+
+// lookups skip the lock while no remapper is installed, which is the common case
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// Installs the callback that translates binary names (`com.example.Foo`) before classes are
+/// resolved, e.g. from the mapping file of an obfuscated build. `None` keeps the name. It applies
+/// to [`Class::find_class`](crate::Class::find_class), and so to every class of
+/// [`define_java_class!`](crate::define_java_class), and to [`ClassCache`], but not to the raw
+/// [`Context::find_class`]. The class names in member descriptors are remapped as well, member
+/// names are not. Install it before the first lookup, classes and members already resolved stay
+/// cached.
+///
+/// ```no_run
+/// # use std::collections::HashMap;
+/// let mapping: HashMap<String, String> = HashMap::from([("com.example.Foo".into(), "a.b".into())]);
+///
+/// typed_jni::set_class_name_remapper(move |name| mapping.get(name).cloned());
+/// ```
+pub fn set_class_name_remapper(remapper: impl Fn(&str) -> Option<String> + Send + Sync + 'static) {
+    *REMAPPER.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(remapper));
+
+    INSTALLED.store(true, Ordering::Release);
+}
+
+/// Removes the remapper of [`set_class_name_remapper`], later lookups use the names as they are.
+pub fn clear_class_name_remapper() {
+    INSTALLED.store(false, Ordering::Release);
+
+    *REMAPPER.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+fn remapper() -> Option<Remapper> {
+    if !INSTALLED.load(Ordering::Acquire) {
+        return None;
+    }
+
+    // called without the lock held, so the remapper may resolve classes or install another one
+    REMAPPER.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+pub(crate) fn remap_class_name(name: &str) -> Cow<'_, str> {
+    match remapper().and_then(|remapper| remapper(name)) {
+        Some(remapped) => Cow::Owned(remapped),
+        None => Cow::Borrowed(name),
+    }
+}
+
+/// Remaps an internal name (`com/example/Foo`), `None` if it is kept. The binary name passed to
+/// the remapper is only built while one is installed.
+pub(crate) fn remap_internal_name(name: &str) -> Option<String> {
+    let remapper = remapper()?;
+
+    remapper(&name.replace('/', ".")).map(|remapped| remapped.replace('.', "/"))
+}
+
+/// Remaps every `L<internal name>;` of a type or method descriptor, `None` if nothing changed.
+pub(crate) fn remap_descriptor(descriptor: &str) -> Option<String> {
+    let remapper = remapper()?;
+
+    let mut remapped = String::with_capacity(descriptor.len());
+    let mut changed = false;
+    let mut rest = descriptor;
+
+    while let Some(start) = rest.find('L') {
+        let Some(len) = rest[start..].find(';') else {
+            break;
+        };
+        let name = &rest[start + 1..start + len];
+
+        remapped.push_str(&rest[..=start]);
+        match remapper(&name.replace('/', ".")) {
+            Some(name) => {
+                remapped.push_str(&name.replace('.', "/"));
+                changed = true;
+            }
+            None => remapped.push_str(name),
+        }
+        remapped.push(';');
+
+        rest = &rest[start + len + 1..];
+    }
+    remapped.push_str(rest);
+
+    changed.then_some(remapped)
+}
+
+/// Keeps global references to classes by binary name (`java.lang.String`), so they can be shared
+/// across frames and threads.
+pub struct ClassCache {
+    loader: Option<GlobalObject<JClassLoader>>,
+    classes: Mutex<BTreeMap<String, GlobalObject<JClass>>>,
+}
+
+impl ClassCache {
+    pub const fn new() -> Self {
+        Self {
+            loader: None,
+            classes: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub const fn with_loader(loader: GlobalObject<JClassLoader>) -> Self {
+        Self {
+            loader: Some(loader),
+            classes: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn get_or_load<'ctx>(&self, ctx: &'ctx Context, name: &str) -> Result<GlobalObject<JClass>, Error<'ctx>> {
+        if let Some(class) = self.classes.lock().unwrap().get(name) {
+            return Ok(class.clone());
+        }
+
+        // the lock is not held while loading, class initializers may use the cache as well
+        let class = self.load(ctx, name)?.to_global()?;
+
+        Ok(self.classes.lock().unwrap().entry(name.to_string()).or_insert(class).clone())
+    }
+
+    pub fn remove(&self, name: &str) -> Option<GlobalObject<JClass>> {
+        self.classes.lock().unwrap().remove(name)
+    }
+
+    pub fn clear(&self) {
+        self.classes.lock().unwrap().clear();
+    }
+
+    fn load<'ctx>(&self, ctx: &'ctx Context, name: &str) -> Result<LocalObject<'ctx, JClass>, Error<'ctx>> {
+        let name = &*remap_class_name(name);
+
+        match &self.loader {
+            Some(loader) => loader.call_method(ctx, "loadClass", name),
+            None => unsafe {
+                let name = CString::new(name.replace('.', "/")).map_err(|_| Error::InvalidName)?;
+                let class = ctx.find_class(name)?;
+
+                Ok(LocalObject::from_raw(class))
+            },
+        }
+    }
+}
+
+impl Default for ClassCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,209 @@
+use core::{ffi::CStr, marker::PhantomData};
+
+use crate::{
+    typed::{__CachedClass, __CachedMethod, Signature, Type},
+    AsRaw, Context, Error, FromRaw, JObject, Local, LocalObject, NoArgs, Object, ObjectType, Ref, StrongRef,
+};
+
+/// `java.util.List<T>`. Generics are erased in the vm, `T` only exists on the Rust side: elements
+/// are not checked when read, and a list filled by Java code may hold anything. Use
+/// [`Object::cast`] on elements of a list that is not known to be homogeneous.
+pub struct JList<T: ObjectType>(PhantomData<T>);
+
+impl<T: ObjectType> Type for JList<T> {
+    const SIGNATURE: Signature = Signature::Object("java/util/List");
+}
+
+impl<T: ObjectType> ObjectType for JList<T> {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/util/List");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/util/List;");
+}
+
+static C_LIST: __CachedClass = __CachedClass::new();
+static M_LIST_SIZE: __CachedMethod<false> = __CachedMethod::new();
+static M_LIST_GET: __CachedMethod<false> = __CachedMethod::new();
+static M_LIST_ADD: __CachedMethod<false> = __CachedMethod::new();
+
+impl<T: ObjectType, R: StrongRef> Object<JList<T>, R> {
+    pub fn size(&self, ctx: &Context) -> i32 {
+        let method = C_LIST
+            .get::<JList<T>>(ctx)
+            .and_then(|class| M_LIST_SIZE.get::<_, NoArgs, i32>(ctx, class.as_raw(), "size"))
+            .expect("BROKEN: unable to find List.size().");
+
+        unsafe {
+            ctx.call_method(self.as_raw(), method, [])
+                .expect("BROKEN: List.size() throws.")
+        }
+    }
+
+    pub fn is_empty(&self, ctx: &Context) -> bool {
+        self.size(ctx) == 0
+    }
+
+    /// An out of bounds index is thrown as `IndexOutOfBoundsException`.
+    pub fn get<'ctx>(&self, ctx: &'ctx Context, index: i32) -> Result<Option<LocalObject<'ctx, T>>, Error<'ctx>> {
+        let class = C_LIST.get::<JList<T>>(ctx)?;
+        let method = M_LIST_GET.get::<_, i32, JObject>(ctx, class.as_raw(), "get")?;
+
+        let element = unsafe { ctx.call_method::<false, _, _, Option<Local>>(self.as_raw(), method, [index.into()])? };
+
+        Ok(element.map(|e| unsafe { LocalObject::from_raw(e) }))
+    }
+
+    /// Immutable lists throw `UnsupportedOperationException`.
+    pub fn add<'ctx, ER: Ref>(&self, ctx: &'ctx Context, element: Option<&Object<T, ER>>) -> Result<bool, Error<'ctx>> {
+        let class = C_LIST.get::<JList<T>>(ctx)?;
+        let method = M_LIST_ADD.get::<_, &LocalObject<JObject>, bool>(ctx, class.as_raw(), "add")?;
+
+        unsafe { Ok(ctx.call_method(self.as_raw(), method, [element.map(|e| e.as_raw()).into()])?) }
+    }
+}
+
+/// `java.util.Iterator<T>`. Like [`JList`] the element type is erased, `T` is what
+/// the caller expects the elements to be.
+pub struct JIterator<T: ObjectType>(PhantomData<T>);
+
+impl<T: ObjectType> Type for JIterator<T> {
+    const SIGNATURE: Signature = Signature::Object("java/util/Iterator");
+}
+
+impl<T: ObjectType> ObjectType for JIterator<T> {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/util/Iterator");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/util/Iterator;");
+}
+
+static C_ITERATOR: __CachedClass = __CachedClass::new();
+static M_HAS_NEXT: __CachedMethod<false> = __CachedMethod::new();
+static M_NEXT: __CachedMethod<false> = __CachedMethod::new();
+
+impl<T: ObjectType, R: StrongRef> Object<JIterator<T>, R> {
+    pub fn has_next<'ctx>(&self, ctx: &'ctx Context) -> Result<bool, Error<'ctx>> {
+        let class = C_ITERATOR.get::<JIterator<T>>(ctx)?;
+        let method = M_HAS_NEXT.get::<_, NoArgs, bool>(ctx, class.as_raw(), "hasNext")?;
+
+        unsafe { Ok(ctx.call_method(self.as_raw(), method, [])?) }
+    }
+
+    /// A `null` element is `Ok(None)`. Exhaustion and concurrent modification are thrown as
+    /// `NoSuchElementException` and `ConcurrentModificationException`.
+    pub fn next<'ctx>(&self, ctx: &'ctx Context) -> Result<Option<LocalObject<'ctx, T>>, Error<'ctx>> {
+        let class = C_ITERATOR.get::<JIterator<T>>(ctx)?;
+        let method = M_NEXT.get::<_, NoArgs, JObject>(ctx, class.as_raw(), "next")?;
+
+        let element = unsafe { ctx.call_method::<false, _, _, Option<Local>>(self.as_raw(), method, [])? };
+
+        Ok(element.map(|e| unsafe { LocalObject::from_raw(e) }))
+    }
+
+    /// Adapts to a Rust [`Iterator`]. Every element is its own local reference, dropping it before
+    /// advancing keeps long iterations within the local reference table. Iteration ends after the
+    /// first error.
+    pub fn iter<'a, 'ctx>(&'a self, ctx: &'ctx Context) -> IteratorElements<'a, 'ctx, T, R> {
+        IteratorElements {
+            ctx,
+            iterator: self,
+            done: false,
+        }
+    }
+}
+
+pub struct IteratorElements<'a, 'ctx, T: ObjectType, R: StrongRef> {
+    ctx: &'ctx Context,
+    iterator: &'a Object<JIterator<T>, R>,
+    done: bool,
+}
+
+impl<'ctx, T: ObjectType, R: StrongRef> Iterator for IteratorElements<'_, 'ctx, T, R> {
+    type Item = Result<Option<LocalObject<'ctx, T>>, Error<'ctx>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let item = match self.iterator.has_next(self.ctx) {
+            Ok(true) => self.iterator.next(self.ctx),
+            Ok(false) => {
+                self.done = true;
+
+                return None;
+            }
+            Err(err) => Err(err),
+        };
+
+        self.done = item.is_err();
+
+        Some(item)
+    }
+}
+
+impl<T: ObjectType, R: StrongRef> core::iter::FusedIterator for IteratorElements<'_, '_, T, R> {}
+
+/// `java.util.Set<T>`, with the element type erased like [`JList`].
+pub struct JSet<T: ObjectType>(PhantomData<T>);
+
+impl<T: ObjectType> Type for JSet<T> {
+    const SIGNATURE: Signature = Signature::Object("java/util/Set");
+}
+
+impl<T: ObjectType> ObjectType for JSet<T> {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/util/Set");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/util/Set;");
+}
+
+static C_SET: __CachedClass = __CachedClass::new();
+static M_SET_SIZE: __CachedMethod<false> = __CachedMethod::new();
+static M_SET_CONTAINS: __CachedMethod<false> = __CachedMethod::new();
+static M_SET_ADD: __CachedMethod<false> = __CachedMethod::new();
+static M_SET_ITERATOR: __CachedMethod<false> = __CachedMethod::new();
+
+impl<T: ObjectType, R: StrongRef> Object<JSet<T>, R> {
+    pub fn size(&self, ctx: &Context) -> i32 {
+        let method = C_SET
+            .get::<JSet<T>>(ctx)
+            .and_then(|class| M_SET_SIZE.get::<_, NoArgs, i32>(ctx, class.as_raw(), "size"))
+            .expect("BROKEN: unable to find Set.size().");
+
+        unsafe {
+            ctx.call_method(self.as_raw(), method, [])
+                .expect("BROKEN: Set.size() throws.")
+        }
+    }
+
+    pub fn is_empty(&self, ctx: &Context) -> bool {
+        self.size(ctx) == 0
+    }
+
+    /// Membership by `equals`. Sets that reject `null` or foreign element types may throw
+    /// `NullPointerException` or `ClassCastException`.
+    pub fn contains<'ctx, ER: Ref>(&self, ctx: &'ctx Context, element: Option<&Object<T, ER>>) -> Result<bool, Error<'ctx>> {
+        let class = C_SET.get::<JSet<T>>(ctx)?;
+        let method = M_SET_CONTAINS.get::<_, &LocalObject<JObject>, bool>(ctx, class.as_raw(), "contains")?;
+
+        unsafe { Ok(ctx.call_method(self.as_raw(), method, [element.map(|e| e.as_raw()).into()])?) }
+    }
+
+    /// `false` if the element was already present. Immutable sets throw
+    /// `UnsupportedOperationException`.
+    pub fn add<'ctx, ER: Ref>(&self, ctx: &'ctx Context, element: Option<&Object<T, ER>>) -> Result<bool, Error<'ctx>> {
+        let class = C_SET.get::<JSet<T>>(ctx)?;
+        let method = M_SET_ADD.get::<_, &LocalObject<JObject>, bool>(ctx, class.as_raw(), "add")?;
+
+        unsafe { Ok(ctx.call_method(self.as_raw(), method, [element.map(|e| e.as_raw()).into()])?) }
+    }
+
+    /// Iterate with [`JIterator::iter`](Object::iter). The order is up to the set.
+    pub fn iterator<'ctx>(&self, ctx: &'ctx Context) -> Result<LocalObject<'ctx, JIterator<T>>, Error<'ctx>> {
+        let class = C_SET.get::<JSet<T>>(ctx)?;
+        let method = M_SET_ITERATOR.get::<_, NoArgs, JIterator<T>>(ctx, class.as_raw(), "iterator")?;
+
+        unsafe {
+            Ok(LocalObject::from_raw(ctx.call_method::<false, _, _, Local>(
+                self.as_raw(),
+                method,
+                [],
+            )?))
+        }
+    }
+}
@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use alloc::{string::String, vec::Vec};
+use alloc::{borrow::Cow, collections::VecDeque, ffi::CString, string::String, vec::Vec};
 use core::{
     ffi::CStr,
     marker::PhantomData,
@@ -12,10 +12,11 @@ use crate::{
     builtin::Throwable,
     reference::{Local, Ref, StrongRef},
     sys::{
-        jfieldID, jmethodID, jobject, jvalue, jweak, JNIEnv, JNINativeInterface_, JNINativeMethod, JNI_ABORT, JNI_COMMIT,
-        JNI_FALSE, JNI_OK, JNI_VERSION_1_4,
+        jboolean, jfieldID, jmethodID, jobject, jvalue, jweak, JNIEnv, JNINativeInterface_, JNINativeMethod, JavaVMAttachArgs,
+        JNI_ABORT, JNI_COMMIT, JNI_FALSE, JNI_OK, JNI_TRUE, JNI_VERSION_1_4,
     },
-    vm, AsRaw, FromRaw, IntoRaw, LocalObject, Raw,
+    vm::{self, VmError},
+    AsRaw, Error, FromRaw, Global, IntoRaw, LocalObject, Raw,
 };
 
 mod __sealed {
@@ -24,33 +25,186 @@ mod __sealed {
 
 macro_rules! call {
     ($this:ident, $func_name:ident) => {
-        { $this.run(|| { $this.env.as_ref().$func_name.unwrap()(&$this.env as *const _ as *mut _) }) }
+        { $this.check_pending(stringify!($func_name)); $this.run(|| { $this.env.as_ref().$func_name.unwrap()(&$this.env as *const _ as *mut _) }) }
     };
     ($this:ident, $func_name:ident, $($args:expr),*) => {
-        { $this.run(|| { $this.env.as_ref().$func_name.unwrap()(&$this.env as *const _ as *mut _, $($args),*) }) }
+        { $this.check_pending(stringify!($func_name)); $this.run(|| { $this.env.as_ref().$func_name.unwrap()(&$this.env as *const _ as *mut _, $($args),*) }) }
     };
 }
 
 macro_rules! call_nothrow {
     ($this:ident, $func_name:ident) => {
-        { $this.run_no_throw(|| { $this.env.as_ref().$func_name.unwrap()(&$this.env as *const _ as *mut _) }) }
+        { $this.check_pending(stringify!($func_name)); $this.run_no_throw(|| { $this.env.as_ref().$func_name.unwrap()(&$this.env as *const _ as *mut _) }) }
     };
     ($this:ident, $func_name:ident, $($args:expr),*) => {
-        { $this.run_no_throw(|| { $this.env.as_ref().$func_name.unwrap()(&$this.env as *const _ as *mut _, $($args),*) }) }
+        { $this.check_pending(stringify!($func_name)); $this.run_no_throw(|| { $this.env.as_ref().$func_name.unwrap()(&$this.env as *const _ as *mut _, $($args),*) }) }
     };
 }
 
+// the functions JNI allows while an exception is pending, besides the exception functions
+#[cfg(all(feature = "debug-assert-exceptions", debug_assertions))]
+const ALLOWED_WITH_PENDING: &[&str] = &[
+    "DeleteLocalRef",
+    "DeleteGlobalRef",
+    "DeleteWeakGlobalRef",
+    "MonitorExit",
+    "PushLocalFrame",
+    "PopLocalFrame",
+    "ReleaseStringChars",
+    "ReleaseStringUTFChars",
+    "ReleaseStringCritical",
+    "ReleasePrimitiveArrayCritical",
+    "ReleaseBooleanArrayElements",
+    "ReleaseByteArrayElements",
+    "ReleaseCharArrayElements",
+    "ReleaseShortArrayElements",
+    "ReleaseIntArrayElements",
+    "ReleaseLongArrayElements",
+    "ReleaseFloatArrayElements",
+    "ReleaseDoubleArrayElements",
+];
+
+/// `&Context` is the `JNIEnv` pointer itself, so `Context` is only ever used behind a reference
+/// and is intentionally neither `Clone` nor `Copy`: a copy would live at a different address and
+/// no longer be a valid `JNIEnv`. `&'ctx Context` is `Copy` and the `'ctx` lifetime keeps it, and
+/// every local reference created from it, from escaping the current native frame.
+///
+/// ```compile_fail
+/// fn copy(ctx: &typed_jni::Context) -> typed_jni::Context {
+///     *ctx
+/// }
+/// ```
+///
+/// To keep the env around for the duration of a native call, store the `&'ctx Context` itself in
+/// a helper struct. The struct takes over the `'ctx` lifetime, so it is bound to the frame too:
+///
+/// ```no_run
+/// # use typed_jni::{Context, JString, LocalObject, TrampolineObject};
+/// # typed_jni::define_java_class!(JavaLogger, "com.example.Logger");
+/// struct Logger<'ctx> {
+///     ctx: &'ctx Context,
+///     prefix: LocalObject<'ctx, JString>,
+/// }
+///
+/// impl<'ctx> Logger<'ctx> {
+///     fn log(&self, message: &str) -> String {
+///         format!("{}: {}", self.prefix.get_string(self.ctx), message)
+///     }
+/// }
+///
+/// extern "C" fn log<'ctx>(ctx: &'ctx Context, _: TrampolineObject<'ctx, JavaLogger>) {
+///     let logger = Logger { ctx, prefix: JString::new(ctx, "native") };
+///
+///     println!("{}", logger.log("called"));
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # use typed_jni::Context;
+/// struct Helper<'ctx> {
+///     ctx: &'ctx Context,
+/// }
+///
+/// fn escape<'ctx>(ctx: &'ctx Context) -> Helper<'static> {
+///     Helper { ctx }
+/// }
+/// ```
 #[repr(transparent)]
 pub struct Context {
     env: NonNull<JNINativeInterface_>,
 }
 
 impl Context {
+    /// Shortens a `&&Context` (or any longer borrow) to a plain `&Context`.
+    pub fn reborrow(&self) -> &Self {
+        self
+    }
+
+    /// Leaves `throwable` pending, it is thrown to the Java caller when the native method returns.
+    ///
+    /// # Safety
+    ///
+    /// `throwable` must be a `java.lang.Throwable`.
     pub unsafe fn throw<R: StrongRef>(&self, throwable: &R) {
         unsafe { self.env.as_ref().Throw.unwrap()(self.as_raw(), *throwable.as_raw()) };
     }
 
-    fn run<R>(&self, f: impl FnOnce() -> R) -> Result<R, LocalObject<Throwable>> {
+    /// Leaves `throwable` pending again, the same object with its original stack trace, e.g. after
+    /// logging it. It is thrown to the Java caller when the native method returns.
+    pub fn rethrow(&self, throwable: LocalObject<'_, Throwable>) {
+        unsafe { self.throw(throwable.as_raw()) }
+    }
+
+    /// Constructs a `class` with `message` and leaves it pending, like [`throw`](Self::throw).
+    ///
+    /// # Safety
+    ///
+    /// `class` must be a class of `java.lang.Throwable` or a subclass.
+    pub unsafe fn throw_new<R: StrongRef>(&self, class: &R, message: &str) {
+        let message = to_modified_utf8(message);
+
+        unsafe { self.env.as_ref().ThrowNew.unwrap()(self.as_raw(), *class.as_raw(), message.as_ptr()) };
+    }
+
+    /// Discards the pending exception, if any.
+    pub fn exception_clear(&self) {
+        unsafe { self.env.as_ref().ExceptionClear.unwrap()(self.as_raw()) };
+    }
+
+    pub fn exception_check(&self) -> bool {
+        unsafe { self.env.as_ref().ExceptionCheck.unwrap()(self.as_raw()) != JNI_FALSE }
+    }
+
+    /// The pending exception, which unlike the error of a failed call stays pending. While it is,
+    /// JNI only allows the exception functions, releasing string and array elements, deleting
+    /// references, [`MonitorGuard`](crate::MonitorGuard) exits and local frames, so inspecting it
+    /// usually means [`exception_clear`](Self::exception_clear) first and
+    /// [`rethrow`](Self::rethrow) if it is not handled:
+    ///
+    /// ```no_run
+    /// # use typed_jni::Context;
+    /// # fn after_callback(ctx: &Context) {
+    /// if let Some(throwable) = ctx.exception_occurred() {
+    ///     ctx.exception_clear();
+    ///
+    ///     if !throwable.to_string().contains("expected") {
+    ///         ctx.rethrow(throwable);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn exception_occurred(&self) -> Option<LocalObject<'_, Throwable>> {
+        let raw = unsafe { self.env.as_ref().ExceptionOccurred.unwrap()(self.as_raw()) };
+
+        (!raw.is_null()).then(|| unsafe { LocalObject::from_raw(Local::from_raw(raw)) })
+    }
+
+    /// Runs a sequence of calls as one exception boundary, the first call that throws ends it with
+    /// `?` and its exception is returned already cleared, as every call of this crate does.
+    ///
+    /// A pending exception at entry is a bug in the caller, asserted in debug builds and discarded
+    /// otherwise.
+    ///
+    /// ```no_run
+    /// # use typed_jni::{Context, JString, LocalObject, NoArgs};
+    /// # fn upper(ctx: &Context, s: &LocalObject<JString>) {
+    /// let upper = ctx.try_block(|ctx| {
+    ///     let trimmed: LocalObject<JString> = s.call_method(ctx, "trim", NoArgs)?;
+    ///     let upper: LocalObject<JString> = trimmed.call_method(ctx, "toUpperCase", NoArgs)?;
+    ///
+    ///     Ok(upper.get_string(ctx))
+    /// });
+    /// # }
+    /// ```
+    pub fn try_block<'ctx, R>(&'ctx self, f: impl FnOnce(&'ctx Context) -> Result<R, Error<'ctx>>) -> Result<R, Error<'ctx>> {
+        debug_assert!(!self.exception_check(), "BROKEN: exception pending on entering try_block");
+
+        self.exception_clear();
+
+        f(self)
+    }
+
+    fn run<R>(&self, f: impl FnOnce() -> R) -> Result<R, LocalObject<'_, Throwable>> {
         unsafe {
             let ex = self.env.as_ref().ExceptionOccurred.unwrap()(self.as_raw());
             if !ex.is_null() {
@@ -81,6 +235,20 @@ impl Context {
         }
     }
 
+    /// With the `debug-assert-exceptions` feature, debug builds panic when `function` would be
+    /// called with an exception pending, which usually means an earlier error was ignored. Without
+    /// it a pending exception is set aside around the call and left pending afterwards.
+    #[inline(always)]
+    fn check_pending(&self, function: &'static str) {
+        #[cfg(all(feature = "debug-assert-exceptions", debug_assertions))]
+        if !ALLOWED_WITH_PENDING.contains(&function) && self.exception_check() {
+            panic!("{function} called with a pending exception, an earlier error was not handled");
+        }
+
+        #[cfg(not(all(feature = "debug-assert-exceptions", debug_assertions)))]
+        let _ = function;
+    }
+
     fn run_no_throw<R>(&self, f: impl FnOnce() -> R) -> R {
         self.run(f).expect("BROKEN: jvm throw unexpected exception.")
     }
@@ -93,13 +261,68 @@ impl Context {
 }
 
 impl Context {
+    /// # Safety
+    ///
+    /// Same as [`from_raw_env`](Self::from_raw_env).
+    #[allow(clippy::transmute_ptr_to_ref)]
     pub unsafe fn from_raw<'a>(env: *mut JNIEnv) -> &'a Self {
         unsafe { core::mem::transmute(env) }
     }
 
+    /// The `JNIEnv` pointer, to call function table entries this crate does not wrap.
+    ///
+    /// It is only valid on the current thread and within the native frame `self` belongs to.
+    /// Exceptions thrown through it are not checked or cleared, so the caller has to handle them
+    /// before using this crate again.
+    ///
+    /// ```no_run
+    /// # use typed_jni::Context;
+    /// # fn version(ctx: &Context) -> i32 {
+    /// let env = ctx.as_raw();
+    ///
+    /// unsafe { (**env).GetVersion.unwrap()(env) }
+    /// # }
+    /// ```
     pub fn as_raw(&self) -> *mut JNIEnv {
         &self.env as *const _ as *mut _
     }
+
+    /// A `Context` for a `JNIEnv` handed to native code directly, e.g. by a load hook, before or
+    /// without [`attach_vm`](crate::attach_vm).
+    ///
+    /// References dropped through it still need an attached vm, so attach first when possible.
+    /// `FindClass` in `JNI_OnLoad` uses the class loader of the library, which makes it the place
+    /// to resolve application classes that later native threads cannot find:
+    ///
+    /// ```no_run
+    /// # use core::{ffi::c_void, ptr::null_mut};
+    /// # use typed_jni::{sys::{JNIEnv, JavaVM, JNI_ERR, JNI_VERSION_1_6}, Context};
+    /// # typed_jni::define_java_class!(JavaCallback, "com.example.Callback");
+    /// #[no_mangle]
+    /// pub unsafe extern "C" fn JNI_OnLoad(vm: *mut JavaVM, _: *mut c_void) -> i32 {
+    ///     if typed_jni::attach_vm(vm).is_err() {
+    ///         return JNI_ERR;
+    ///     }
+    ///
+    ///     let mut env: *mut JNIEnv = null_mut();
+    ///     unsafe { (**vm).GetEnv.unwrap()(vm, (&mut env as *mut *mut JNIEnv).cast(), JNI_VERSION_1_6 as i32) };
+    ///
+    ///     let ctx = unsafe { Context::from_raw_env(env) };
+    ///
+    ///     // cached as a global reference, later lookups from any thread skip `FindClass`
+    ///     JavaCallback::class(ctx).expect("class not found");
+    ///
+    ///     JNI_VERSION_1_6 as i32
+    /// }
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// `env` must be a valid `JNIEnv` of the current thread and the returned `Context` must not
+    /// outlive it or leave that thread.
+    pub unsafe fn from_raw_env<'a>(env: *mut JNIEnv) -> &'a Self {
+        unsafe { Self::from_raw(env) }
+    }
 }
 
 fn current_context<'a>() -> Option<&'a Context> {
@@ -117,49 +340,198 @@ fn current_context<'a>() -> Option<&'a Context> {
 }
 
 impl Context {
-    pub fn with_current<R>(f: impl FnOnce(&Self) -> R) -> Option<R> {
-        let ctx = current_context()?;
+    /// Runs `f` with the `Context` of the current thread. Without an attached vm, or on a thread
+    /// not attached to it, it is [`Error::Detached`], so threading mistakes surface as errors. Code
+    /// that already holds the `JNIEnv` of a native call can skip the `GetEnv` lookup with
+    /// [`from_raw`](Self::from_raw).
+    pub fn with_current<R>(f: impl FnOnce(&Self) -> R) -> Result<R, Error<'static>> {
+        if !vm::is_vm_available() {
+            return Err(Error::Detached);
+        }
 
-        Some(f(&ctx))
+        current_context().map(f).ok_or(Error::Detached)
+    }
+
+    /// Like [`with_current`](Self::with_current) without the checks, for hot paths on threads
+    /// known to be attached.
+    ///
+    /// # Safety
+    ///
+    /// A vm must be attached with [`attach_vm`](crate::attach_vm) and the current thread attached
+    /// to it.
+    pub unsafe fn with_current_unchecked<R>(f: impl FnOnce(&Self) -> R) -> R {
+        let vm = vm::require_vm();
+        let mut env: *mut JNIEnv = null_mut();
+
+        unsafe {
+            (**vm).GetEnv.unwrap()(vm, (&mut env as *mut *mut JNIEnv).cast(), JNI_VERSION_1_4 as i32);
+
+            f(Context::from_raw(env))
+        }
     }
 
     pub fn with_attached<R>(f: impl FnOnce(&Self) -> R) -> R {
-        match current_context() {
-            None => unsafe {
-                let vm = vm::require_vm();
-                let mut env: *mut JNIEnv = null_mut();
+        with_attached(None, f)
+    }
 
-                let attached = (**vm).AttachCurrentThread.unwrap()(vm, (&mut env as *mut *mut JNIEnv).cast(), null_mut());
-                if attached == JNI_OK {
-                    let ctx = Context::from_raw(env);
+    /// `name` and `group` only take effect if the current thread is not attached yet.
+    pub fn with_attached_named<R>(name: &str, group: Option<&Global>, f: impl FnOnce(&Self) -> R) -> R {
+        let name = to_modified_utf8(name);
 
-                    let ret = f(&ctx);
+        let mut args = JavaVMAttachArgs {
+            version: JNI_VERSION_1_4 as _,
+            name: name.as_ptr() as _,
+            group: group.map(|g| *g.as_raw()).unwrap_or(null_mut()),
+        };
+
+        with_attached(Some(&mut args), f)
+    }
+}
 
-                    (**vm).DetachCurrentThread.unwrap()(vm);
+impl Context {
+    /// Attaches the current thread until [`detach_current_thread`](Self::detach_current_thread),
+    /// for threads that run many bursts of JNI calls, e.g. pooled workers. Meanwhile
+    /// [`with_attached`](Self::with_attached) finds the thread attached and leaves it attached.
+    /// A no-op if the thread is attached already.
+    pub fn attach_current_thread() -> Result<(), VmError> {
+        if current_context().is_some() {
+            return Ok(());
+        }
 
-                    ret
-                } else {
-                    panic!("BROKEN: unable to attach current thread.")
+        unsafe {
+            let vm = vm::require_vm();
+            let mut env: *mut JNIEnv = null_mut();
+
+            match (**vm).AttachCurrentThread.unwrap()(vm, (&mut env as *mut *mut JNIEnv).cast(), null_mut()) {
+                JNI_OK => Ok(()),
+                err => Err(VmError(err)),
+            }
+        }
+    }
+
+    /// Detaches a thread attached with [`attach_current_thread`](Self::attach_current_thread),
+    /// releasing its monitors and remaining local references. Threads running Java code, e.g.
+    /// inside a native method, cannot detach.
+    ///
+    /// # Safety
+    ///
+    /// No `&Context` of this thread and no local reference created through one may be used
+    /// afterwards, so call it outside of any [`with_attached`](Self::with_attached) or
+    /// [`with_current`](Self::with_current) closure and with no `Local` or `LocalObject` left to
+    /// drop.
+    pub unsafe fn detach_current_thread() -> Result<(), VmError> {
+        unsafe {
+            let vm = vm::require_vm();
+
+            match (**vm).DetachCurrentThread.unwrap()(vm) {
+                JNI_OK => {
+                    // every local reference of the thread is gone now
+                    #[cfg(feature = "debug-refs")]
+                    crate::debug_refs::check_leaked(0, "detaching current thread");
+
+                    Ok(())
                 }
-            },
-            Some(ctx) => f(&ctx),
+                err => Err(VmError(err)),
+            }
+        }
+    }
+}
+
+fn with_attached<R>(args: Option<&mut JavaVMAttachArgs>, f: impl FnOnce(&Context) -> R) -> R {
+    match current_context() {
+        None => unsafe {
+            let vm = vm::require_vm();
+            let mut env: *mut JNIEnv = null_mut();
+
+            let args = args.map(|a| a as *mut JavaVMAttachArgs).unwrap_or(null_mut());
+
+            let attached = (**vm).AttachCurrentThread.unwrap()(vm, (&mut env as *mut *mut JNIEnv).cast(), args.cast());
+            if attached == JNI_OK {
+                let ctx = Context::from_raw(env);
+
+                #[cfg(feature = "debug-refs")]
+                let outstanding = crate::debug_refs::outstanding_local_refs();
+
+                let ret = f(ctx);
+
+                #[cfg(feature = "debug-refs")]
+                crate::debug_refs::check_leaked(outstanding, "detaching current thread");
+
+                (**vm).DetachCurrentThread.unwrap()(vm);
+
+                ret
+            } else {
+                panic!("BROKEN: unable to attach current thread.")
+            }
+        },
+        Some(ctx) => f(ctx),
+    }
+}
+
+pub(crate) fn to_modified_utf8(s: &str) -> CString {
+    let mut bytes = Vec::with_capacity(s.len() + 1);
+
+    for c in s.chars() {
+        match c {
+            '\0' => bytes.extend_from_slice(&[0xC0, 0x80]),
+            c if c as u32 > 0xFFFF => {
+                for unit in c.encode_utf16(&mut [0u16; 2]) {
+                    bytes.extend_from_slice(&[
+                        0xE0 | (*unit >> 12) as u8,
+                        0x80 | ((*unit >> 6) & 0x3F) as u8,
+                        0x80 | (*unit & 0x3F) as u8,
+                    ]);
+                }
+            }
+            c => bytes.extend_from_slice(c.encode_utf8(&mut [0u8; 4]).as_bytes()),
         }
     }
+
+    unsafe { CString::from_vec_unchecked(bytes) }
 }
 
+const STACK_STRING_CAPACITY: usize = 128;
+
 impl Context {
-    pub fn new_string(&self, s: impl AsRef<str>) -> Local {
+    /// Short ASCII strings are passed to `NewStringUTF` through a stack buffer, anything else is
+    /// encoded to a temporary UTF-16 buffer first.
+    pub fn new_string(&self, s: impl AsRef<str>) -> Local<'_> {
+        let s = s.as_ref();
+
+        if s.len() < STACK_STRING_CAPACITY && s.is_ascii() && !s.as_bytes().contains(&0) {
+            let mut buf = [0u8; STACK_STRING_CAPACITY];
+            buf[..s.len()].copy_from_slice(s.as_bytes());
+
+            return unsafe { self.new_string_utf(CStr::from_bytes_with_nul_unchecked(&buf[..=s.len()])) };
+        }
+
+        self.new_string_utf16(&s.encode_utf16().collect::<Vec<_>>())
+    }
+
+    pub fn new_string_utf16(&self, s: &[u16]) -> Local<'_> {
         unsafe {
             self.ensure_local_capacity(4);
 
-            let u16s = s.as_ref().encode_utf16().collect::<Vec<_>>();
-
-            let obj = call_nothrow!(self, NewString, u16s.as_ptr(), u16s.len() as _);
+            let obj = call_nothrow!(self, NewString, s.as_ptr(), s.len() as _);
 
             Local::from_raw(obj)
         }
     }
 
+    /// # Safety
+    ///
+    /// `s` must be valid modified UTF-8.
+    pub unsafe fn new_string_utf(&self, s: &CStr) -> Local<'_> {
+        unsafe {
+            self.ensure_local_capacity(4);
+
+            Local::from_raw(call_nothrow!(self, NewStringUTF, s.as_ptr()))
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `s` must be a `java.lang.String`.
     pub unsafe fn get_string<R: StrongRef>(&self, s: &R) -> String {
         unsafe {
             let obj = s.as_raw();
@@ -176,20 +548,88 @@ impl Context {
         }
     }
 
-    pub fn get_object_class<R: StrongRef>(&self, object: &R) -> Local {
+    /// # Safety
+    ///
+    /// `s` must be a `java.lang.String`.
+    pub unsafe fn get_string_length<R: StrongRef>(&self, s: &R) -> i32 {
+        unsafe { call_nothrow!(self, GetStringLength, *s.as_raw()) }
+    }
+
+    /// # Safety
+    ///
+    /// `s` must be a `java.lang.String`.
+    pub unsafe fn get_string_region<R: StrongRef>(
+        &self,
+        s: &R,
+        start: i32,
+        buf: &mut [u16],
+    ) -> Result<(), LocalObject<'_, Throwable>> {
+        unsafe {
+            call!(
+                self,
+                GetStringRegion,
+                *s.as_raw(),
+                start,
+                buf.len().try_into().unwrap(),
+                buf.as_mut_ptr()
+            )
+        }
+    }
+
+    /// Encodes `len` UTF-16 units from `start` as modified UTF-8 into `buf` followed by a NUL.
+    ///
+    /// # Safety
+    ///
+    /// `s` must be a `java.lang.String` and `buf` must be large enough for the encoded units.
+    pub unsafe fn get_string_utf_region<R: StrongRef>(
+        &self,
+        s: &R,
+        start: i32,
+        len: i32,
+        buf: *mut u8,
+    ) -> Result<(), LocalObject<'_, Throwable>> {
+        unsafe { call!(self, GetStringUTFRegion, *s.as_raw(), start, len, buf.cast()) }
+    }
+
+    /// A direct `java.nio.ByteBuffer` over `capacity` bytes at `address`, `LookupFailed` if the vm
+    /// does not support direct buffers.
+    ///
+    /// # Safety
+    ///
+    /// The memory must stay valid for as long as Java uses the buffer.
+    pub unsafe fn new_direct_byte_buffer(&self, address: *mut u8, capacity: usize) -> Result<Local<'_>, Error<'_>> {
+        unsafe { lookup_result(call!(self, NewDirectByteBuffer, address.cast(), capacity as i64)).map(|r| Local::from_raw(r)) }
+    }
+
+    pub fn get_object_class<R: StrongRef>(&self, object: &R) -> Local<'_> {
         self.ensure_local_capacity(4);
 
         unsafe { Local::from_raw(call_nothrow!(self, GetObjectClass, *object.as_raw())) }
     }
 
+    /// # Safety
+    ///
+    /// `class` must be a `java.lang.Class`, and `superclass` too for
+    /// [`is_assignable_from`](Self::is_assignable_from).
     pub unsafe fn is_instance_of<R1: StrongRef, R2: StrongRef>(&self, object: &R1, class: &R2) -> bool {
         unsafe { call_nothrow!(self, IsInstanceOf, *object.as_raw(), *class.as_raw()) != JNI_FALSE }
     }
 
+    #[allow(clippy::missing_safety_doc)]
     pub unsafe fn is_assignable_from<R1: StrongRef, R2: StrongRef>(&self, class: &R1, superclass: &R2) -> bool {
         unsafe { call_nothrow!(self, IsAssignableFrom, *class.as_raw(), *superclass.as_raw()) != JNI_FALSE }
     }
 
+    /// Reference identity, not `equals`. `None` stands for `null`, so a cleared weak reference is the
+    /// same object as `None`. Typed objects pass their references with `as_raw`:
+    ///
+    /// ```no_run
+    /// # use typed_jni::{AsRaw, Context, JString, LocalObject};
+    /// # fn check(ctx: &Context, a: &LocalObject<JString>, b: &LocalObject<JString>) {
+    /// let same = ctx.is_same_object(Some(a.as_raw()), Some(b.as_raw()));
+    /// let null = ctx.is_same_object(Some(a.as_raw()), None::<&typed_jni::Global>);
+    /// # }
+    /// ```
     pub fn is_same_object<R1: Ref, R2: Ref>(&self, a: Option<&R1>, b: Option<&R2>) -> bool {
         unsafe {
             call_nothrow!(
@@ -202,6 +642,8 @@ impl Context {
     }
 }
 
+// handles passed to the reference functions must be live and of the kind the JNI function expects
+#[allow(clippy::missing_safety_doc)]
 impl Context {
     pub unsafe fn new_global_ref(&self, object: jobject) -> jobject {
         unsafe { call_nothrow!(self, NewGlobalRef, object) }
@@ -268,9 +710,39 @@ macro_rules! define_member {
 define_member!(Method, jmethodID);
 define_member!(Field, jfieldID);
 
+fn lookup_result<T>(raw: Result<*mut T, LocalObject<'_, Throwable>>) -> Result<*mut T, Error<'_>> {
+    match raw {
+        Ok(raw) if raw.is_null() => Err(Error::LookupFailed),
+        Ok(raw) => Ok(raw),
+        Err(err) => Err(Error::Throwable(err)),
+    }
+}
+
 impl Context {
-    pub fn find_class(&self, name: impl AsRef<CStr>) -> Result<Local, LocalObject<Throwable>> {
-        unsafe { call!(self, FindClass, name.as_ref().as_ptr()).map(|r| Local::from_raw(r)) }
+    pub fn find_class(&self, name: impl AsRef<CStr>) -> Result<Local<'_>, Error<'_>> {
+        unsafe { lookup_result(call!(self, FindClass, name.as_ref().as_ptr())).map(|r| Local::from_raw(r)) }
+    }
+
+    /// Defines the class in `bytes`, the contents of a class file, in `loader`, `None` is the
+    /// bootstrap class loader. A class the loader has defined already is a `LinkageError`, and
+    /// Android does not support defining classes this way at all.
+    pub fn define_class<R: StrongRef>(
+        &self,
+        name: impl AsRef<CStr>,
+        loader: Option<&R>,
+        bytes: &[u8],
+    ) -> Result<Local<'_>, Error<'_>> {
+        unsafe {
+            lookup_result(call!(
+                self,
+                DefineClass,
+                name.as_ref().as_ptr(),
+                loader.map(|r| *r.as_raw()).unwrap_or(null_mut()),
+                bytes.as_ptr().cast(),
+                bytes.len().try_into().unwrap()
+            ))
+            .map(|r| Local::from_raw(r))
+        }
     }
 
     pub fn find_method<const STATIC: bool, C: StrongRef>(
@@ -278,7 +750,7 @@ impl Context {
         class: &C,
         name: impl AsRef<CStr>,
         signature: impl AsRef<CStr>,
-    ) -> Result<Method<STATIC>, LocalObject<Throwable>> {
+    ) -> Result<Method<STATIC>, Error<'_>> {
         unsafe {
             let raw = if STATIC {
                 call!(
@@ -298,7 +770,94 @@ impl Context {
                 )
             };
 
-            raw.map(|id| Method::from_raw(id))
+            lookup_result(raw).map(|id| Method::from_raw(id))
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `method` must be a `java.lang.reflect.Method` or `Constructor`, and `STATIC` must match its
+    /// modifiers.
+    pub unsafe fn from_reflected_method<const STATIC: bool, M: StrongRef>(
+        &self,
+        method: &M,
+    ) -> Result<Method<STATIC>, Error<'_>> {
+        unsafe { lookup_result(call!(self, FromReflectedMethod, *method.as_raw())).map(|id| Method::from_raw(id)) }
+    }
+
+    pub fn find_method_recursive<const STATIC: bool, C: StrongRef>(
+        &self,
+        class: &C,
+        name: impl AsRef<CStr>,
+        signature: impl AsRef<CStr>,
+    ) -> Result<Method<STATIC>, Error<'_>> {
+        let err = match self.find_method(class, name.as_ref(), signature.as_ref()) {
+            Ok(m) => return Ok(m),
+            Err(err) => err,
+        };
+
+        let m_get_interfaces: Method<false> = match self
+            .find_class(c"java/lang/Class")
+            .and_then(|c| self.find_method(&c, c"getInterfaces", c"()[Ljava/lang/Class;"))
+        {
+            Ok(m) => m,
+            Err(_) => return Err(err),
+        };
+
+        let mut pending = VecDeque::from([class.to_local(self)]);
+        // an interface inherited along several paths is probed once
+        let mut visited: Vec<Local> = Vec::new();
+        let mut root = true;
+
+        while let Some(current) = pending.pop_front() {
+            if !core::mem::take(&mut root) {
+                if let Ok(m) = self.find_method(&current, name.as_ref(), signature.as_ref()) {
+                    return Ok(m);
+                }
+            }
+
+            let mut enqueue = |class| {
+                if !visited
+                    .iter()
+                    .chain(pending.iter())
+                    .any(|c| self.is_same_object(Some(c), Some(&class)))
+                {
+                    pending.push_back(class);
+                }
+            };
+
+            if let Some(superclass) = self.get_superclass(&current) {
+                enqueue(superclass);
+            }
+
+            unsafe {
+                if let Ok(Some(interfaces)) = self.call_method::<false, _, _, Option<Local>>(&current, m_get_interfaces, []) {
+                    for index in 0..self.get_array_length(&interfaces) {
+                        if let Ok(Some(interface)) = self.get_object_array_element(&interfaces, index) {
+                            enqueue(interface);
+                        }
+                    }
+                }
+            }
+
+            visited.push(current);
+        }
+
+        self.find_class(c"java/lang/Object")
+            .and_then(|c| self.find_method(&c, name.as_ref(), signature.as_ref()))
+            .map_err(|_| err)
+    }
+
+    pub fn get_superclass<C: StrongRef>(&self, class: &C) -> Option<Local<'_>> {
+        unsafe {
+            self.ensure_local_capacity(4);
+
+            let raw = call_nothrow!(self, GetSuperclass, *class.as_raw());
+            if raw.is_null() {
+                None
+            } else {
+                Some(Local::from_raw(raw))
+            }
         }
     }
 
@@ -307,7 +866,7 @@ impl Context {
         class: &C,
         name: impl AsRef<CStr>,
         signature: impl AsRef<CStr>,
-    ) -> Result<Field<STATIC>, LocalObject<Throwable>> {
+    ) -> Result<Field<STATIC>, Error<'_>> {
         unsafe {
             let raw = if STATIC {
                 call!(
@@ -327,7 +886,7 @@ impl Context {
                 )
             };
 
-            raw.map(|id| Field::from_raw(id))
+            lookup_result(raw).map(|id| Field::from_raw(id))
         }
     }
 }
@@ -349,12 +908,17 @@ pub enum CallArg<'a> {
     Float(f32),
     Double(f64),
     Object(Option<AnyObject<'a>>),
+    OwnedObject(Local<'a>),
+    /// Converted to a `java.lang.String` with the context of the call it is passed to.
+    Str(Cow<'a, str>),
 }
 
 impl<'a> CallArg<'a> {
-    fn as_raw(&self) -> jvalue {
+    fn as_raw<'ctx>(&self, ctx: &'ctx Context, strings: &mut Vec<Local<'ctx>>) -> jvalue {
         match self {
-            CallArg::Boolean(z) => jvalue { z: *z },
+            CallArg::Boolean(z) => jvalue {
+                z: jboolean_from_bool(*z),
+            },
             CallArg::Byte(b) => jvalue { b: *b },
             CallArg::Char(c) => jvalue { c: *c },
             CallArg::Short(s) => jvalue { s: *s },
@@ -364,6 +928,15 @@ impl<'a> CallArg<'a> {
             CallArg::Double(d) => jvalue { d: *d },
             CallArg::Object(Some(obj)) => jvalue { l: obj.raw },
             CallArg::Object(None) => jvalue { l: null_mut() },
+            CallArg::OwnedObject(obj) => jvalue { l: *obj.as_raw() },
+            CallArg::Str(s) => {
+                let s = ctx.new_string(s);
+                let l = *s.as_raw();
+
+                strings.push(s);
+
+                jvalue { l }
+            }
         }
     }
 }
@@ -387,6 +960,8 @@ impl_value_from!(i64, Long);
 impl_value_from!(f32, Float);
 impl_value_from!(f64, Double);
 
+/// Any reference is passed as is, so a `&GlobalObject<T>` argument needs no local copy. A weak
+/// reference should be upgraded first, once it is cleared the callee receives `null`.
 impl<'a, R: Ref> From<&'a R> for CallArg<'a> {
     fn from(value: &'a R) -> Self {
         Self::Object(Some(AnyObject {
@@ -396,6 +971,18 @@ impl<'a, R: Ref> From<&'a R> for CallArg<'a> {
     }
 }
 
+impl<'a, 's: 'a> From<&'s str> for CallArg<'a> {
+    fn from(value: &'s str) -> Self {
+        Self::Str(Cow::Borrowed(value))
+    }
+}
+
+impl<'a> From<String> for CallArg<'a> {
+    fn from(value: String) -> Self {
+        Self::Str(Cow::Owned(value))
+    }
+}
+
 impl<'a, R: Ref> From<Option<&'a R>> for CallArg<'a> {
     fn from(value: Option<&'a R>) -> Self {
         Self::Object(value.map(|r| AnyObject {
@@ -405,6 +992,19 @@ impl<'a, R: Ref> From<Option<&'a R>> for CallArg<'a> {
     }
 }
 
+/// `jboolean` is a byte: conforming vms return `JNI_TRUE`/`JNI_FALSE`, but any nonzero value is true.
+fn bool_from_jboolean(z: jboolean) -> bool {
+    z != JNI_FALSE
+}
+
+fn jboolean_from_bool(z: bool) -> jboolean {
+    if z {
+        JNI_TRUE
+    } else {
+        JNI_FALSE
+    }
+}
+
 #[doc(hidden)]
 pub trait CallResult<'ctx>: Sized + __sealed::Sealed + 'ctx {
     unsafe fn call<const STATIC: bool, T: StrongRef>(
@@ -417,6 +1017,9 @@ pub trait CallResult<'ctx>: Sized + __sealed::Sealed + 'ctx {
 
 macro_rules! impl_call_result {
     ($typ:ty, $call:ident, $call_static:ident) => {
+        impl_call_result!($typ, $call, $call_static, core::convert::identity);
+    };
+    ($typ:ty, $call:ident, $call_static:ident, $from_raw:path) => {
         impl __sealed::Sealed for $typ {}
 
         impl<'ctx> CallResult<'ctx> for $typ {
@@ -427,11 +1030,13 @@ macro_rules! impl_call_result {
                 args: &[jvalue],
             ) -> Result<Self, LocalObject<'ctx, Throwable>> {
                 unsafe {
-                    if STATIC {
+                    let ret = if STATIC {
                         call!(ctx, $call_static, *this.as_raw(), method.into_raw(), args.as_ptr())
                     } else {
                         call!(ctx, $call, *this.as_raw(), method.into_raw(), args.as_ptr())
-                    }
+                    };
+
+                    ret.map($from_raw)
                 }
             }
         }
@@ -439,7 +1044,7 @@ macro_rules! impl_call_result {
 }
 
 impl_call_result!((), CallVoidMethodA, CallStaticVoidMethodA);
-impl_call_result!(bool, CallBooleanMethodA, CallStaticBooleanMethodA);
+impl_call_result!(bool, CallBooleanMethodA, CallStaticBooleanMethodA, bool_from_jboolean);
 impl_call_result!(i8, CallByteMethodA, CallStaticByteMethodA);
 impl_call_result!(u16, CallCharMethodA, CallStaticCharMethodA);
 impl_call_result!(i16, CallShortMethodA, CallStaticShortMethodA);
@@ -486,7 +1091,8 @@ impl<'ctx> CallResult<'ctx> for Local<'ctx> {
 pub trait CallArgs: __sealed::Sealed {
     type RawArgs: AsRef<[jvalue]>;
 
-    fn as_raw(&self) -> Self::RawArgs;
+    /// String arguments become locals in `strings`, which must be kept until the call returns.
+    fn as_raw<'ctx>(&self, ctx: &'ctx Context, strings: &mut Vec<Local<'ctx>>) -> Self::RawArgs;
 }
 
 impl<'a, const N: usize> __sealed::Sealed for [CallArg<'a>; N] {}
@@ -494,12 +1100,12 @@ impl<'a, const N: usize> __sealed::Sealed for [CallArg<'a>; N] {}
 impl<'a, const N: usize> CallArgs for [CallArg<'a>; N] {
     type RawArgs = [jvalue; N];
 
-    fn as_raw(&self) -> Self::RawArgs {
+    fn as_raw<'ctx>(&self, ctx: &'ctx Context, strings: &mut Vec<Local<'ctx>>) -> Self::RawArgs {
         unsafe {
             let mut ret = [MaybeUninit::<jvalue>::uninit(); N];
 
             for (index, value) in self.iter().enumerate() {
-                ret[index] = MaybeUninit::new(value.as_raw());
+                ret[index] = MaybeUninit::new(value.as_raw(ctx, strings));
             }
 
             ret.map(|m| m.assume_init())
@@ -512,22 +1118,25 @@ impl<'a> __sealed::Sealed for &[CallArg<'a>] {}
 impl<'a> CallArgs for &[CallArg<'a>] {
     type RawArgs = Vec<jvalue>;
 
-    fn as_raw(&self) -> Self::RawArgs {
-        self.iter().map(|v| v.as_raw()).collect()
+    fn as_raw<'ctx>(&self, ctx: &'ctx Context, strings: &mut Vec<Local<'ctx>>) -> Self::RawArgs {
+        self.iter().map(|v| v.as_raw(ctx, strings)).collect()
     }
 }
 
+// `method` and `args` must match the class or object they are used with, as `CallXMethodA` requires
+#[allow(clippy::missing_safety_doc)]
 impl Context {
     pub unsafe fn new_object<R: StrongRef, A: CallArgs>(
         &self,
         class: &R,
         method: Method<false>,
         args: A,
-    ) -> Result<Local, LocalObject<Throwable>> {
+    ) -> Result<Local<'_>, LocalObject<'_, Throwable>> {
         unsafe {
             self.ensure_local_capacity(4);
 
-            let args = args.as_raw();
+            let mut strings = Vec::new();
+            let args = args.as_raw(self, &mut strings);
             let args = args.as_ref();
 
             call!(self, NewObjectA, *class.as_raw(), method.into_raw(), args.as_ptr()).map(|r| Local::from_raw(r))
@@ -541,7 +1150,8 @@ impl Context {
         args: A,
     ) -> Result<R, LocalObject<'ctx, Throwable>> {
         unsafe {
-            let args = args.as_raw();
+            let mut strings = Vec::new();
+            let args = args.as_raw(self, &mut strings);
             let args = args.as_ref();
 
             R::call(self, this, method, args)
@@ -561,14 +1171,17 @@ pub trait SetArg: Sized + __sealed::Sealed {
 
 macro_rules! impl_get_return {
     ($typ:ty, $get:ident, $get_static:ident) => {
+        impl_get_return!($typ, $get, $get_static, core::convert::identity);
+    };
+    ($typ:ty, $get:ident, $get_static:ident, $from_raw:path) => {
         impl<'ctx> GetReturn<'ctx> for $typ {
             unsafe fn get<const STATIC: bool, T: StrongRef>(ctx: &'ctx Context, this: &T, field: Field<STATIC>) -> Self {
                 unsafe {
-                    if STATIC {
+                    $from_raw(if STATIC {
                         call_nothrow!(ctx, $get_static, *this.as_raw(), field.into_raw())
                     } else {
                         call_nothrow!(ctx, $get, *this.as_raw(), field.into_raw())
-                    }
+                    })
                 }
             }
         }
@@ -577,13 +1190,16 @@ macro_rules! impl_get_return {
 
 macro_rules! impl_set_arg {
     ($typ:ty, $set:ident, $set_static:ident) => {
+        impl_set_arg!($typ, $set, $set_static, core::convert::identity);
+    };
+    ($typ:ty, $set:ident, $set_static:ident, $into_raw:path) => {
         impl SetArg for $typ {
             unsafe fn set<const STATIC: bool, T: StrongRef>(self, ctx: &Context, this: &T, field: Field<STATIC>) {
                 unsafe {
                     if STATIC {
-                        call_nothrow!(ctx, $set_static, *this.as_raw(), field.into_raw(), self)
+                        call_nothrow!(ctx, $set_static, *this.as_raw(), field.into_raw(), $into_raw(self))
                     } else {
-                        call_nothrow!(ctx, $set, *this.as_raw(), field.into_raw(), self)
+                        call_nothrow!(ctx, $set, *this.as_raw(), field.into_raw(), $into_raw(self))
                     }
                 }
             }
@@ -591,7 +1207,7 @@ macro_rules! impl_set_arg {
     };
 }
 
-impl_get_return!(bool, GetBooleanField, GetStaticBooleanField);
+impl_get_return!(bool, GetBooleanField, GetStaticBooleanField, bool_from_jboolean);
 impl_get_return!(i8, GetByteField, GetStaticByteField);
 impl_get_return!(u16, GetCharField, GetStaticCharField);
 impl_get_return!(i16, GetShortField, GetStaticShortField);
@@ -600,7 +1216,7 @@ impl_get_return!(i64, GetLongField, GetStaticLongField);
 impl_get_return!(f32, GetFloatField, GetStaticFloatField);
 impl_get_return!(f64, GetDoubleField, GetStaticDoubleField);
 
-impl_set_arg!(bool, SetBooleanField, SetStaticBooleanField);
+impl_set_arg!(bool, SetBooleanField, SetStaticBooleanField, jboolean_from_bool);
 impl_set_arg!(i8, SetByteField, SetStaticByteField);
 impl_set_arg!(u16, SetCharField, SetStaticCharField);
 impl_set_arg!(i16, SetShortField, SetStaticShortField);
@@ -672,6 +1288,8 @@ impl<'a> SetArg for Option<&'a Local<'a>> {
     }
 }
 
+// `field` must be a field of `this` whose type is `V`, as `GetXField` and `SetXField` require
+#[allow(clippy::missing_safety_doc)]
 impl Context {
     pub unsafe fn get_field<'ctx, const STATIC: bool, T: StrongRef, V: GetReturn<'ctx>>(
         &'ctx self,
@@ -688,7 +1306,7 @@ impl Context {
 
 #[doc(hidden)]
 pub trait PrimitiveArrayElement: Sized + __sealed::Sealed {
-    unsafe fn new_array(ctx: &Context, length: i32) -> Result<Local, LocalObject<Throwable>>;
+    unsafe fn new_array(ctx: &Context, length: i32) -> Result<Local<'_>, LocalObject<'_, Throwable>>;
 
     unsafe fn get_region<'ctx, T: StrongRef>(
         ctx: &'ctx Context,
@@ -704,6 +1322,7 @@ pub trait PrimitiveArrayElement: Sized + __sealed::Sealed {
         buf: &[Self],
     ) -> Result<(), LocalObject<'ctx, Throwable>>;
 
+    #[allow(clippy::mut_from_ref)]
     unsafe fn get_elements<'r, T: StrongRef>(ctx: &'r Context, this: &'r T) -> &'r mut [Self];
 
     unsafe fn release_elements<T: StrongRef>(ctx: &Context, this: &T, buf: &mut [Self], commit: bool);
@@ -712,7 +1331,7 @@ pub trait PrimitiveArrayElement: Sized + __sealed::Sealed {
 macro_rules! impl_primitive_array_element {
     ($typ:ty, $new:ident, $get_region:ident, $set_region:ident, $get_elements:ident, $release_elements:ident) => {
         impl PrimitiveArrayElement for $typ {
-            unsafe fn new_array(ctx: &Context, length: i32) -> Result<Local, LocalObject<Throwable>> {
+            unsafe fn new_array(ctx: &Context, length: i32) -> Result<Local<'_>, LocalObject<'_, Throwable>> {
                 unsafe { call!(ctx, $new, length).map(|r| Local::from_raw(r)) }
             }
 
@@ -731,7 +1350,7 @@ macro_rules! impl_primitive_array_element {
                         *this.as_raw(),
                         offset,
                         buf.len().try_into().unwrap(),
-                        buf.as_mut_ptr()
+                        buf.as_mut_ptr().cast()
                     )
                 }
             }
@@ -751,7 +1370,7 @@ macro_rules! impl_primitive_array_element {
                         *this.as_raw(),
                         offset,
                         buf.len().try_into().unwrap(),
-                        buf.as_ptr()
+                        buf.as_ptr().cast()
                     )
                 }
             }
@@ -759,7 +1378,7 @@ macro_rules! impl_primitive_array_element {
             unsafe fn get_elements<'r, T: StrongRef>(ctx: &'r Context, this: &'r T) -> &'r mut [Self] {
                 unsafe {
                     let length = call_nothrow!(ctx, GetArrayLength, *this.as_raw());
-                    let ptr = call_nothrow!(ctx, $get_elements, *this.as_raw(), null_mut());
+                    let ptr: *mut Self = call_nothrow!(ctx, $get_elements, *this.as_raw(), null_mut()).cast();
 
                     core::slice::from_raw_parts_mut(ptr, length as _)
                 }
@@ -771,7 +1390,7 @@ macro_rules! impl_primitive_array_element {
                         ctx,
                         $release_elements,
                         *this.as_raw(),
-                        buf.as_mut_ptr(),
+                        buf.as_mut_ptr().cast(),
                         (if commit { JNI_COMMIT } else { JNI_ABORT }) as i32
                     )
                 }
@@ -780,14 +1399,72 @@ macro_rules! impl_primitive_array_element {
     };
 }
 
-impl_primitive_array_element!(
-    bool,
-    NewBooleanArray,
-    GetBooleanArrayRegion,
-    SetBooleanArrayRegion,
-    GetBooleanArrayElements,
-    ReleaseBooleanArrayElements
-);
+/// `jboolean` is a byte and the vm does not stop native code from storing values other than 0/1,
+/// which are not valid `bool`s, so the vm never writes into a `[bool]` directly. Reads go through a
+/// byte buffer where any nonzero value is true, as in Java bytecode, and the elements are such a
+/// copy, written back with `SetBooleanArrayRegion` only on commit.
+impl PrimitiveArrayElement for bool {
+    unsafe fn new_array(ctx: &Context, length: i32) -> Result<Local<'_>, LocalObject<'_, Throwable>> {
+        unsafe { call!(ctx, NewBooleanArray, length).map(|r| Local::from_raw(r)) }
+    }
+
+    unsafe fn get_region<'ctx, T: StrongRef>(
+        ctx: &'ctx Context,
+        this: &T,
+        offset: i32,
+        buf: &mut [Self],
+    ) -> Result<(), LocalObject<'ctx, Throwable>> {
+        let mut bytes = alloc::vec![JNI_FALSE; buf.len()];
+
+        unsafe { ctx.get_boolean_array_region_raw(this, offset, &mut bytes)? };
+
+        for (b, byte) in buf.iter_mut().zip(bytes) {
+            *b = bool_from_jboolean(byte);
+        }
+
+        Ok(())
+    }
+
+    unsafe fn set_region<'ctx, T: StrongRef>(
+        ctx: &'ctx Context,
+        this: &T,
+        offset: i32,
+        buf: &[Self],
+    ) -> Result<(), LocalObject<'ctx, Throwable>> {
+        unsafe {
+            call!(
+                ctx,
+                SetBooleanArrayRegion,
+                *this.as_raw(),
+                offset,
+                buf.len().try_into().unwrap(),
+                buf.as_ptr().cast()
+            )
+        }
+    }
+
+    unsafe fn get_elements<'r, T: StrongRef>(ctx: &'r Context, this: &'r T) -> &'r mut [Self] {
+        unsafe {
+            let length = call_nothrow!(ctx, GetArrayLength, *this.as_raw());
+            let mut buf = alloc::vec![false; length as usize];
+
+            Self::get_region(ctx, this, 0, &mut buf).expect("BROKEN: read of a whole boolean array failed.");
+
+            alloc::boxed::Box::leak(buf.into_boxed_slice())
+        }
+    }
+
+    unsafe fn release_elements<T: StrongRef>(ctx: &Context, this: &T, buf: &mut [Self], commit: bool) {
+        unsafe {
+            if commit {
+                Self::set_region(ctx, this, 0, buf).expect("BROKEN: write of a whole boolean array failed.");
+            }
+
+            drop(alloc::boxed::Box::from_raw(buf as *mut [Self]));
+        }
+    }
+}
+
 impl_primitive_array_element!(
     i8,
     NewByteArray,
@@ -845,12 +1522,17 @@ impl_primitive_array_element!(
     ReleaseDoubleArrayElements
 );
 
+// `object` must be an array, of `E` for the primitive functions and of objects otherwise
+#[allow(clippy::missing_safety_doc)]
 impl Context {
     pub unsafe fn get_array_length<R: StrongRef>(&self, object: &R) -> i32 {
         unsafe { call_nothrow!(self, GetArrayLength, *object.as_raw()) }
     }
 
-    pub unsafe fn new_primitive_array<E: PrimitiveArrayElement>(&self, size: i32) -> Result<Local, LocalObject<Throwable>> {
+    pub unsafe fn new_primitive_array<E: PrimitiveArrayElement>(
+        &self,
+        size: i32,
+    ) -> Result<Local<'_>, LocalObject<'_, Throwable>> {
         unsafe { E::new_array(self, size) }
     }
 
@@ -872,6 +1554,43 @@ impl Context {
         unsafe { E::set_region(self, this, offset, buf) }
     }
 
+    pub unsafe fn get_boolean_array_region_raw<'ctx, T: StrongRef>(
+        &'ctx self,
+        this: &T,
+        offset: i32,
+        buf: &mut [u8],
+    ) -> Result<(), LocalObject<'ctx, Throwable>> {
+        unsafe {
+            call!(
+                self,
+                GetBooleanArrayRegion,
+                *this.as_raw(),
+                offset,
+                buf.len().try_into().unwrap(),
+                buf.as_mut_ptr()
+            )
+        }
+    }
+
+    pub unsafe fn set_boolean_array_region_raw<'ctx, T: StrongRef>(
+        &'ctx self,
+        this: &T,
+        offset: i32,
+        buf: &[u8],
+    ) -> Result<(), LocalObject<'ctx, Throwable>> {
+        unsafe {
+            call!(
+                self,
+                SetBooleanArrayRegion,
+                *this.as_raw(),
+                offset,
+                buf.len().try_into().unwrap(),
+                buf.as_ptr()
+            )
+        }
+    }
+
+    #[allow(clippy::mut_from_ref)]
     pub unsafe fn get_primitive_array_elements<'r, E: PrimitiveArrayElement, T: StrongRef>(&'r self, this: &'r T) -> &'r mut [E] {
         unsafe { E::get_elements(self, this) }
     }
@@ -885,12 +1604,35 @@ impl Context {
         unsafe { E::release_elements(self, this, buf, commit) }
     }
 
+    /// Copies the first `buf.len()` bytes of a primitive array out of a `GetPrimitiveArrayCritical`
+    /// region, `false` if the vm could not provide one. No other JNI call and no allocation happen
+    /// while the region is held.
+    ///
+    /// `buf` must not be longer than the array in bytes.
+    pub unsafe fn copy_primitive_array_critical<T: StrongRef>(&self, this: &T, buf: &mut [u8]) -> bool {
+        let copied = self.run(|| unsafe {
+            let env = self.as_raw();
+            let ptr = self.env.as_ref().GetPrimitiveArrayCritical.unwrap()(env, *this.as_raw(), null_mut());
+            if ptr.is_null() {
+                return false;
+            }
+
+            core::ptr::copy_nonoverlapping(ptr as *const u8, buf.as_mut_ptr(), buf.len());
+
+            self.env.as_ref().ReleasePrimitiveArrayCritical.unwrap()(env, *this.as_raw(), ptr, JNI_ABORT as i32);
+
+            true
+        });
+
+        matches!(copied, Ok(true))
+    }
+
     pub unsafe fn new_object_array<R1: StrongRef, R2: Ref>(
         &self,
         length: i32,
         class: &R1,
         initial: Option<&R2>,
-    ) -> Result<Local, LocalObject<Throwable>> {
+    ) -> Result<Local<'_>, LocalObject<'_, Throwable>> {
         unsafe {
             self.ensure_local_capacity(4);
 
@@ -909,7 +1651,7 @@ impl Context {
         &self,
         object: &R,
         index: i32,
-    ) -> Result<Option<Local>, LocalObject<Throwable>> {
+    ) -> Result<Option<Local<'_>>, LocalObject<'_, Throwable>> {
         unsafe {
             self.ensure_local_capacity(4);
 
@@ -928,7 +1670,7 @@ impl Context {
         object: &R1,
         index: i32,
         value: Option<&R2>,
-    ) -> Result<(), LocalObject<Throwable>> {
+    ) -> Result<(), LocalObject<'_, Throwable>> {
         unsafe {
             call!(
                 self,
@@ -941,12 +1683,29 @@ impl Context {
     }
 }
 
+// `object` and `class` must be live, and natives must match the signatures they are registered with
+#[allow(clippy::missing_safety_doc)]
 impl Context {
+    pub unsafe fn monitor_enter(&self, object: jobject) -> Result<(), Error<'_>> {
+        match unsafe { call!(self, MonitorEnter, object)? } {
+            JNI_OK => Ok(()),
+            code => Err(Error::Failed(code)),
+        }
+    }
+
+    /// Throws `IllegalMonitorStateException` if the current thread does not own the monitor.
+    pub unsafe fn monitor_exit(&self, object: jobject) -> Result<(), Error<'_>> {
+        match unsafe { call!(self, MonitorExit, object)? } {
+            JNI_OK => Ok(()),
+            code => Err(Error::Failed(code)),
+        }
+    }
+
     pub unsafe fn register_natives<const COUNT: usize, R: StrongRef, N: AsRef<CStr>, S: AsRef<CStr>>(
         &self,
         class: &R,
         natives: [(N, S, *const ()); COUNT],
-    ) -> Result<(), LocalObject<Throwable>> {
+    ) -> Result<(), LocalObject<'_, Throwable>> {
         unsafe {
             let mut funcs = [MaybeUninit::<JNINativeMethod>::uninit(); COUNT];
             for (index, (name, signature, ptr)) in natives.iter().enumerate() {
@@ -976,3 +1735,118 @@ fn test_reference_equal_ptr() {
 
     assert_eq!(raw, r_raw);
 }
+
+#[cfg(test)]
+#[test]
+fn test_to_modified_utf8() {
+    assert_eq!(to_modified_utf8("thread-1").as_bytes(), b"thread-1");
+    assert_eq!(to_modified_utf8("a\0b").as_bytes(), b"a\xC0\x80b");
+    assert_eq!(to_modified_utf8("线程").as_bytes(), "线程".as_bytes());
+    assert_eq!(to_modified_utf8("😀").as_bytes(), b"\xED\xA0\xBD\xED\xB8\x80");
+}
+
+#[cfg(test)]
+#[test]
+fn test_lookup_failed_without_exception() {
+    use core::ffi::c_char;
+
+    use crate::{mock::MockEnv, sys::jclass};
+
+    unsafe extern "C" fn find_class(_: *mut JNIEnv, _: *const c_char) -> jclass {
+        null_mut()
+    }
+
+    unsafe extern "C" fn get_method_id(_: *mut JNIEnv, _: jclass, _: *const c_char, _: *const c_char) -> jmethodID {
+        null_mut()
+    }
+
+    unsafe extern "C" fn get_field_id(_: *mut JNIEnv, _: jclass, _: *const c_char, _: *const c_char) -> jfieldID {
+        null_mut()
+    }
+
+    let env = MockEnv::new(|functions| {
+        functions.FindClass = Some(find_class);
+        functions.GetMethodID = Some(get_method_id);
+        functions.GetStaticMethodID = Some(get_method_id);
+        functions.GetFieldID = Some(get_field_id);
+        functions.GetStaticFieldID = Some(get_field_id);
+    });
+    let ctx = env.ctx();
+
+    assert!(matches!(ctx.find_class(c"Missing"), Err(Error::LookupFailed)));
+
+    let class = crate::mock::dangling_local();
+    assert!(matches!(
+        ctx.find_method::<false, _>(&class, c"m", c"()V"),
+        Err(Error::LookupFailed)
+    ));
+    assert!(matches!(
+        ctx.find_method::<true, _>(&class, c"m", c"()V"),
+        Err(Error::LookupFailed)
+    ));
+    assert!(matches!(
+        ctx.find_field::<false, _>(&class, c"f", c"I"),
+        Err(Error::LookupFailed)
+    ));
+    assert!(matches!(
+        ctx.find_field::<true, _>(&class, c"f", c"I"),
+        Err(Error::LookupFailed)
+    ));
+
+    // dropping a local reference needs an attached vm
+    core::mem::forget(class);
+}
+
+#[cfg(test)]
+#[test]
+fn test_nonzero_jboolean_is_true() {
+    use crate::mock::MockEnv;
+
+    unsafe extern "C" fn call_boolean_method(_: *mut JNIEnv, _: jobject, _: jmethodID, _: *const jvalue) -> jboolean {
+        2
+    }
+
+    unsafe extern "C" fn get_boolean_field(_: *mut JNIEnv, _: jobject, _: jfieldID) -> jboolean {
+        2
+    }
+
+    let env = MockEnv::new(|functions| {
+        functions.CallBooleanMethodA = Some(call_boolean_method);
+        functions.CallStaticBooleanMethodA = Some(call_boolean_method);
+        functions.GetBooleanField = Some(get_boolean_field);
+        functions.GetStaticBooleanField = Some(get_boolean_field);
+    });
+    let ctx = env.ctx();
+
+    let this = crate::mock::dangling_local();
+    unsafe {
+        let ret: bool = ctx.call_method(&this, Method::<false>::from_raw(null_mut()), []).unwrap();
+        assert!(ret);
+        let ret: bool = ctx.call_method(&this, Method::<true>::from_raw(null_mut()), []).unwrap();
+        assert!(ret);
+        assert!(ctx.get_field::<false, _, bool>(&this, Field::from_raw(null_mut())));
+        assert!(ctx.get_field::<true, _, bool>(&this, Field::from_raw(null_mut())));
+    }
+
+    // dropping a local reference needs an attached vm
+    core::mem::forget(this);
+}
+
+#[cfg(test)]
+#[test]
+fn test_new_global_ref_out_of_memory() {
+    use crate::mock::MockEnv;
+
+    unsafe extern "C" fn new_global_ref(_: *mut JNIEnv, _: jobject) -> jobject {
+        null_mut()
+    }
+
+    let env = MockEnv::new(|functions| functions.NewGlobalRef = Some(new_global_ref));
+    let ctx = env.ctx();
+
+    let object = NonNull::<u8>::dangling().as_ptr().cast();
+    assert!(matches!(
+        unsafe { crate::reference::new_global(ctx, object) },
+        Err(Error::OutOfMemory)
+    ));
+}
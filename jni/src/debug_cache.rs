@@ -0,0 +1,15 @@
+use std::cell::Cell;
+
+thread_local! {
+    static LAST_LOOKUP_HIT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether the last method or field lookup of `call_method`, `get_field` and friends on the current
+/// thread was served by the member cache. False before the first lookup.
+pub fn last_lookup_was_hit() -> bool {
+    LAST_LOOKUP_HIT.get()
+}
+
+pub(crate) fn record_lookup(hit: bool) {
+    LAST_LOOKUP_HIT.set(hit);
+}
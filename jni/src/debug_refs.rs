@@ -0,0 +1,58 @@
+use std::{boxed::Box, cell::Cell, sync::RwLock};
+
+pub const WARN_THRESHOLD: usize = 256;
+
+type Reporter = Box<dyn Fn(&str) + Send + Sync>;
+
+static REPORTER: RwLock<Option<Reporter>> = RwLock::new(None);
+
+thread_local! {
+    static OUTSTANDING: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Installs the callback that receives the warnings of this module instead of stderr, e.g. to
+/// forward them to `android.util.Log` where stderr is discarded. It replaces the previous one and
+/// runs on the thread that holds the references.
+pub fn set_leak_reporter(reporter: impl Fn(&str) + Send + Sync + 'static) {
+    *REPORTER.write().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(reporter));
+}
+
+pub fn outstanding_local_refs() -> usize {
+    OUTSTANDING.get()
+}
+
+fn report(message: &str) {
+    match REPORTER.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(reporter) => reporter(message),
+        None => std::eprintln!("typed-jni: {}", message),
+    }
+}
+
+pub(crate) fn track_acquire() {
+    let count = OUTSTANDING.get() + 1;
+
+    OUTSTANDING.set(count);
+
+    // a leak in a loop keeps growing, so warn again at every multiple of the threshold
+    if count.is_multiple_of(WARN_THRESHOLD) {
+        report(&std::format!(
+            "{} local references outstanding on current thread, check for local reference leaks",
+            count
+        ));
+    }
+}
+
+pub(crate) fn track_release() {
+    OUTSTANDING.set(OUTSTANDING.get().saturating_sub(1));
+}
+
+/// Reports the references acquired since `before` that are still outstanding when the vm deletes
+/// every local reference of the frame, before `when`.
+pub(crate) fn check_leaked(before: usize, when: &str) {
+    let count = OUTSTANDING.get();
+    if count > before {
+        report(&std::format!("{} local references leaked before {}", count - before, when));
+
+        OUTSTANDING.set(before);
+    }
+}
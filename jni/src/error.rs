@@ -0,0 +1,108 @@
+#[cfg(feature = "debug-diagnostics")]
+use alloc::string::String;
+use core::fmt::{Debug, Display, Formatter};
+
+use crate::{builtin::Throwable, LocalObject};
+
+/// New variants may be added, some only with a feature enabled, so matches need a wildcard arm.
+#[non_exhaustive]
+pub enum Error<'ctx> {
+    Throwable(LocalObject<'ctx, Throwable>),
+    /// The JVM returned null without throwing.
+    LookupFailed,
+    /// A class, member or signature name contains an interior NUL and cannot be passed to the JVM.
+    InvalidName,
+    /// The JVM could not allocate a new global reference.
+    OutOfMemory,
+    /// No vm was attached with [`attach_vm`](crate::attach_vm), or the current thread is not
+    /// attached to it.
+    Detached,
+    /// A Rust slice is longer than the `i32::MAX` elements of a Java array.
+    TooLong,
+    /// A JNI function returned this error code without throwing.
+    Failed(i32),
+    /// A `NoSuchMethodError` or `NoSuchFieldError` from a lookup of the typed API, which points at
+    /// a binding that does not match the class rather than a class that failed to load. `class` is
+    /// the binary name, `signature` the JNI descriptor that was looked up.
+    #[cfg(feature = "debug-diagnostics")]
+    MemberNotFound {
+        class: String,
+        name: String,
+        signature: String,
+        throwable: LocalObject<'ctx, Throwable>,
+    },
+}
+
+impl<'ctx> Error<'ctx> {
+    pub fn throwable(&self) -> Option<&LocalObject<'ctx, Throwable>> {
+        match self {
+            Error::Throwable(t) => Some(t),
+            #[cfg(feature = "debug-diagnostics")]
+            Error::MemberNotFound { throwable, .. } => Some(throwable),
+            _ => None,
+        }
+    }
+
+    pub fn into_throwable(self) -> Option<LocalObject<'ctx, Throwable>> {
+        match self {
+            Error::Throwable(t) => Some(t),
+            #[cfg(feature = "debug-diagnostics")]
+            Error::MemberNotFound { throwable, .. } => Some(throwable),
+            _ => None,
+        }
+    }
+}
+
+impl<'ctx> From<LocalObject<'ctx, Throwable>> for Error<'ctx> {
+    fn from(value: LocalObject<'ctx, Throwable>) -> Self {
+        Error::Throwable(value)
+    }
+}
+
+impl Debug for Error<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::Throwable(t) => f.debug_tuple("Throwable").field(&format_args!("{t}")).finish(),
+            Error::LookupFailed => f.write_str("LookupFailed"),
+            Error::InvalidName => f.write_str("InvalidName"),
+            Error::OutOfMemory => f.write_str("OutOfMemory"),
+            Error::Detached => f.write_str("Detached"),
+            Error::TooLong => f.write_str("TooLong"),
+            Error::Failed(code) => f.debug_tuple("Failed").field(code).finish(),
+            #[cfg(feature = "debug-diagnostics")]
+            Error::MemberNotFound {
+                class,
+                name,
+                signature,
+                throwable,
+            } => f
+                .debug_struct("MemberNotFound")
+                .field("class", class)
+                .field("name", name)
+                .field("signature", signature)
+                .field("throwable", &format_args!("{throwable}"))
+                .finish(),
+        }
+    }
+}
+
+impl Display for Error<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::Throwable(t) => Display::fmt(t, f),
+            Error::LookupFailed => f.write_str("lookup failed without a pending exception"),
+            Error::InvalidName => f.write_str("name contains an interior NUL"),
+            Error::OutOfMemory => f.write_str("out of memory creating a global reference"),
+            Error::Detached => f.write_str("current thread is not attached to a vm"),
+            Error::TooLong => f.write_str("too many elements for a java array"),
+            Error::Failed(code) => f.write_fmt(format_args!("jni call failed with code {code}")),
+            #[cfg(feature = "debug-diagnostics")]
+            Error::MemberNotFound {
+                class, name, signature, ..
+            } => f.write_fmt(format_args!("{class} has no member {name} {signature}")),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error<'_> {}
@@ -0,0 +1,34 @@
+//! The common JDK exceptions, with their superclasses declared through [`Extends`].
+//!
+//! ```no_run
+//! # use typed_jni::{exceptions::JIllegalStateException, AsRaw, Context};
+//! # fn closed(ctx: &Context) {
+//! let class = JIllegalStateException::class(ctx).unwrap();
+//!
+//! unsafe { ctx.throw_new(class.as_raw(), "already closed") };
+//! # }
+//! ```
+
+use crate::{define_java_class, Extends, Throwable};
+
+define_java_class!(JException, "java.lang.Exception");
+define_java_class!(JRuntimeException, "java.lang.RuntimeException");
+define_java_class!(JIOException, "java.io.IOException");
+define_java_class!(JIllegalArgumentException, "java.lang.IllegalArgumentException");
+define_java_class!(JIllegalStateException, "java.lang.IllegalStateException");
+define_java_class!(JNullPointerException, "java.lang.NullPointerException");
+define_java_class!(JUnsupportedOperationException, "java.lang.UnsupportedOperationException");
+
+macro_rules! extends {
+    ($typ:ty: $($sup:ty),+) => {
+        $(unsafe impl Extends<$sup> for $typ {})+
+    };
+}
+
+extends!(JException: Throwable);
+extends!(JRuntimeException: JException, Throwable);
+extends!(JIOException: JException, Throwable);
+extends!(JIllegalArgumentException: JRuntimeException, JException, Throwable);
+extends!(JIllegalStateException: JRuntimeException, JException, Throwable);
+extends!(JNullPointerException: JRuntimeException, JException, Throwable);
+extends!(JUnsupportedOperationException: JRuntimeException, JException, Throwable);
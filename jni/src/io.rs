@@ -0,0 +1,170 @@
+use core::ffi::CStr;
+use std::io::{self, Read, Write};
+
+use crate::{
+    typed::{__CachedClass, __CachedMethod, Signature, Type},
+    AsRaw, Context, Error, JByteArray, LocalObject, NoArgs, Object, ObjectType, StrongRef,
+};
+
+/// Bytes moved per JNI call, and the size of the `byte[]` an adapter reuses.
+const CHUNK_SIZE: usize = 8192;
+
+/// `java.io.InputStream`, read from Rust with [`reader`](Object::reader).
+pub struct JInputStream;
+
+impl Type for JInputStream {
+    const SIGNATURE: Signature = Signature::Object("java/io/InputStream");
+}
+
+impl ObjectType for JInputStream {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/io/InputStream");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/io/InputStream;");
+}
+
+/// `java.io.OutputStream`, written from Rust with [`writer`](Object::writer).
+pub struct JOutputStream;
+
+impl Type for JOutputStream {
+    const SIGNATURE: Signature = Signature::Object("java/io/OutputStream");
+}
+
+impl ObjectType for JOutputStream {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/io/OutputStream");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/io/OutputStream;");
+}
+
+static C_INPUT_STREAM: __CachedClass = __CachedClass::new();
+static C_OUTPUT_STREAM: __CachedClass = __CachedClass::new();
+static M_READ: __CachedMethod<false> = __CachedMethod::new();
+static M_WRITE: __CachedMethod<false> = __CachedMethod::new();
+static M_FLUSH: __CachedMethod<false> = __CachedMethod::new();
+
+/// A thrown exception, usually an `IOException`, becomes an [`io::Error`] of kind
+/// [`Other`](io::ErrorKind::Other) whose inner error is the [`JavaError`](crate::JavaError).
+fn to_io_error(ctx: &Context, err: Error) -> io::Error {
+    match err {
+        Error::Throwable(throwable) => match throwable.into_error(ctx) {
+            Ok(err) => io::Error::other(err),
+            Err(err) => io::Error::other(err.to_string()),
+        },
+        err => io::Error::other(err.to_string()),
+    }
+}
+
+fn alloc_chunk(ctx: &Context) -> Result<LocalObject<'_, JByteArray>, Error<'_>> {
+    Ok(LocalObject::<JByteArray>::new_primitive(ctx, CHUNK_SIZE as i32)?)
+}
+
+impl<R: StrongRef> Object<JInputStream, R> {
+    /// Adapts to [`Read`], every `read` is at most one `InputStream.read(byte[], int, int)` of up
+    /// to 8 KiB through a `byte[]` allocated here. Wrap it in a [`BufReader`](io::BufReader) for
+    /// many small reads.
+    pub fn reader<'a, 'ctx>(&'a self, ctx: &'ctx Context) -> Result<StreamReader<'a, 'ctx, R>, Error<'ctx>> {
+        Ok(StreamReader {
+            ctx,
+            stream: self,
+            chunk: alloc_chunk(ctx)?,
+        })
+    }
+}
+
+pub struct StreamReader<'a, 'ctx, R: StrongRef> {
+    ctx: &'ctx Context,
+    stream: &'a Object<JInputStream, R>,
+    chunk: LocalObject<'ctx, JByteArray>,
+}
+
+impl<'ctx, R: StrongRef> StreamReader<'_, 'ctx, R> {
+    fn read_chunk(&self, buf: &mut [u8]) -> Result<usize, Error<'ctx>> {
+        let ctx = self.ctx;
+        let class = C_INPUT_STREAM.get::<JInputStream>(ctx)?;
+        let method = M_READ.get::<_, (&LocalObject<JByteArray>, i32, i32), i32>(ctx, class.as_raw(), "read")?;
+
+        let len = buf.len().min(CHUNK_SIZE);
+        let n: i32 = unsafe {
+            ctx.call_method(
+                self.stream.as_raw(),
+                method,
+                [self.chunk.as_raw().into(), 0.into(), (len as i32).into()],
+            )?
+        };
+
+        // -1 is the end of the stream
+        let n = usize::try_from(n).unwrap_or(0);
+        self.chunk.get_bytes_region(ctx, 0, &mut buf[..n])?;
+
+        Ok(n)
+    }
+}
+
+impl<R: StrongRef> Read for StreamReader<'_, '_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        self.read_chunk(buf).map_err(|err| to_io_error(self.ctx, err))
+    }
+}
+
+impl<R: StrongRef> Object<JOutputStream, R> {
+    /// Adapts to [`Write`], every `write` is at most one `OutputStream.write(byte[], int, int)` of
+    /// up to 8 KiB through a `byte[]` allocated here. `flush` calls `OutputStream.flush()`, nothing
+    /// is buffered on the Rust side.
+    pub fn writer<'a, 'ctx>(&'a self, ctx: &'ctx Context) -> Result<StreamWriter<'a, 'ctx, R>, Error<'ctx>> {
+        Ok(StreamWriter {
+            ctx,
+            stream: self,
+            chunk: alloc_chunk(ctx)?,
+        })
+    }
+}
+
+pub struct StreamWriter<'a, 'ctx, R: StrongRef> {
+    ctx: &'ctx Context,
+    stream: &'a Object<JOutputStream, R>,
+    chunk: LocalObject<'ctx, JByteArray>,
+}
+
+impl<'ctx, R: StrongRef> StreamWriter<'_, 'ctx, R> {
+    fn write_chunk(&self, buf: &[u8]) -> Result<usize, Error<'ctx>> {
+        let ctx = self.ctx;
+        let class = C_OUTPUT_STREAM.get::<JOutputStream>(ctx)?;
+        let method = M_WRITE.get::<_, (&LocalObject<JByteArray>, i32, i32), ()>(ctx, class.as_raw(), "write")?;
+
+        let buf = &buf[..buf.len().min(CHUNK_SIZE)];
+        self.chunk.set_bytes_region(ctx, 0, buf)?;
+
+        unsafe {
+            ctx.call_method::<false, _, _, ()>(
+                self.stream.as_raw(),
+                method,
+                [self.chunk.as_raw().into(), 0.into(), (buf.len() as i32).into()],
+            )?
+        };
+
+        Ok(buf.len())
+    }
+
+    fn flush_stream(&self) -> Result<(), Error<'ctx>> {
+        let ctx = self.ctx;
+        let class = C_OUTPUT_STREAM.get::<JOutputStream>(ctx)?;
+        let method = M_FLUSH.get::<_, NoArgs, ()>(ctx, class.as_raw(), "flush")?;
+
+        unsafe { Ok(ctx.call_method(self.stream.as_raw(), method, [])?) }
+    }
+}
+
+impl<R: StrongRef> Write for StreamWriter<'_, '_, R> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        self.write_chunk(buf).map_err(|err| to_io_error(self.ctx, err))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flush_stream().map_err(|err| to_io_error(self.ctx, err))
+    }
+}
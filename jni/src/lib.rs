@@ -5,21 +5,64 @@ extern crate core;
 
 mod args;
 mod builtin;
+#[cfg(feature = "cache")]
+pub mod cache;
+#[cfg(feature = "std")]
+mod class_cache;
+#[cfg(feature = "collections")]
+mod collections;
 mod context;
+#[cfg(feature = "debug-cache")]
+pub mod debug_cache;
+#[cfg(feature = "debug-refs")]
+pub mod debug_refs;
+mod error;
+#[cfg(feature = "exceptions")]
+pub mod exceptions;
+#[cfg(feature = "io")]
+mod io;
+#[cfg(test)]
+mod mock;
+mod monitor;
+mod native_ptr;
+#[cfg(feature = "std")]
+mod panic;
+pub mod prelude;
+#[cfg(feature = "proxy")]
+mod proxy;
 mod raw;
 mod reference;
 mod resolver;
 pub mod sys;
+#[cfg(feature = "time")]
+mod time;
+mod trampoline;
 mod typed;
 mod vm;
 
 pub use args::*;
 pub use builtin::*;
+#[cfg(feature = "std")]
+pub use class_cache::*;
+#[cfg(feature = "collections")]
+pub use collections::*;
 pub use context::*;
+pub use error::*;
+#[cfg(feature = "io")]
+pub use io::*;
+pub use monitor::*;
+pub use native_ptr::*;
+#[cfg(feature = "std")]
+pub use panic::{catch_panic, set_panic_reporter};
 pub use raw::*;
 pub use reference::*;
+#[cfg(feature = "time")]
+pub use time::*;
+pub use trampoline::*;
 pub use typed::*;
-pub use vm::attach_vm;
+#[cfg(feature = "invocation")]
+pub use vm::create_java_vm;
+pub use vm::{attach_vm, Vm, VmError};
 
 #[doc(hidden)]
 pub const unsafe fn __class_name_to_internal_name_bytes<const N: usize>(s: &'static str) -> [u8; N] {
@@ -27,19 +70,46 @@ pub const unsafe fn __class_name_to_internal_name_bytes<const N: usize>(s: &'sta
     let mut ret = [0u8; N];
 
     let mut index = 0;
-    while index < N {
-        if data[index] == b'.' {
-            ret[index] = b'/';
-        } else {
-            ret[index] = data[index];
-        }
+    while index < data.len() {
+        ret[index] = match data[index] {
+            b'.' => b'/',
+            0 => panic!("class name must not contain NUL"),
+            b => b,
+        };
+
+        index += 1;
+    }
+
+    ret
+}
+
+#[doc(hidden)]
+pub const unsafe fn __class_name_to_descriptor_bytes<const N: usize>(s: &'static str) -> [u8; N] {
+    let name: [u8; N] = unsafe { __class_name_to_internal_name_bytes(s) };
+    let mut ret = [0u8; N];
+
+    ret[0] = b'L';
+
+    let mut index = 0;
+    while index < s.len() {
+        ret[index + 1] = name[index];
 
         index += 1;
     }
 
+    ret[s.len() + 1] = b';';
+
     ret
 }
 
+#[doc(hidden)]
+pub const fn __bytes_to_cstr(bytes: &'static [u8]) -> &'static core::ffi::CStr {
+    match core::ffi::CStr::from_bytes_with_nul(bytes) {
+        Ok(s) => s,
+        Err(_) => panic!("invalid class name"),
+    }
+}
+
 #[doc(hidden)]
 pub const unsafe fn __bytes_to_str(bytes: &'static [u8]) -> &'static str {
     unsafe { core::str::from_utf8_unchecked(bytes) }
@@ -60,6 +130,240 @@ macro_rules! define_java_class {
             });
         }
 
-        impl $crate::ObjectType for $name {}
+        impl $crate::ObjectType for $name {
+            const CLASS_NAME: ::core::option::Option<&'static ::core::ffi::CStr> = ::core::option::Option::Some({
+                const BYTES: [u8; ($class).len() + 1] = unsafe { $crate::__class_name_to_internal_name_bytes($class) };
+
+                $crate::__bytes_to_cstr(&BYTES)
+            });
+
+            const DESCRIPTOR: ::core::option::Option<&'static ::core::ffi::CStr> = ::core::option::Option::Some({
+                const BYTES: [u8; ($class).len() + 3] = unsafe { $crate::__class_name_to_descriptor_bytes($class) };
+
+                $crate::__bytes_to_cstr(&BYTES)
+            });
+        }
+
+        #[allow(dead_code)]
+        impl $name {
+            fn __cached_class() -> &'static $crate::__CachedClass {
+                static CLASS: $crate::__CachedClass = $crate::__CachedClass::new();
+
+                &CLASS
+            }
+
+            /// Found once and kept as a global reference, later calls skip `FindClass`.
+            pub fn class<'ctx>(
+                ctx: &'ctx $crate::Context,
+            ) -> ::core::result::Result<$crate::LocalClass<'ctx, $name>, $crate::Error<'ctx>> {
+                Self::__cached_class().get(ctx)
+            }
+
+            /// Drops the cached class, e.g. after its class loader has been replaced.
+            ///
+            /// # Safety
+            ///
+            /// Must not race with [`class`](Self::class), and must not be used on a
+            /// [`java_bindings!`] class whose member ids would be left pointing into the old class.
+            pub unsafe fn invalidate_class() {
+                unsafe { Self::__cached_class().invalidate() }
+            }
+
+            pub fn call_static<'ctx, 'a, V, A>(
+                ctx: &'ctx $crate::Context,
+                name: &'static str,
+                args: A,
+            ) -> ::core::result::Result<V, $crate::Error<'ctx>>
+            where
+                V: $crate::Type + $crate::FromRaw,
+                V::Raw: $crate::CallResult<'ctx>,
+                A: $crate::Args<'a>,
+                A::Array<$crate::Signature>: ::core::convert::AsRef<[$crate::Signature]>,
+                A::Array<$crate::CallArg<'a>>: $crate::CallArgs,
+            {
+                Self::class(ctx)?.call_method(ctx, name, args)
+            }
+
+            /// Reads a static field, e.g. a library constant, of the cached class.
+            pub fn get_static<'ctx, V>(
+                ctx: &'ctx $crate::Context,
+                name: &'static str,
+            ) -> ::core::result::Result<V, $crate::Error<'ctx>>
+            where
+                V: $crate::Type + $crate::FromRaw,
+                V::Raw: $crate::GetReturn<'ctx>,
+            {
+                Self::class(ctx)?.get_field(ctx, name)
+            }
+
+            pub fn set_static<'ctx, V>(
+                ctx: &'ctx $crate::Context,
+                name: &'static str,
+                value: V,
+            ) -> ::core::result::Result<(), $crate::Error<'ctx>>
+            where
+                V: $crate::Type + $crate::IntoRaw,
+                V::Raw: $crate::SetArg,
+            {
+                Self::class(ctx)?.set_field(ctx, name, value)
+            }
+        }
+    };
+}
+
+/// Defines classes like [`define_java_class!`] together with typed accessors whose class, method
+/// and field ids are resolved once and shared by all threads.
+///
+/// ```no_run
+/// use typed_jni::{java_bindings, Context, JString, LocalObject};
+///
+/// java_bindings! {
+///     class JavaExample = "com.example.Example" {
+///         fn greet(name: &str) -> LocalObject<'ctx, JString>;
+///         static fn factory(count: i32) -> LocalObject<'ctx, JavaExample>;
+///         field count: i32 => set_count;
+///         static field INSTANCES: i32;
+///     }
+/// }
+///
+/// fn run(ctx: &Context) {
+///     let example = JavaExample::factory(ctx, 3).unwrap();
+///     JavaExample::set_count(ctx, &example, JavaExample::count(ctx, &example).unwrap() + 1).unwrap();
+///     println!("{}", JavaExample::greet(ctx, &example, "rust").unwrap());
+/// }
+/// ```
+///
+/// Instance members take the receiver after `ctx`. Types may refer to the `'ctx` lifetime of the
+/// context, a missing return type is `void` and `=> setter` adds a setter to a field.
+#[macro_export]
+macro_rules! java_bindings {
+    ($(class $name:ident = $class:literal { $($members:tt)* })*) => {
+        $(
+            $crate::define_java_class!($name, $class);
+
+            $crate::java_bindings!(@members $name { $($members)* });
+        )*
+    };
+    (@members $name:ident {}) => {};
+    (@members $name:ident { fn $method:ident($($arg:ident: $typ:ty),* $(,)?) $(-> $ret:ty)?; $($rest:tt)* }) => {
+        #[allow(dead_code, non_snake_case)]
+        impl $name {
+            pub fn $method<'ctx, R: $crate::StrongRef>(
+                ctx: &'ctx $crate::Context,
+                this: &$crate::Object<$name, R>,
+                $($arg: $typ),*
+            ) -> ::core::result::Result<$crate::java_bindings!(@ret $($ret)?), $crate::Error<'ctx>> {
+                static METHOD: $crate::__CachedMethod<false> = $crate::__CachedMethod::new();
+
+                let method = METHOD.get::<_, ($($typ),*), $crate::java_bindings!(@ret $($ret)?)>(ctx, $crate::AsRaw::as_raw(&Self::class(ctx)?), ::core::stringify!($method))?;
+
+                unsafe {
+                    let ret = ctx.call_method($crate::AsRaw::as_raw(this), method, $crate::Args::into_raw(($($arg),*)))?;
+
+                    ::core::result::Result::Ok($crate::FromRaw::from_raw(ret))
+                }
+            }
+        }
+
+        $crate::java_bindings!(@members $name { $($rest)* });
+    };
+    (@members $name:ident { static fn $method:ident($($arg:ident: $typ:ty),* $(,)?) $(-> $ret:ty)?; $($rest:tt)* }) => {
+        #[allow(dead_code, non_snake_case)]
+        impl $name {
+            pub fn $method<'ctx>(
+                ctx: &'ctx $crate::Context,
+                $($arg: $typ),*
+            ) -> ::core::result::Result<$crate::java_bindings!(@ret $($ret)?), $crate::Error<'ctx>> {
+                static METHOD: $crate::__CachedMethod<true> = $crate::__CachedMethod::new();
+
+                let class = Self::class(ctx)?;
+                let method = METHOD.get::<_, ($($typ),*), $crate::java_bindings!(@ret $($ret)?)>(ctx, $crate::AsRaw::as_raw(&class), ::core::stringify!($method))?;
+
+                unsafe {
+                    let ret = ctx.call_method($crate::AsRaw::as_raw(&class), method, $crate::Args::into_raw(($($arg),*)))?;
+
+                    ::core::result::Result::Ok($crate::FromRaw::from_raw(ret))
+                }
+            }
+        }
+
+        $crate::java_bindings!(@members $name { $($rest)* });
     };
+    (@members $name:ident { field $field:ident: $typ:ty $(=> $setter:ident)?; $($rest:tt)* }) => {
+        #[allow(dead_code, non_snake_case)]
+        impl $name {
+            pub fn $field<'ctx, R: $crate::StrongRef>(
+                ctx: &'ctx $crate::Context,
+                this: &$crate::Object<$name, R>,
+            ) -> ::core::result::Result<$typ, $crate::Error<'ctx>> {
+                static FIELD: $crate::__CachedField<false> = $crate::__CachedField::new();
+
+                let field = FIELD.get::<_, $typ>(ctx, $crate::AsRaw::as_raw(&Self::class(ctx)?), ::core::stringify!($field))?;
+
+                unsafe { ::core::result::Result::Ok($crate::FromRaw::from_raw(ctx.get_field($crate::AsRaw::as_raw(this), field))) }
+            }
+
+            $(
+                pub fn $setter<'ctx, R: $crate::StrongRef>(
+                    ctx: &'ctx $crate::Context,
+                    this: &$crate::Object<$name, R>,
+                    value: $typ,
+                ) -> ::core::result::Result<(), $crate::Error<'ctx>> {
+                    static FIELD: $crate::__CachedField<false> = $crate::__CachedField::new();
+
+                    let field = FIELD.get::<_, $typ>(ctx, $crate::AsRaw::as_raw(&Self::class(ctx)?), ::core::stringify!($field))?;
+
+                    unsafe { ctx.set_field($crate::AsRaw::as_raw(this), field, $crate::IntoRaw::into_raw(value)) };
+
+                    ::core::result::Result::Ok(())
+                }
+            )?
+        }
+
+        $crate::java_bindings!(@members $name { $($rest)* });
+    };
+    (@members $name:ident { static field $field:ident: $typ:ty $(=> $setter:ident)?; $($rest:tt)* }) => {
+        #[allow(dead_code, non_snake_case)]
+        impl $name {
+            pub fn $field<'ctx>(ctx: &'ctx $crate::Context) -> ::core::result::Result<$typ, $crate::Error<'ctx>> {
+                static FIELD: $crate::__CachedField<true> = $crate::__CachedField::new();
+
+                let class = Self::class(ctx)?;
+                let field = FIELD.get::<_, $typ>(ctx, $crate::AsRaw::as_raw(&class), ::core::stringify!($field))?;
+
+                unsafe { ::core::result::Result::Ok($crate::FromRaw::from_raw(ctx.get_field($crate::AsRaw::as_raw(&class), field))) }
+            }
+
+            $(
+                pub fn $setter<'ctx>(ctx: &'ctx $crate::Context, value: $typ) -> ::core::result::Result<(), $crate::Error<'ctx>> {
+                    static FIELD: $crate::__CachedField<true> = $crate::__CachedField::new();
+
+                    let class = Self::class(ctx)?;
+                    let field = FIELD.get::<_, $typ>(ctx, $crate::AsRaw::as_raw(&class), ::core::stringify!($field))?;
+
+                    unsafe { ctx.set_field($crate::AsRaw::as_raw(&class), field, $crate::IntoRaw::into_raw(value)) };
+
+                    ::core::result::Result::Ok(())
+                }
+            )?
+        }
+
+        $crate::java_bindings!(@members $name { $($rest)* });
+    };
+    (@ret) => { () };
+    (@ret $ret:ty) => { $ret };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ObjectType, Type};
+
+    define_java_class!(JavaInner, "com.example.Outer$Inner");
+
+    #[test]
+    fn test_compile_time_class_names() {
+        assert_eq!(JavaInner::CLASS_NAME, Some(c"com/example/Outer$Inner"));
+        assert_eq!(JavaInner::DESCRIPTOR, Some(c"Lcom/example/Outer$Inner;"));
+        assert_eq!(JavaInner::SIGNATURE.to_string(), "Lcom/example/Outer$Inner;");
+    }
 }
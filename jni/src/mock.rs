@@ -0,0 +1,48 @@
+use alloc::boxed::Box;
+use core::ptr::{null_mut, NonNull};
+
+use crate::{
+    sys::{jboolean, jthrowable, JNIEnv, JNINativeInterface_, JNI_FALSE},
+    Context, FromRaw, Local,
+};
+
+unsafe extern "C" fn exception_occurred(_: *mut JNIEnv) -> jthrowable {
+    null_mut()
+}
+
+unsafe extern "C" fn exception_check(_: *mut JNIEnv) -> jboolean {
+    JNI_FALSE
+}
+
+/// A `JNIEnv` without a vm for unit tests. Only the exception functions are implemented, reporting
+/// no exception, every other function is `None` unless the test sets it.
+pub struct MockEnv {
+    _functions: Box<JNINativeInterface_>,
+    env: Box<*const JNINativeInterface_>,
+}
+
+impl MockEnv {
+    pub fn new(setup: impl FnOnce(&mut JNINativeInterface_)) -> Self {
+        let mut functions: Box<JNINativeInterface_> = Box::new(unsafe { core::mem::zeroed() });
+        functions.ExceptionOccurred = Some(exception_occurred);
+        functions.ExceptionCheck = Some(exception_check);
+
+        setup(&mut functions);
+
+        let env = Box::new(&*functions as *const JNINativeInterface_);
+
+        Self {
+            _functions: functions,
+            env,
+        }
+    }
+
+    pub fn ctx(&self) -> &Context {
+        unsafe { Context::from_raw(&*self.env as *const _ as *mut JNIEnv) }
+    }
+}
+
+/// A non-null handle for mock calls, which must be forgotten since deleting it needs a vm.
+pub fn dangling_local<'ctx>() -> Local<'ctx> {
+    unsafe { Local::from_raw(NonNull::<u8>::dangling().as_ptr().cast()) }
+}
@@ -0,0 +1,50 @@
+use core::marker::PhantomData;
+
+use crate::{sys::jobject, Context, Error};
+
+/// Holds a Java monitor entered with [`Object::lock`](crate::Object::lock) or
+/// [`Class::lock`](crate::Class::lock), exiting it on drop like the end of a `synchronized` block.
+///
+/// Monitors are reentrant, so locking an object the thread already holds nests another guard and
+/// every guard exits once. The JVM counts the entries of each object rather than keeping a stack,
+/// so guards may be dropped in any order, also across objects, without unbalancing a monitor:
+///
+/// ```no_run
+/// # use typed_jni::{Context, JString, LocalObject};
+/// # fn sync(ctx: &Context, object: &LocalObject<JString>) {
+/// let outer = object.lock(ctx).unwrap();
+/// let inner = object.lock(ctx).unwrap();
+///
+/// drop(outer);
+/// drop(inner);
+/// # }
+/// ```
+///
+/// A guard that is forgotten leaves the monitor entered until the thread exits it otherwise, or
+/// detaches from the vm.
+pub struct MonitorGuard<'a> {
+    ctx: &'a Context,
+    object: jobject,
+    _object: PhantomData<&'a ()>,
+}
+
+impl<'a> MonitorGuard<'a> {
+    /// `object` must stay alive for `'a`.
+    pub(crate) unsafe fn enter(ctx: &'a Context, object: jobject) -> Result<Self, Error<'a>> {
+        unsafe { ctx.monitor_enter(object)? };
+
+        Ok(Self {
+            ctx,
+            object,
+            _object: PhantomData,
+        })
+    }
+}
+
+impl Drop for MonitorGuard<'_> {
+    fn drop(&mut self) {
+        // the guard entered the monitor on this thread, so the exit cannot throw
+        // IllegalMonitorStateException and there is nothing to report from a drop
+        let _ = unsafe { self.ctx.monitor_exit(self.object) };
+    }
+}
@@ -0,0 +1,90 @@
+use alloc::boxed::Box;
+use core::marker::PhantomData;
+
+/// Stores a `Box<T>` in a Java `long` field, the usual `nativeHandle` idiom.
+///
+/// The Java object owns the box from [`into_jlong`](Self::into_jlong) until its `close()` calls
+/// into native code, which frees it with [`drop_from_jlong`](Self::drop_from_jlong) and resets the
+/// field to `0`. `0` is never a valid handle, so a closed object can be detected.
+///
+/// ```no_run
+/// # use typed_jni::{Context, NativePtr, TrampolineObject};
+/// # typed_jni::define_java_class!(JavaCounter, "com.example.Counter");
+/// extern "C" fn close<'ctx>(ctx: &'ctx Context, this: TrampolineObject<'ctx, JavaCounter>) {
+///     let handle: i64 = this.get_field(ctx, "nativeHandle").unwrap();
+///     this.set_field(ctx, "nativeHandle", 0i64).unwrap();
+///
+///     unsafe { NativePtr::<u64>::drop_from_jlong(handle) };
+/// }
+/// ```
+pub struct NativePtr<T>(PhantomData<T>);
+
+impl<T> NativePtr<T> {
+    pub fn into_jlong(value: Box<T>) -> i64 {
+        Box::into_raw(value) as usize as i64
+    }
+
+    /// `0` maps to a null pointer.
+    pub fn from_jlong(handle: i64) -> *mut T {
+        handle as usize as *mut T
+    }
+
+    /// # Safety
+    ///
+    /// `handle` must come from [`into_jlong`](Self::into_jlong) and not be dropped yet, and no
+    /// `&mut` to the value may exist while the reference is alive.
+    pub unsafe fn as_ref<'a>(handle: i64) -> Option<&'a T> {
+        unsafe { Self::from_jlong(handle).as_ref() }
+    }
+
+    /// Takes the ownership back, `0` is `None`.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must come from [`into_jlong`](Self::into_jlong) and must not be used afterwards.
+    pub unsafe fn take_from_jlong(handle: i64) -> Option<Box<T>> {
+        let ptr = Self::from_jlong(handle);
+
+        if ptr.is_null() {
+            None
+        } else {
+            unsafe { Some(Box::from_raw(ptr)) }
+        }
+    }
+
+    /// Drops the boxed value, `0` is ignored.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as [`take_from_jlong`](Self::take_from_jlong).
+    pub unsafe fn drop_from_jlong(handle: i64) {
+        drop(unsafe { Self::take_from_jlong(handle) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, rc::Rc, string::String};
+
+    use crate::NativePtr;
+
+    #[test]
+    fn test_native_ptr_lifecycle() {
+        let marker = Rc::new(());
+
+        let handle = NativePtr::into_jlong(Box::new((String::from("native"), marker.clone())));
+        assert_ne!(handle, 0);
+        assert_eq!(Rc::strong_count(&marker), 2);
+
+        let value = unsafe { NativePtr::<(String, Rc<()>)>::as_ref(handle) }.unwrap();
+        assert_eq!(value.0, "native");
+
+        unsafe { NativePtr::<(String, Rc<()>)>::drop_from_jlong(handle) };
+        assert_eq!(Rc::strong_count(&marker), 1);
+
+        assert!(NativePtr::<String>::from_jlong(0).is_null());
+        assert!(unsafe { NativePtr::<String>::as_ref(0) }.is_none());
+        assert!(unsafe { NativePtr::<String>::take_from_jlong(0) }.is_none());
+        unsafe { NativePtr::<String>::drop_from_jlong(0) };
+    }
+}
@@ -0,0 +1,78 @@
+use std::{
+    any::Any,
+    boxed::Box,
+    panic::{catch_unwind, AssertUnwindSafe},
+    string::String,
+    sync::RwLock,
+};
+
+use crate::Context;
+
+type Reporter = Box<dyn Fn(&Context, &str) + Send + Sync>;
+
+static REPORTER: RwLock<Option<Reporter>> = RwLock::new(None);
+
+/// Installs the callback [`catch_panic`] calls with the panic message before throwing it, e.g. to
+/// forward it to `android.util.Log`. It replaces the previous one and runs on the panicking native
+/// thread. A panic or pending exception from the reporter itself is discarded.
+pub fn set_panic_reporter(reporter: impl Fn(&Context, &str) + Send + Sync + 'static) {
+    *REPORTER.write().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(reporter));
+}
+
+/// Runs the body of a native method so that a panic does not unwind into the vm, which is
+/// undefined behavior. A panic is reported, thrown as `java.lang.RuntimeException` and `default`
+/// is returned, the vm ignores the return value while an exception is pending. With the
+/// `debug-refs` feature, local references that `f` leaves outstanding are reported as leaked.
+///
+/// ```no_run
+/// # use typed_jni::{Context, TrampolineClass};
+/// # typed_jni::define_java_class!(JavaExample, "com.example.Example");
+/// extern "C" fn parse<'ctx>(ctx: &'ctx Context, _: TrampolineClass<'ctx, JavaExample>, value: i32) -> i32 {
+///     typed_jni::catch_panic(ctx, 0, || u8::try_from(value).unwrap() as i32)
+/// }
+/// ```
+pub fn catch_panic<R>(ctx: &Context, default: R, f: impl FnOnce() -> R) -> R {
+    #[cfg(feature = "debug-refs")]
+    let outstanding = crate::debug_refs::outstanding_local_refs();
+
+    let ret = catch_unwind(AssertUnwindSafe(f));
+
+    // the vm deletes the local references of the native method once it returns
+    #[cfg(feature = "debug-refs")]
+    crate::debug_refs::check_leaked(outstanding, "returning from a native method");
+
+    match ret {
+        Ok(ret) => ret,
+        Err(payload) => {
+            let message = panic_message(&*payload);
+
+            report(ctx, &message);
+
+            if let Ok(class) = ctx.find_class(c"java/lang/RuntimeException") {
+                unsafe { ctx.throw_new(&class, &message) };
+            }
+
+            default
+        }
+    }
+}
+
+fn report(ctx: &Context, message: &str) {
+    let reporter = REPORTER.read().unwrap_or_else(|e| e.into_inner());
+
+    if let Some(reporter) = reporter.as_ref() {
+        let _ = catch_unwind(AssertUnwindSafe(|| reporter(ctx, message)));
+
+        ctx.exception_clear();
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        String::from(*s)
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        String::from("Box<dyn Any>")
+    }
+}
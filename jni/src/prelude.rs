@@ -0,0 +1,5 @@
+pub use crate::{
+    define_java_class, java_bindings, Args, Class, Context, Error, FromRaw, GlobalClass, GlobalObject, IntoRaw, JString,
+    LocalClass, LocalObject, NoArgs, Null, Object, ObjectType, StrongRef, Throwable, TrampolineClass, TrampolineObject,
+    TrampolineReturn, Type, WeakClass, WeakObject, WeakRef,
+};
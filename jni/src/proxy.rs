@@ -0,0 +1,280 @@
+use core::{
+    ffi::CStr,
+    ptr::null_mut,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use std::{boxed::Box, sync::Mutex};
+
+use crate::{
+    catch_panic,
+    sys::jobject,
+    typed::{__CachedClass, Signature, Type},
+    Array, AsRaw, Context, Error, Extends, FromRaw, IntoRaw, JClass, JClassLoader, JCompletableFuture, JMethod, JObject,
+    JRunnable, LocalClass, LocalObject, NativePtr, Object, ObjectType, StrongRef, Throwable, TrampolineClass, TrampolineObject,
+    TrampolineReturn,
+};
+
+/// The class file compiled from `java/com/github/kr328/typedjni/NativeInvocationHandler.java`.
+const HANDLER_CLASS_FILE: &[u8] = include_bytes!("../java/com/github/kr328/typedjni/NativeInvocationHandler.class");
+
+type Handler = Box<
+    dyn for<'ctx> Fn(
+            &'ctx Context,
+            TrampolineObject<'ctx, JMethod>,
+            Option<TrampolineObject<'ctx, Array<JObject>>>,
+        ) -> Result<Option<LocalObject<'ctx, JObject>>, Error<'ctx>>
+        + Send
+        + Sync,
+>;
+
+struct NativeInvocationHandler;
+
+impl Type for NativeInvocationHandler {
+    const SIGNATURE: Signature = Signature::Object("com/github/kr328/typedjni/NativeInvocationHandler");
+}
+
+impl ObjectType for NativeInvocationHandler {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"com/github/kr328/typedjni/NativeInvocationHandler");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Lcom/github/kr328/typedjni/NativeInvocationHandler;");
+}
+
+unsafe impl Extends<JInvocationHandler> for NativeInvocationHandler {}
+
+struct JInvocationHandler;
+
+impl Type for JInvocationHandler {
+    const SIGNATURE: Signature = Signature::Object("java/lang/reflect/InvocationHandler");
+}
+
+impl ObjectType for JInvocationHandler {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/lang/reflect/InvocationHandler");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/lang/reflect/InvocationHandler;");
+}
+
+struct JProxy;
+
+impl Type for JProxy {
+    const SIGNATURE: Signature = Signature::Object("java/lang/reflect/Proxy");
+}
+
+impl ObjectType for JProxy {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/lang/reflect/Proxy");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/lang/reflect/Proxy;");
+}
+
+struct JBiConsumer;
+
+impl Type for JBiConsumer {
+    const SIGNATURE: Signature = Signature::Object("java/util/function/BiConsumer");
+}
+
+impl ObjectType for JBiConsumer {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/util/function/BiConsumer");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/util/function/BiConsumer;");
+}
+
+struct CompletionException;
+
+impl Type for CompletionException {
+    const SIGNATURE: Signature = Signature::Object("java/util/concurrent/CompletionException");
+}
+
+impl ObjectType for CompletionException {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/util/concurrent/CompletionException");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/util/concurrent/CompletionException;");
+}
+
+static C_COMPLETION_EXCEPTION: __CachedClass = __CachedClass::new();
+static C_HANDLER: __CachedClass = __CachedClass::new();
+static REGISTERED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn invoke_native<'ctx>(
+    ctx: &'ctx Context,
+    _: TrampolineObject<'ctx, NativeInvocationHandler>,
+    handle: i64,
+    method: TrampolineObject<'ctx, JMethod>,
+    args: Option<TrampolineObject<'ctx, Array<JObject>>>,
+) -> jobject {
+    catch_panic(ctx, null_mut(), || {
+        let handler = unsafe { NativePtr::<Handler>::as_ref(handle) }.expect("BROKEN: proxy handler released.");
+
+        handler(ctx, method, args).into_return()
+    })
+}
+
+extern "C" fn release_native<'ctx>(ctx: &'ctx Context, _: TrampolineClass<'ctx, NativeInvocationHandler>, handle: i64) {
+    catch_panic(ctx, (), || unsafe { NativePtr::<Handler>::drop_from_jlong(handle) })
+}
+
+/// Finds the handler class, or defines it from the embedded class file in the system class loader
+/// where it is not on the class path, and registers its natives once.
+fn handler_class(ctx: &Context) -> Result<LocalClass<'_, NativeInvocationHandler>, Error<'_>> {
+    let class = match C_HANDLER.get::<NativeInvocationHandler>(ctx) {
+        Ok(class) => class,
+        Err(_) => {
+            let loader: LocalObject<JClassLoader> =
+                LocalClass::<JClassLoader>::find_class(ctx)?.call_method(ctx, "getSystemClassLoader", ())?;
+
+            // another thread may have defined it in the meantime, which the lookup below finds
+            let _ = ctx.define_class(
+                NativeInvocationHandler::CLASS_NAME.unwrap(),
+                Some(loader.as_raw()),
+                HANDLER_CLASS_FILE,
+            );
+
+            C_HANDLER.get::<NativeInvocationHandler>(ctx)?
+        }
+    };
+
+    if !REGISTERED.load(Ordering::Acquire) {
+        unsafe {
+            ctx.register_natives(
+                class.as_raw(),
+                [
+                    (
+                        c"invokeNative",
+                        c"(JLjava/lang/reflect/Method;[Ljava/lang/Object;)Ljava/lang/Object;",
+                        invoke_native as *const (),
+                    ),
+                    (c"releaseNative", c"(J)V", release_native as *const ()),
+                ],
+            )?;
+        }
+
+        REGISTERED.store(true, Ordering::Release);
+    }
+
+    Ok(class)
+}
+
+impl Context {
+    /// A `java.lang.reflect.Proxy` implementing the interface `I`, whose methods call `handler` with
+    /// the reflected method and its arguments, `None` for a method without parameters. Primitive
+    /// arguments arrive boxed, and the return value is unboxed by the proxy, so a method returning
+    /// `int` has to return an `Integer`. `equals`, `hashCode` and `toString` compare and print the
+    /// proxy by identity without calling `handler`.
+    ///
+    /// An [`Error::Throwable`] returned by `handler` is thrown to the caller, a checked exception
+    /// the method does not declare is wrapped in `UndeclaredThrowableException` by the proxy. Other
+    /// errors and panics are thrown as `java.lang.RuntimeException`.
+    ///
+    /// `handler` may be called on any thread that calls the proxy and is dropped on the finalizer
+    /// thread once the proxy is collected. This relies on `Object.finalize`, which is deprecated
+    /// for removal: on a vm started with `--finalization=disabled`, `handler` is never dropped and
+    /// leaks with everything it captures. `I` is found with `FindClass`, and the proxy class is
+    /// defined in the class loader of `I`.
+    ///
+    /// ```no_run
+    /// # use typed_jni::{Context, JObject, LocalObject};
+    /// # typed_jni::define_java_class!(JavaListener, "com.example.Listener");
+    /// # fn register(ctx: &Context) {
+    /// let listener = ctx
+    ///     .new_proxy::<JavaListener, _>(|ctx, method, _args| {
+    ///         println!("{} called", method.name(ctx)?);
+    ///
+    ///         Ok(None)
+    ///     })
+    ///     .unwrap();
+    /// # }
+    /// ```
+    ///
+    /// The invocation handler is a small Java class embedded in this crate and defined at runtime
+    /// if it is not found. Android cannot define classes from class files, so an Android
+    /// application has to include `java/com/github/kr328/typedjni/NativeInvocationHandler.java`
+    /// of this crate in its sources.
+    pub fn new_proxy<I, F>(&self, handler: F) -> Result<LocalObject<'_, I>, Error<'_>>
+    where
+        I: ObjectType,
+        F: for<'ctx> Fn(
+                &'ctx Context,
+                TrampolineObject<'ctx, JMethod>,
+                Option<TrampolineObject<'ctx, Array<JObject>>>,
+            ) -> Result<Option<LocalObject<'ctx, JObject>>, Error<'ctx>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let c_handler = handler_class(self)?;
+
+        let interface = unsafe { LocalObject::<JClass>::from_raw(LocalClass::<I>::find_class(self)?.into_raw()) };
+        let loader: Option<LocalObject<JClassLoader>> = interface.call_method(self, "getClassLoader", ())?;
+        let interfaces =
+            LocalObject::<Array<JClass>>::new_with_initial(self, 1, &LocalClass::<JClass>::find_class(self)?, &interface)?;
+
+        let handle = NativePtr::<Handler>::into_jlong(Box::new(Box::new(handler)));
+        let invocation_handler = match c_handler.new_object(self, handle) {
+            Ok(invocation_handler) => invocation_handler.upcast::<JInvocationHandler>(),
+            Err(err) => {
+                unsafe { NativePtr::<Handler>::drop_from_jlong(handle) };
+
+                return Err(err);
+            }
+        };
+
+        let proxy: LocalObject<JObject> = LocalClass::<JProxy>::find_class(self)?.call_method(
+            self,
+            "newProxyInstance",
+            (loader.as_ref(), &interfaces, &invocation_handler),
+        )?;
+
+        Ok(unsafe { LocalObject::from_raw(proxy.into_raw()) })
+    }
+
+    /// A `java.lang.Runnable` whose `run()` calls `f`, see [`new_proxy`](Self::new_proxy). Runs
+    /// from several threads at once are serialized, and a `run()` of the same runnable from within
+    /// `f` deadlocks. `f` is dropped once the runnable is collected.
+    pub fn new_runnable(&self, f: impl FnMut() + Send + 'static) -> Result<LocalObject<'_, JRunnable>, Error<'_>> {
+        let f = Mutex::new(f);
+
+        self.new_proxy::<JRunnable, _>(move |_, _, _| {
+            (f.lock().unwrap_or_else(|e| e.into_inner()))();
+
+            Ok(None)
+        })
+    }
+}
+
+impl<T: ObjectType, R: StrongRef> Object<JCompletableFuture<T>, R> {
+    /// Calls `f` with the result once the future completes, right away if it is done already,
+    /// through `whenComplete` and a proxy from [`Context::new_proxy`]. `f` runs on the thread that
+    /// completes the future. A failure arrives as `Err` with the thrown exception, a
+    /// `CompletionException` is unwrapped to its cause. The returned future completes like this one
+    /// after `f` has run.
+    pub fn when_complete<'ctx, F>(
+        &self,
+        ctx: &'ctx Context,
+        f: F,
+    ) -> Result<LocalObject<'ctx, JCompletableFuture<T>>, Error<'ctx>>
+    where
+        F: for<'a> FnOnce(&'a Context, Result<Option<LocalObject<'a, T>>, LocalObject<'a, Throwable>>) + Send + 'static,
+    {
+        let f = Mutex::new(Some(f));
+
+        let action = ctx.new_proxy::<JBiConsumer, _>(move |ctx, _, args| {
+            let Some(f) = f.lock().unwrap_or_else(|e| e.into_inner()).take() else {
+                return Ok(None);
+            };
+
+            let args = args.expect("BROKEN: BiConsumer.accept without arguments.");
+            let result = args.get_element(ctx, 0)?;
+
+            match args.get_element(ctx, 1)? {
+                None => f(ctx, Ok(result.map(|r| unsafe { LocalObject::from_raw(r.into_raw()) }))),
+                Some(thrown) => {
+                    let thrown = unsafe { LocalObject::<Throwable>::from_raw(thrown.into_raw()) };
+                    let c_completion = C_COMPLETION_EXCEPTION.get::<CompletionException>(ctx)?;
+
+                    if thrown.is_instance_of(ctx, &c_completion) {
+                        f(ctx, Err(thrown.cause(ctx)?.unwrap_or(thrown)))
+                    } else {
+                        f(ctx, Err(thrown))
+                    }
+                }
+            }
+
+            Ok(None)
+        })?;
+
+        self.call_method(ctx, "whenComplete", &action)
+    }
+}
@@ -1,3 +1,6 @@
+/// Conversions between the typed wrappers and their underlying handles. For references and typed
+/// objects ([`Object`](crate::Object), [`Class`](crate::Class), including [`JString`](crate::JString)
+/// and arrays) the handle is the reference kind, which converts further to a raw `jobject`.
 pub trait Raw {
     type Raw;
 }
@@ -6,10 +9,13 @@ impl<V: Raw> Raw for Option<V> {
     type Raw = Option<V::Raw>;
 }
 
+/// Borrows the handle, ownership stays with `self`.
 pub trait AsRaw: Raw {
     fn as_raw(&self) -> &Self::Raw;
 }
 
+/// Gives up the handle. An owned reference is not released anymore, the caller becomes responsible
+/// for it, e.g. by returning it from a native method or converting it back with [`FromRaw`].
 pub trait IntoRaw: Raw {
     fn into_raw(self) -> Self::Raw;
 }
@@ -20,7 +26,11 @@ impl<V: IntoRaw> IntoRaw for Option<V> {
     }
 }
 
+/// Takes ownership of the handle, which is released when the result is dropped.
 pub trait FromRaw: Raw {
+    /// # Safety
+    ///
+    /// `raw` must be valid for the reference kind and not owned by anything else.
     unsafe fn from_raw(raw: Self::Raw) -> Self;
 }
 
@@ -3,7 +3,7 @@ use core::{marker::PhantomData, ptr::NonNull};
 use crate::{
     context::Context,
     sys::{_jobject, jobject},
-    AsRaw, FromRaw, IntoRaw, Raw,
+    vm, AsRaw, Error, FromRaw, IntoRaw, Raw,
 };
 
 mod __sealed {
@@ -19,8 +19,9 @@ impl<R: Ref> Raw for R {
 }
 
 pub trait StrongRef: Ref {
-    fn to_global(&self) -> Global {
-        Context::with_attached(|ctx| unsafe { Global::from_raw(ctx.new_global_ref(*self.as_raw())) })
+    /// `NewGlobalRef` returns null when the vm is out of memory, which is [`Error::OutOfMemory`].
+    fn to_global(&self) -> Result<Global, Error<'static>> {
+        Context::with_attached(|ctx| unsafe { new_global(ctx, *self.as_raw()) })
     }
 
     fn to_local<'ctx>(&self, ctx: &'ctx Context) -> Local<'ctx> {
@@ -32,6 +33,16 @@ pub trait StrongRef: Ref {
     }
 }
 
+pub(crate) unsafe fn new_global(ctx: &Context, object: jobject) -> Result<Global, Error<'static>> {
+    let raw = unsafe { ctx.new_global_ref(object) };
+
+    if raw.is_null() {
+        Err(Error::OutOfMemory)
+    } else {
+        unsafe { Ok(Global::from_raw(raw)) }
+    }
+}
+
 pub trait WeakRef: Ref {
     fn upgrade_global(&self) -> Option<Global> {
         Context::with_attached(|ctx| unsafe {
@@ -78,6 +89,11 @@ impl Clone for Global {
 
 impl Drop for Global {
     fn drop(&mut self) {
+        // references still held by thread locals are released together with a destroyed vm
+        if !vm::is_vm_available() {
+            return;
+        }
+
         unsafe { Context::with_attached(|ctx| ctx.delete_global_ref(self.raw.as_ptr())) }
     }
 }
@@ -114,6 +130,14 @@ impl Ref for Global {
 
 impl StrongRef for Global {}
 
+/// A local reference is only valid on the thread that created it, so it is neither `Send` nor
+/// `Sync`. Convert it with [`StrongRef::to_global`] before moving the object to another thread.
+///
+/// ```compile_fail
+/// fn assert_send<T: Send>() {}
+///
+/// assert_send::<typed_jni::LocalObject<'static, typed_jni::JString>>();
+/// ```
 #[repr(transparent)]
 pub struct Local<'ctx> {
     raw: NonNull<_jobject>,
@@ -124,6 +148,9 @@ impl<'ctx> Clone for Local<'ctx> {
     fn clone(&self) -> Self {
         let raw = Context::with_current(|ctx| unsafe { ctx.new_local_ref(self.raw.as_ptr()) }).unwrap();
 
+        #[cfg(feature = "debug-refs")]
+        crate::debug_refs::track_acquire();
+
         Local {
             raw: NonNull::new(raw).unwrap(),
             _ctx: PhantomData,
@@ -134,6 +161,9 @@ impl<'ctx> Clone for Local<'ctx> {
 impl<'ctx> Drop for Local<'ctx> {
     fn drop(&mut self) {
         Context::with_current(|ctx| unsafe { ctx.delete_local_ref(self.raw.as_ptr()) }).unwrap();
+
+        #[cfg(feature = "debug-refs")]
+        crate::debug_refs::track_release();
     }
 }
 
@@ -145,6 +175,9 @@ impl<'ctx> AsRaw for Local<'ctx> {
 
 impl<'ctx> FromRaw for Local<'ctx> {
     unsafe fn from_raw(raw: Self::Raw) -> Self {
+        #[cfg(feature = "debug-refs")]
+        crate::debug_refs::track_acquire();
+
         Self {
             raw: NonNull::new(raw).unwrap(),
             _ctx: PhantomData,
@@ -158,6 +191,9 @@ impl<'ctx> IntoRaw for Local<'ctx> {
 
         core::mem::forget(self);
 
+        #[cfg(feature = "debug-refs")]
+        crate::debug_refs::track_release();
+
         r.as_ptr()
     }
 }
@@ -188,6 +224,16 @@ impl<'ctx> AsRaw for Trampoline<'ctx> {
     }
 }
 
+/// A trampoline reference is owned by the native frame, so nothing is released on either side.
+impl<'ctx> FromRaw for Trampoline<'ctx> {
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self {
+            raw: NonNull::new(raw).unwrap(),
+            _ctx: PhantomData,
+        }
+    }
+}
+
 impl<'ctx> IntoRaw for Trampoline<'ctx> {
     fn into_raw(self) -> Self::Raw {
         self.raw.as_ptr()
@@ -216,6 +262,11 @@ impl Clone for Weak {
 
 impl Drop for Weak {
     fn drop(&mut self) {
+        // references still held by thread locals are released together with a destroyed vm
+        if !vm::is_vm_available() {
+            return;
+        }
+
         unsafe { Context::with_attached(|ctx| ctx.delete_weak_global_ref(self.raw.as_ptr())) }
     }
 }
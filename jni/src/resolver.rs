@@ -1,107 +1,261 @@
-use alloc::{
-    ffi::CString,
-    format,
-    string::{String, ToString},
+use alloc::{string::String, vec::Vec};
+use core::{
+    ffi::CStr,
+    fmt::{Display, Write},
 };
-use core::fmt::{Display, Formatter};
 
-use crate::{Args, Context, Field, LocalObject, Method, Signature, StrongRef, Throwable, Type};
+use crate::{Args, Context, Error, Field, Method, MethodSignature, Signature, StrongRef, Type};
 
 #[cfg(feature = "cache")]
 mod cache {
-    use std::cell::RefCell;
+    use std::{any::TypeId, cell::RefCell, vec::Vec};
 
     use uluru::LRUCache;
 
-    use crate::{Context, LocalObject, StrongRef, Throwable, Weak};
+    use super::FieldSlots;
+    use crate::{Context, Error, MethodSignature, Signature, StrongRef, Weak};
 
+    // caches are per thread and evict the least recently used entry, so memory stays bounded
+    // under contention and is released with the thread
     const MAX_CACHED_PER_THREAD: usize = 32;
 
+    // `kind` is the `TypeId` of the `Method` or `Field`, which tells apart methods from fields and
+    // static from instance members
     struct Entry {
         class: Weak,
-        types_id: usize,
+        kind: TypeId,
         name: &'static str,
+        args: Vec<Signature>,
+        ret: Signature,
         member: *const (),
     }
 
+    struct Layout {
+        class: Weak,
+        layout_id: TypeId,
+        fields: FieldSlots,
+    }
+
     thread_local! {
-        static CACHED: RefCell<LRUCache<Entry, MAX_CACHED_PER_THREAD>> = RefCell::new(LRUCache::new());
+        static CACHED: RefCell<LRUCache<Entry, MAX_CACHED_PER_THREAD>> = const { RefCell::new(LRUCache::new()) };
+        static LAYOUTS: RefCell<LRUCache<Layout, MAX_CACHED_PER_THREAD>> = const { RefCell::new(LRUCache::new()) };
     }
 
+    pub fn with_field_layout<C: StrongRef, R, F: FnOnce(&mut FieldSlots) -> R>(
+        ctx: &Context,
+        class: &C,
+        layout_id: TypeId,
+        f: F,
+    ) -> R {
+        // slots are taken out while reading so that nested layouts do not hit the RefCell borrow
+        let mut fields = LAYOUTS
+            .with(|layouts| {
+                layouts
+                    .borrow_mut()
+                    .find(|e| e.layout_id == layout_id && is_cached_class(ctx, &e.class, class))
+                    .map(|e| core::mem::take(&mut e.fields))
+            })
+            .unwrap_or_default();
+
+        let ret = f(&mut fields);
+
+        LAYOUTS.with(|layouts| {
+            let mut layouts = layouts.borrow_mut();
+
+            match layouts.find(|e| e.layout_id == layout_id && is_cached_class(ctx, &e.class, class)) {
+                Some(e) => e.fields = fields,
+                None => insert_reusing_expired(
+                    ctx,
+                    &mut layouts,
+                    |e| &e.class,
+                    Layout {
+                        class: class.downgrade_weak(),
+                        layout_id,
+                        fields,
+                    },
+                ),
+            }
+        });
+
+        ret
+    }
+
+    /// A field is looked up with the `signature` of a method without arguments returning its type.
     pub fn find_member<
         'ctx,
         C: StrongRef,
-        M: Copy,
-        F: FnOnce(Option<*const ()>) -> Result<(M, *const ()), LocalObject<'ctx, Throwable>>,
+        M: Copy + 'static,
+        F: FnOnce(Option<*const ()>) -> Result<(M, *const ()), Error<'ctx>>,
     >(
         ctx: &'ctx Context,
         class: &C,
         name: &'static str,
+        signature: MethodSignature,
         find: F,
-    ) -> Result<M, LocalObject<'ctx, Throwable>> {
+    ) -> Result<M, Error<'ctx>> {
         CACHED.with(|entries| {
             let mut entries = entries.borrow_mut();
 
-            let types_id = find_member::<C, M, F> as *const () as usize;
+            let kind = TypeId::of::<M>();
 
+            // names are usually the same literal, the content is compared for separate ones
             let cached = entries.find(|e| {
-                e.types_id == types_id && name.as_ptr() == e.name.as_ptr() && ctx.is_same_object(Some(&e.class), Some(class))
+                e.kind == kind
+                    && (core::ptr::eq(e.name, name) || e.name == name)
+                    && e.ret == signature.ret
+                    && e.args == signature.args
+                    && is_cached_class(ctx, &e.class, class)
             });
-            match cached {
-                Some(e) => Ok(find(Some(e.member))?.0),
-                None => {
-                    let (member, cache) = find(None)?;
-
-                    entries.insert(Entry {
-                        class: class.downgrade_weak(),
-                        types_id,
-                        name,
-                        member: cache,
-                    });
+            // `is_cached_class` only matches a live class, so the member ids of an unloaded one are
+            // never trusted
+            if let Some(e) = cached {
+                #[cfg(feature = "debug-cache")]
+                crate::debug_cache::record_lookup(true);
 
-                    Ok(member)
-                }
+                return Ok(find(Some(e.member))?.0);
             }
+
+            #[cfg(feature = "debug-cache")]
+            crate::debug_cache::record_lookup(false);
+
+            let (member, cache) = find(None)?;
+
+            insert_reusing_expired(
+                ctx,
+                &mut entries,
+                |e| &e.class,
+                Entry {
+                    class: class.downgrade_weak(),
+                    kind,
+                    name,
+                    args: signature.args.to_vec(),
+                    ret: signature.ret,
+                    member: cache,
+                },
+            );
+
+            Ok(member)
         })
     }
-}
 
-pub fn method_signature_of(args: &[Signature], ret: &Signature) -> String {
-    struct ArgsSignature<'a>(&'a [Signature]);
+    // only a live weak reference is trusted to compare against `class`, a collected one may match
+    // anything
+    pub(super) fn is_cached_class<C: StrongRef>(ctx: &Context, cached: &Weak, class: &C) -> bool {
+        !ctx.is_same_object(Some(cached), None::<&Weak>) && ctx.is_same_object(Some(cached), Some(class))
+    }
 
-    impl<'a> Display for ArgsSignature<'a> {
-        fn fmt(&self, f: &mut Formatter<'_>) -> alloc::fmt::Result {
-            for x in self.0.iter() {
-                x.fmt(f)?
+    fn insert_reusing_expired<T, const N: usize>(
+        ctx: &Context,
+        entries: &mut LRUCache<T, N>,
+        class_of: impl Fn(&T) -> &Weak,
+        entry: T,
+    ) {
+        match entries.find(|e| ctx.is_same_object(Some(class_of(e)), None::<&Weak>)) {
+            Some(expired) => *expired = entry,
+            None => {
+                entries.insert(entry);
             }
-
-            Ok(())
         }
     }
+}
 
-    format!("({}){}", ArgsSignature(args), ret)
+pub type FieldSlots = Vec<(&'static str, Signature, *const ())>;
+
+#[cfg(feature = "cache")]
+pub use cache::with_field_layout;
+
+#[cfg(not(feature = "cache"))]
+pub fn with_field_layout<C: StrongRef, R, F: FnOnce(&mut FieldSlots) -> R>(
+    _ctx: &Context,
+    _class: &C,
+    _layout_id: core::any::TypeId,
+    f: F,
+) -> R {
+    f(&mut Vec::new())
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static SCRATCH: core::cell::Cell<String> = const { core::cell::Cell::new(String::new()) };
+}
+
+/// Builds the NUL terminated `name` and `signature` of a member lookup in a per-thread scratch
+/// buffer, so repeated lookups do not allocate. Nested lookups (e.g. from a class initializer
+/// calling back into native code) get a buffer of their own. The class names in `signature` go
+/// through the [remapper](crate::set_class_name_remapper). An interior NUL is
+/// [`Error::InvalidName`] and `f` is not called.
+pub fn with_member_cstrs<'ctx, R>(
+    name: &str,
+    signature: impl Display,
+    f: impl FnOnce(&CStr, &CStr) -> Result<R, Error<'ctx>>,
+) -> Result<R, Error<'ctx>> {
+    #[cfg(feature = "std")]
+    let mut buf = SCRATCH.take();
+    #[cfg(not(feature = "std"))]
+    let mut buf = String::new();
+
+    buf.clear();
+    buf.push_str(name);
+    buf.push('\0');
+    let name_len = buf.len();
+    write!(buf, "{}", signature).unwrap();
+
+    #[cfg(feature = "std")]
+    if let Some(remapped) = crate::class_cache::remap_descriptor(&buf[name_len..]) {
+        buf.truncate(name_len);
+        buf.push_str(&remapped);
+    }
+
+    buf.push('\0');
+
+    let ret = {
+        let (name, signature) = buf.as_bytes().split_at(name_len);
+
+        match (CStr::from_bytes_with_nul(name), CStr::from_bytes_with_nul(signature)) {
+            (Ok(name), Ok(signature)) => f(name, signature),
+            _ => Err(Error::InvalidName),
+        }
+    };
+
+    #[cfg(feature = "std")]
+    SCRATCH.set(buf);
+
+    ret
 }
 
 pub fn find_method<'a, 'ctx, const STATIC: bool, C: StrongRef, A: Args<'a>, R: Type>(
     ctx: &'ctx Context,
     class: &C,
     name: &'static str,
-) -> Result<Method<STATIC>, LocalObject<'ctx, Throwable>>
+) -> Result<Method<STATIC>, Error<'ctx>>
 where
     A::Array<Signature>: AsRef<[Signature]>,
 {
+    let args = A::signatures();
+    let signature = MethodSignature {
+        args: args.as_ref(),
+        ret: R::SIGNATURE,
+    };
+
+    find_method_by_signature(ctx, class, name, signature)
+}
+
+/// Like [`find_method`] with a signature that is not spelled by Rust types. The cache entry is
+/// the same as for any types of that signature.
+pub fn find_method_by_signature<'ctx, const STATIC: bool, C: StrongRef>(
+    ctx: &'ctx Context,
+    class: &C,
+    name: &'static str,
+    signature: MethodSignature,
+) -> Result<Method<STATIC>, Error<'ctx>> {
     #[cfg(feature = "cache")]
     return {
         use crate::{FromRaw, IntoRaw};
 
-        cache::find_member(ctx, class, name, |cached| match cached {
+        cache::find_member(ctx, class, name, signature, |cached| match cached {
             Some(ptr) => unsafe { Ok((Method::from_raw(ptr as _), ptr)) },
             None => {
-                let m = ctx.find_method(
-                    class,
-                    CString::new(name).unwrap(),
-                    CString::new(method_signature_of(A::signatures().as_ref(), &R::SIGNATURE)).unwrap(),
-                )?;
+                let m = lookup_method::<STATIC, C>(ctx, class, name, signature)?;
 
                 Ok((m, m.into_raw() as *const ()))
             }
@@ -109,30 +263,73 @@ where
     };
 
     #[cfg(not(feature = "cache"))]
-    ctx.find_method(
-        class,
-        CString::new(name).unwrap(),
-        CString::new(method_signature_of(A::signatures().as_ref(), &R::SIGNATURE)).unwrap(),
-    )
+    lookup_method::<STATIC, C>(ctx, class, name, signature)
+}
+
+/// Skips the member cache even with the `cache` feature.
+pub fn find_method_uncached<'a, 'ctx, const STATIC: bool, C: StrongRef, A: Args<'a>, R: Type>(
+    ctx: &'ctx Context,
+    class: &C,
+    name: &str,
+) -> Result<Method<STATIC>, Error<'ctx>>
+where
+    A::Array<Signature>: AsRef<[Signature]>,
+{
+    let args = A::signatures();
+    let signature = MethodSignature {
+        args: args.as_ref(),
+        ret: R::SIGNATURE,
+    };
+
+    lookup_method(ctx, class, name, signature)
+}
+
+fn lookup_method<'ctx, const STATIC: bool, C: StrongRef>(
+    ctx: &'ctx Context,
+    class: &C,
+    name: &str,
+    signature: MethodSignature,
+) -> Result<Method<STATIC>, Error<'ctx>> {
+    with_member_cstrs(name, signature, |name, signature| {
+        #[cfg(feature = "debug-diagnostics")]
+        return ctx
+            .find_method(class, name, signature)
+            .map_err(|err| member_not_found(ctx, class, name, signature, err));
+
+        #[cfg(not(feature = "debug-diagnostics"))]
+        ctx.find_method(class, name, signature)
+    })
+}
+
+pub fn find_field<'ctx, const STATIC: bool, C: StrongRef, T: Type>(
+    ctx: &'ctx Context,
+    class: &C,
+    name: &'static str,
+) -> Result<Field<STATIC>, Error<'ctx>> {
+    find_field_by_signature(ctx, class, name, T::SIGNATURE)
 }
 
-pub fn find_field<'a, 'ctx, const STATIC: bool, C: StrongRef, T: Type>(
+/// Like [`find_field`] with a signature that is not spelled by a Rust type.
+pub fn find_field_by_signature<'ctx, const STATIC: bool, C: StrongRef>(
     ctx: &'ctx Context,
     class: &C,
     name: &'static str,
-) -> Result<Field<STATIC>, LocalObject<'ctx, Throwable>> {
+    signature: Signature,
+) -> Result<Field<STATIC>, Error<'ctx>> {
     #[cfg(feature = "cache")]
     return {
         use crate::{FromRaw, IntoRaw};
 
-        cache::find_member(ctx, class, name, |cached| match cached {
+        // keyed like a method without arguments returning the field type
+        let key = MethodSignature {
+            args: &[],
+            ret: signature,
+        };
+
+        cache::find_member(ctx, class, name, key, |cached| match cached {
             Some(ptr) => unsafe { Ok((Field::from_raw(ptr as _), ptr)) },
             None => {
-                let f = ctx.find_field(
-                    class,
-                    CString::new(name).unwrap(),
-                    CString::new(T::SIGNATURE.to_string()).unwrap(),
-                )?;
+                let f = lookup_field::<STATIC, C>(ctx, class, name, signature)?;
 
                 Ok((f, f.into_raw() as _))
             }
@@ -140,11 +337,70 @@ pub fn find_field<'a, 'ctx, const STATIC: bool, C: StrongRef, T: Type>(
     };
 
     #[cfg(not(feature = "cache"))]
-    ctx.find_field(
-        class,
-        CString::new(name).unwrap(),
-        CString::new(T::SIGNATURE.to_string()).unwrap(),
-    )
+    lookup_field::<STATIC, C>(ctx, class, name, signature)
+}
+
+/// Skips the member cache even with the `cache` feature.
+pub fn find_field_uncached<'ctx, const STATIC: bool, C: StrongRef, T: Type>(
+    ctx: &'ctx Context,
+    class: &C,
+    name: &str,
+) -> Result<Field<STATIC>, Error<'ctx>> {
+    lookup_field(ctx, class, name, T::SIGNATURE)
+}
+
+fn lookup_field<'ctx, const STATIC: bool, C: StrongRef>(
+    ctx: &'ctx Context,
+    class: &C,
+    name: &str,
+    signature: Signature,
+) -> Result<Field<STATIC>, Error<'ctx>> {
+    with_member_cstrs(name, signature, |name, signature| {
+        #[cfg(feature = "debug-diagnostics")]
+        return ctx
+            .find_field(class, name, signature)
+            .map_err(|err| member_not_found(ctx, class, name, signature, err));
+
+        #[cfg(not(feature = "debug-diagnostics"))]
+        ctx.find_field(class, name, signature)
+    })
+}
+
+/// Keeps any other error, e.g. an `ExceptionInInitializerError` of the class, as it is.
+#[cfg(feature = "debug-diagnostics")]
+fn member_not_found<'ctx, C: StrongRef>(
+    ctx: &'ctx Context,
+    class: &C,
+    name: &CStr,
+    signature: &CStr,
+    err: Error<'ctx>,
+) -> Error<'ctx> {
+    use alloc::string::ToString;
+
+    use crate::{AsRaw, FromRaw, JClass, Object};
+
+    let Error::Throwable(throwable) = err else {
+        return err;
+    };
+
+    let missing = [c"java/lang/NoSuchMethodError", c"java/lang/NoSuchFieldError"]
+        .into_iter()
+        .any(|error| {
+            ctx.find_class(error)
+                .is_ok_and(|error| unsafe { ctx.is_instance_of(throwable.as_raw(), &error) })
+        });
+    if !missing {
+        return Error::Throwable(throwable);
+    }
+
+    let class = unsafe { Object::<JClass, _>::from_raw(class.to_local(ctx)) };
+
+    Error::MemberNotFound {
+        class: class.get_name(ctx),
+        name: name.to_string_lossy().to_string(),
+        signature: signature.to_string_lossy().to_string(),
+        throwable,
+    }
 }
 
 #[cfg(test)]
@@ -209,4 +465,104 @@ mod tests {
     fn test_atomic_ordering_acqrel() {
         test_atomic_ordering(Ordering::Release, Ordering::Acquire);
     }
+
+    #[cfg(feature = "cache")]
+    #[test]
+    fn test_expired_weak_never_matches() {
+        use crate::{
+            mock::{dangling_local, MockEnv},
+            resolver::cache::is_cached_class,
+            sys::{jboolean, jobject, JNIEnv, JNI_FALSE, JNI_TRUE},
+            AsRaw, FromRaw, Weak,
+        };
+
+        const EXPIRED: usize = 0x10;
+
+        // models a vm that compares a collected weak reference equal to everything
+        unsafe extern "C" fn is_same_object(_: *mut JNIEnv, a: jobject, b: jobject) -> jboolean {
+            if a as usize == EXPIRED || a == b {
+                JNI_TRUE
+            } else {
+                JNI_FALSE
+            }
+        }
+
+        let env = MockEnv::new(|functions| functions.IsSameObject = Some(is_same_object));
+        let ctx = env.ctx();
+
+        let class = dangling_local();
+        let live = unsafe { Weak::from_raw(*class.as_raw()) };
+        let expired = unsafe { Weak::from_raw(EXPIRED as jobject) };
+
+        assert!(is_cached_class(ctx, &live, &class));
+        assert!(!is_cached_class(ctx, &expired, &class));
+        assert!(!ctx.is_same_object(Some(&live), None::<&Weak>));
+
+        // dropping references needs an attached vm
+        core::mem::forget(class);
+        core::mem::forget(live);
+        core::mem::forget(expired);
+    }
+
+    #[test]
+    fn test_member_cstrs_scratch() {
+        use crate::{resolver::with_member_cstrs, Error, MethodSignature, Signature};
+
+        let signature = MethodSignature {
+            args: &[Signature::Int, Signature::Object("java/lang/String")],
+            ret: Signature::Void,
+        };
+
+        let first = with_member_cstrs("call", signature, |name, signature| {
+            assert_eq!(name, c"call");
+            assert_eq!(signature, c"(ILjava/lang/String;)V");
+
+            // nested lookups must not observe or clobber the outer buffer
+            with_member_cstrs("value", Signature::Long, |name, signature| {
+                assert_eq!(name, c"value");
+                assert_eq!(signature, c"J");
+
+                Ok::<_, Error>(())
+            })?;
+
+            assert_eq!(name, c"call");
+
+            Ok(name.as_ptr())
+        })
+        .unwrap();
+
+        let second = with_member_cstrs("call", signature, |name, _| Ok::<_, Error>(name.as_ptr())).unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_member_cstrs_nul_name() {
+        use crate::{resolver::with_member_cstrs, Error, Signature};
+
+        let ret = with_member_cstrs("a\0b", Signature::Int, |_, _| -> Result<(), Error> { unreachable!() });
+        assert!(matches!(ret, Err(Error::InvalidName)));
+
+        let ret = with_member_cstrs("a", Signature::Object("a\0b"), |_, _| -> Result<(), Error> { unreachable!() });
+        assert!(matches!(ret, Err(Error::InvalidName)));
+
+        // the scratch buffer is still usable afterwards
+        let ret = with_member_cstrs("a", Signature::Int, |name, _| Ok::<_, Error>(name.to_owned()));
+        assert_eq!(ret.unwrap().as_c_str(), c"a");
+    }
+
+    #[test]
+    fn test_zero_args_signature() {
+        use crate::{Args, JString, MethodSignature, NoArgs, Signature, Type};
+
+        fn method_signature_of(args: &[Signature], ret: &Signature) -> String {
+            MethodSignature { args, ret: *ret }.to_string()
+        }
+
+        assert!(<() as Args>::signatures().is_empty());
+        assert!(<NoArgs as Args>::signatures().is_empty());
+
+        assert_eq!(method_signature_of(&<() as Args>::signatures(), &Signature::Void), "()V");
+        assert_eq!(method_signature_of(&[], &JString::SIGNATURE), "()Ljava/lang/String;");
+        assert_eq!(method_signature_of(&[], &Signature::Array(&Signature::Int)), "()[I");
+    }
 }
@@ -4,8 +4,8 @@
 #![allow(unused_qualifications)]
 #![allow(dead_code)]
 
-pub const JNI_FALSE: bool = false;
-pub const JNI_TRUE: bool = true;
+pub const JNI_FALSE: jboolean = 0;
+pub const JNI_TRUE: jboolean = 1;
 pub const JNI_OK: i32 = 0;
 pub const JNI_ERR: i32 = -1;
 pub const JNI_EDETACHED: i32 = -2;
@@ -29,7 +29,7 @@ pub const JNI_VERSION_21: u32 = 1376256;
 pub type jint = i32;
 pub type jlong = i64;
 pub type jbyte = i8;
-pub type jboolean = bool;
+pub type jboolean = u8;
 pub type jchar = u16;
 pub type jshort = i16;
 pub type jfloat = f32;
@@ -0,0 +1,143 @@
+use core::ffi::CStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{
+    context::CallResult,
+    typed::{__CachedClass, __CachedMethod, Signature, Type},
+    AsRaw, Context, Error, FromRaw, Local, LocalObject, NoArgs, Object, ObjectType, StrongRef,
+};
+
+/// `java.time.Instant`, a point on the time line with nanosecond precision.
+pub struct JInstant;
+
+impl Type for JInstant {
+    const SIGNATURE: Signature = Signature::Object("java/time/Instant");
+}
+
+impl ObjectType for JInstant {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/time/Instant");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/time/Instant;");
+}
+
+/// `java.time.Duration`, which unlike [`Duration`] may be negative.
+pub struct JDuration;
+
+impl Type for JDuration {
+    const SIGNATURE: Signature = Signature::Object("java/time/Duration");
+}
+
+impl ObjectType for JDuration {
+    const CLASS_NAME: Option<&'static CStr> = Some(c"java/time/Duration");
+    const DESCRIPTOR: Option<&'static CStr> = Some(c"Ljava/time/Duration;");
+}
+
+static C_INSTANT: __CachedClass = __CachedClass::new();
+static C_DURATION: __CachedClass = __CachedClass::new();
+
+/// Seconds and a nanosecond adjustment that may be negative, which both `ofEpochSecond` and
+/// `ofSeconds` normalize. Seconds beyond `i64` are saturated.
+fn to_java_parts(duration: Duration, negative: bool) -> (i64, i64) {
+    let secs = i64::try_from(duration.as_secs()).unwrap_or(i64::MAX);
+    let nanos = duration.subsec_nanos() as i64;
+
+    if negative {
+        (-secs, -nanos)
+    } else {
+        (secs, nanos)
+    }
+}
+
+impl Context {
+    /// `Instant.ofEpochSecond`, times before the epoch included. A time outside the range of
+    /// `Instant` is thrown as `DateTimeException`.
+    pub fn new_instant(&self, time: SystemTime) -> Result<LocalObject<'_, JInstant>, Error<'_>> {
+        static M_OF_EPOCH_SECOND: __CachedMethod<true> = __CachedMethod::new();
+
+        let (secs, nanos) = match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => to_java_parts(after, false),
+            Err(before) => to_java_parts(before.duration(), true),
+        };
+
+        let class = C_INSTANT.get::<JInstant>(self)?;
+        let method = M_OF_EPOCH_SECOND.get::<_, (i64, i64), JInstant>(self, class.as_raw(), "ofEpochSecond")?;
+
+        unsafe {
+            Ok(LocalObject::from_raw(self.call_method::<true, _, _, Local>(
+                class.as_raw(),
+                method,
+                [secs.into(), nanos.into()],
+            )?))
+        }
+    }
+
+    /// `Duration.ofSeconds`. A duration beyond `i64` seconds, e.g. [`Duration::MAX`], saturates to
+    /// the longest `java.time.Duration`.
+    pub fn new_duration(&self, duration: Duration) -> Result<LocalObject<'_, JDuration>, Error<'_>> {
+        static M_OF_SECONDS: __CachedMethod<true> = __CachedMethod::new();
+
+        let (secs, nanos) = to_java_parts(duration, false);
+
+        let class = C_DURATION.get::<JDuration>(self)?;
+        let method = M_OF_SECONDS.get::<_, (i64, i64), JDuration>(self, class.as_raw(), "ofSeconds")?;
+
+        unsafe {
+            Ok(LocalObject::from_raw(self.call_method::<true, _, _, Local>(
+                class.as_raw(),
+                method,
+                [secs.into(), nanos.into()],
+            )?))
+        }
+    }
+}
+
+impl<R: StrongRef> Object<JInstant, R> {
+    /// `None` if the instant is out of the range of [`SystemTime`] on this platform.
+    pub fn to_system_time(&self, ctx: &Context) -> Option<SystemTime> {
+        static M_GET_EPOCH_SECOND: __CachedMethod<false> = __CachedMethod::new();
+        static M_GET_NANO: __CachedMethod<false> = __CachedMethod::new();
+
+        let secs: i64 = call_getter(ctx, self, &C_INSTANT, &M_GET_EPOCH_SECOND, "getEpochSecond");
+        let nanos: i32 = call_getter(ctx, self, &C_INSTANT, &M_GET_NANO, "getNano");
+
+        // the nanosecond part is always a positive adjustment, also before the epoch
+        let nanos = Duration::from_nanos(nanos as u64);
+        if secs >= 0 {
+            UNIX_EPOCH.checked_add(Duration::from_secs(secs as u64))?.checked_add(nanos)
+        } else {
+            UNIX_EPOCH
+                .checked_sub(Duration::from_secs(secs.unsigned_abs()))?
+                .checked_add(nanos)
+        }
+    }
+}
+
+impl<R: StrongRef> Object<JDuration, R> {
+    /// `None` for negative durations.
+    pub fn to_duration(&self, ctx: &Context) -> Option<Duration> {
+        static M_GET_SECONDS: __CachedMethod<false> = __CachedMethod::new();
+        static M_GET_NANO: __CachedMethod<false> = __CachedMethod::new();
+
+        let secs: i64 = call_getter(ctx, self, &C_DURATION, &M_GET_SECONDS, "getSeconds");
+        let nanos: i32 = call_getter(ctx, self, &C_DURATION, &M_GET_NANO, "getNano");
+
+        u64::try_from(secs).ok().map(|secs| Duration::new(secs, nanos as u32))
+    }
+}
+
+fn call_getter<'ctx, T: ObjectType, R: StrongRef, V: Type + CallResult<'ctx>>(
+    ctx: &'ctx Context,
+    this: &Object<T, R>,
+    class: &__CachedClass,
+    method: &__CachedMethod<false>,
+    name: &'static str,
+) -> V {
+    let method = class
+        .get::<T>(ctx)
+        .and_then(|class| method.get::<_, NoArgs, V>(ctx, class.as_raw(), name))
+        .expect("BROKEN: unable to find java/time getter.");
+
+    unsafe {
+        ctx.call_method(this.as_raw(), method, [])
+            .expect("BROKEN: java/time getter throws.")
+    }
+}
@@ -0,0 +1,203 @@
+use alloc::string::ToString;
+use core::ptr::null_mut;
+
+use crate::{
+    sys::jobject, AsRaw, Class, Context, Error, IntoRaw, Jchar, Local, LocalObject, Object, ObjectType, Throwable, Trampoline,
+};
+
+/// Converts the Rust return value of a native method into what the JNI calling convention
+/// expects, so a trampoline can be declared with `-> T::Raw` and end with `.into_return()`.
+///
+/// ```no_run
+/// # use typed_jni::{Context, JString, LocalObject, TrampolineClass, TrampolineReturn};
+/// # typed_jni::define_java_class!(JavaExample, "com.example.Example");
+/// extern "C" fn name<'ctx>(
+///     ctx: &'ctx Context,
+///     _: TrampolineClass<'ctx, JavaExample>,
+///     empty: bool,
+/// ) -> <Option<LocalObject<'ctx, JString>> as TrampolineReturn>::Raw {
+///     (!empty).then(|| JString::new(ctx, "name")).into_return()
+/// }
+/// ```
+///
+/// A `Result<_, LocalObject<Throwable>>` throws the error and returns zero or `null`, which the vm
+/// ignores while the exception is pending, so the body of a native method can use `?`:
+///
+/// ```no_run
+/// # use typed_jni::{Context, JString, LocalObject, Throwable, TrampolineClass, TrampolineObject, TrampolineReturn};
+/// # typed_jni::define_java_class!(JavaExample, "com.github.kr328.typedjni.Example");
+/// extern "C" fn native_function<'ctx>(
+///     ctx: &'ctx Context,
+///     _class: TrampolineClass<'ctx, JavaExample>,
+///     value: TrampolineObject<'ctx, JString>,
+///     start: i32,
+/// ) -> <Result<i32, LocalObject<'ctx, Throwable>> as TrampolineReturn>::Raw {
+///     let print = || -> Result<i32, LocalObject<'ctx, Throwable>> {
+///         let mut buf = [0u8; 64];
+///         let written = value.copy_utf_region(ctx, start, &mut buf)?;
+///
+///         println!("value = {}", String::from_utf8_lossy(&buf[..written]));
+///
+///         Ok(written as i32)
+///     };
+///
+///     print().into_return()
+/// }
+/// ```
+///
+/// A `Result<_, Error>` rethrows a Java throwable the same way, any other error is thrown as a
+/// `java.lang.RuntimeException` with the error as its message.
+pub trait TrampolineReturn {
+    type Raw;
+
+    fn into_return(self) -> Self::Raw;
+}
+
+fn throw_pending<R>(thrown: LocalObject<Throwable>, default: R) -> R {
+    Context::with_current(|ctx| unsafe { ctx.throw(thrown.as_raw()) })
+        .expect("BROKEN: return from native method on detached thread.");
+
+    default
+}
+
+fn throw_error<R>(err: Error, default: R) -> R {
+    let message = err.to_string();
+
+    match err.into_throwable() {
+        Some(thrown) => throw_pending(thrown, default),
+        None => {
+            Context::with_current(|ctx| {
+                if let Ok(class) = ctx.find_class(c"java/lang/RuntimeException") {
+                    unsafe { ctx.throw_new(&class, &message) };
+                }
+            })
+            .expect("BROKEN: return from native method on detached thread.");
+
+            default
+        }
+    }
+}
+
+macro_rules! impl_trampoline_return_primitive {
+    ($typ:ty) => {
+        impl TrampolineReturn for $typ {
+            type Raw = $typ;
+
+            fn into_return(self) -> Self::Raw {
+                self
+            }
+        }
+
+        impl<'ctx> TrampolineReturn for Result<$typ, LocalObject<'ctx, Throwable>> {
+            type Raw = $typ;
+
+            fn into_return(self) -> Self::Raw {
+                self.unwrap_or_else(|thrown| throw_pending(thrown, Default::default()))
+            }
+        }
+
+        impl<'ctx> TrampolineReturn for Result<$typ, Error<'ctx>> {
+            type Raw = $typ;
+
+            fn into_return(self) -> Self::Raw {
+                self.unwrap_or_else(|err| throw_error(err, Default::default()))
+            }
+        }
+    };
+}
+
+impl_trampoline_return_primitive!(());
+impl_trampoline_return_primitive!(bool);
+impl_trampoline_return_primitive!(i8);
+impl_trampoline_return_primitive!(u16);
+impl_trampoline_return_primitive!(i16);
+impl_trampoline_return_primitive!(i32);
+impl_trampoline_return_primitive!(i64);
+impl_trampoline_return_primitive!(f32);
+impl_trampoline_return_primitive!(f64);
+
+impl TrampolineReturn for Jchar {
+    type Raw = u16;
+
+    fn into_return(self) -> Self::Raw {
+        self.into_raw()
+    }
+}
+
+impl<'ctx> TrampolineReturn for Result<Jchar, LocalObject<'ctx, Throwable>> {
+    type Raw = u16;
+
+    fn into_return(self) -> Self::Raw {
+        self.map_or_else(|thrown| throw_pending(thrown, 0), |c| c.into_return())
+    }
+}
+
+impl<'ctx> TrampolineReturn for Result<Jchar, Error<'ctx>> {
+    type Raw = u16;
+
+    fn into_return(self) -> Self::Raw {
+        self.map_or_else(|err| throw_error(err, 0), |c| c.into_return())
+    }
+}
+
+macro_rules! impl_trampoline_return_object {
+    ($name:ident) => {
+        impl<'ctx, T: ObjectType> TrampolineReturn for $name<T, Local<'ctx>> {
+            type Raw = jobject;
+
+            /// The local reference is handed over to the caller frame.
+            fn into_return(self) -> Self::Raw {
+                self.into_raw().into_raw()
+            }
+        }
+
+        impl<'ctx, T: ObjectType> TrampolineReturn for $name<T, Trampoline<'ctx>> {
+            type Raw = jobject;
+
+            fn into_return(self) -> Self::Raw {
+                self.into_raw().into_raw()
+            }
+        }
+
+        impl<'ctx, T: ObjectType> TrampolineReturn for Option<$name<T, Local<'ctx>>> {
+            type Raw = jobject;
+
+            fn into_return(self) -> Self::Raw {
+                self.map(|o| o.into_return()).unwrap_or(null_mut())
+            }
+        }
+
+        impl<'ctx, T: ObjectType> TrampolineReturn for Option<$name<T, Trampoline<'ctx>>> {
+            type Raw = jobject;
+
+            fn into_return(self) -> Self::Raw {
+                self.map(|o| o.into_return()).unwrap_or(null_mut())
+            }
+        }
+
+        impl_trampoline_return_object!(@result $name<T, Local<'ctx>>);
+        impl_trampoline_return_object!(@result $name<T, Trampoline<'ctx>>);
+        impl_trampoline_return_object!(@result Option<$name<T, Local<'ctx>>>);
+        impl_trampoline_return_object!(@result Option<$name<T, Trampoline<'ctx>>>);
+    };
+    (@result $typ:ty) => {
+        impl<'ctx, T: ObjectType> TrampolineReturn for Result<$typ, LocalObject<'ctx, Throwable>> {
+            type Raw = jobject;
+
+            fn into_return(self) -> Self::Raw {
+                self.map_or_else(|thrown| throw_pending(thrown, null_mut()), |o| o.into_return())
+            }
+        }
+
+        impl<'ctx, T: ObjectType> TrampolineReturn for Result<$typ, Error<'ctx>> {
+            type Raw = jobject;
+
+            fn into_return(self) -> Self::Raw {
+                self.map_or_else(|err| throw_error(err, null_mut()), |o| o.into_return())
+            }
+        }
+    };
+}
+
+impl_trampoline_return_object!(Object);
+impl_trampoline_return_object!(Class);
@@ -5,26 +5,27 @@ use alloc::{
     string::{String, ToString},
 };
 use core::{
-    fmt::{Debug, Display, Formatter},
+    any::TypeId,
+    ffi::CStr,
+    fmt::{Debug, Display, Formatter, Write},
     marker::PhantomData,
     ptr::null_mut,
     sync::atomic::{AtomicPtr, Ordering},
 };
 
 use crate::{
-    builtin::Throwable,
-    context::{CallArg, CallResult, Context, GetReturn, Method, SetArg},
+    context::{CallArg, CallResult, Context, Field, GetReturn, Method, SetArg},
     reference::{Local, Ref, StrongRef},
     resolver,
-    sys::_jmethodID,
-    AsRaw, CallArgs, FromRaw, Global, IntoRaw, Raw, Trampoline, Weak, WeakRef,
+    sys::{_jfieldID, _jmethodID, _jobject},
+    AsRaw, CallArgs, Error, FromRaw, Global, IntoRaw, MonitorGuard, Raw, Trampoline, Weak, WeakRef,
 };
 
 mod __sealed {
     pub trait Sealed {}
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Signature {
     Void,
     Boolean,
@@ -51,9 +52,36 @@ impl Display for Signature {
             Signature::Long => f.write_str("J"),
             Signature::Float => f.write_str("F"),
             Signature::Double => f.write_str("D"),
-            Signature::Object(name) => f.write_fmt(format_args!("L{};", name)),
-            Signature::Array(inner) => f.write_fmt(format_args!("[{}", inner)),
+            Signature::Object(name) => {
+                f.write_char('L')?;
+                f.write_str(name)?;
+                f.write_char(';')
+            }
+            Signature::Array(inner) => {
+                f.write_char('[')?;
+                Display::fmt(inner, f)
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MethodSignature<'a> {
+    pub args: &'a [Signature],
+    pub ret: Signature,
+}
+
+impl Display for MethodSignature<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> alloc::fmt::Result {
+        f.write_str("(")?;
+
+        for arg in self.args {
+            Display::fmt(arg, f)?;
         }
+
+        f.write_str(")")?;
+
+        Display::fmt(&self.ret, f)
     }
 }
 
@@ -61,11 +89,15 @@ pub trait Type: Sized {
     const SIGNATURE: Signature;
 }
 
-pub trait ObjectType: Type {}
+pub trait ObjectType: Type {
+    const CLASS_NAME: Option<&'static CStr> = None;
+
+    const DESCRIPTOR: Option<&'static CStr> = None;
+}
 
 pub trait PrimitiveType: Type + __sealed::Sealed {}
 
-impl<'a, T: Type> Type for &'a T {
+impl<T: Type> Type for &T {
     const SIGNATURE: Signature = T::SIGNATURE;
 }
 
@@ -125,6 +157,58 @@ impl_value_for!(i64);
 impl_value_for!(f32);
 impl_value_for!(f64);
 
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Jchar(pub u16);
+
+impl Type for Jchar {
+    const SIGNATURE: Signature = Signature::Char;
+}
+
+impl Raw for Jchar {
+    type Raw = u16;
+}
+
+impl IntoRaw for Jchar {
+    fn into_raw(self) -> Self::Raw {
+        self.0
+    }
+}
+
+impl FromRaw for Jchar {
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self(raw)
+    }
+}
+
+#[derive(Debug)]
+pub struct CharConversionError;
+
+impl Display for CharConversionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("CharConversionError")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CharConversionError {}
+
+impl TryFrom<char> for Jchar {
+    type Error = CharConversionError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        u16::try_from(value as u32).map(Jchar).map_err(|_| CharConversionError)
+    }
+}
+
+impl TryFrom<Jchar> for char {
+    type Error = CharConversionError;
+
+    fn try_from(value: Jchar) -> Result<Self, Self::Error> {
+        char::from_u32(value.0 as u32).ok_or(CharConversionError)
+    }
+}
+
 #[repr(transparent)]
 pub struct Class<T: ObjectType, R: Ref> {
     typ: PhantomData<T>,
@@ -155,6 +239,19 @@ pub type TrampolineClass<'ctx, T> = Class<T, Trampoline<'ctx>>;
 pub type GlobalClass<T> = Class<T, Global>;
 pub type WeakClass<T> = Class<T, Weak>;
 
+/// A weak reference is upgraded for the call, a cleared one has no object to print.
+#[cfg(feature = "debug-to-string")]
+fn ref_to_string<R: Ref>(r: &R) -> String {
+    Context::with_attached(|ctx| unsafe {
+        let local = ctx.new_local_ref(*r.as_raw());
+        if local.is_null() {
+            return "<cleared>".to_string();
+        }
+
+        object_to_string(&Local::from_raw(local))
+    })
+}
+
 fn object_to_string<R: StrongRef>(r: &R) -> String {
     Context::with_attached(|ctx| {
         static M_TO_STRING: AtomicPtr<_jmethodID> = AtomicPtr::new(null_mut());
@@ -225,15 +322,6 @@ macro_rules! impl_common {
             }
         }
 
-        impl<T: ObjectType, R: Ref> FromRaw for $name<T, R> {
-            unsafe fn from_raw(raw: Self::Raw) -> Self {
-                Self {
-                    reference: raw,
-                    typ: PhantomData,
-                }
-            }
-        }
-
         impl<'a, T: ObjectType, R: Ref> Raw for &'a $name<T, R> {
             type Raw = &'a R;
         }
@@ -245,8 +333,8 @@ macro_rules! impl_common {
         }
 
         impl<T: ObjectType, R: StrongRef> $name<T, R> {
-            pub fn to_global(&self) -> $name<T, Global> {
-                unsafe { $name::from_raw(self.as_raw().to_global()) }
+            pub fn to_global(&self) -> Result<$name<T, Global>, Error<'static>> {
+                unsafe { Ok($name::from_raw(self.as_raw().to_global()?)) }
             }
 
             pub fn to_local<'ctx>(&self, ctx: &'ctx Context) -> $name<T, Local<'ctx>> {
@@ -268,6 +356,17 @@ macro_rules! impl_common {
             }
         }
 
+        impl<T: ObjectType, R: Ref> $name<T, R> {
+            /// Reference identity regardless of the reference kinds, see [`Context::is_same_object`].
+            pub fn is_same_object<O>(&self, ctx: &Context, other: &O) -> bool
+            where
+                O: AsRaw,
+                O::Raw: Ref,
+            {
+                ctx.is_same_object(Some(self.as_raw()), Some(other.as_raw()))
+            }
+        }
+
         impl<T: ObjectType, R: StrongRef> $name<T, R> {
             pub fn is_instance_of<NT: ObjectType, NR: StrongRef>(&self, ctx: &Context, class: &Class<NT, NR>) -> bool {
                 unsafe { ctx.is_instance_of(self.as_raw(), class.as_raw()) }
@@ -275,6 +374,16 @@ macro_rules! impl_common {
         }
 
         impl<T: ObjectType, R: StrongRef> $name<T, R> {
+            /// Enters the monitor of this object until the guard is dropped, see [`MonitorGuard`].
+            pub fn lock<'a>(&'a self, ctx: &'a Context) -> Result<MonitorGuard<'a>, Error<'a>> {
+                unsafe { MonitorGuard::enter(ctx, *self.as_raw().as_raw()) }
+            }
+        }
+
+        impl<T: ObjectType, R: StrongRef> $name<T, R> {
+            /// # Safety
+            ///
+            /// `class` must be the class of `NT`, the object is only checked against `class`.
             pub unsafe fn cast<'ctx, NT: ObjectType, NR: StrongRef>(
                 &self,
                 ctx: &'ctx Context,
@@ -288,9 +397,32 @@ macro_rules! impl_common {
             }
         }
 
-        impl<T: ObjectType, R: StrongRef> Debug for $name<T, R> {
+        /// The type descriptor and the raw handle, e.g. `Object<Ljava/lang/String;>(0x7f5c3c0058a0)`,
+        /// without calling into the vm. The `debug-to-string` feature appends `toString()`.
+        #[cfg(not(feature = "debug-to-string"))]
+        impl<T: ObjectType, R: Ref> Debug for $name<T, R> {
             fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-                f.write_str(&object_to_string(self.as_raw()))
+                write!(
+                    f,
+                    "{}<{}>({:p})",
+                    stringify!($name),
+                    T::SIGNATURE,
+                    *self.as_raw().as_raw()
+                )
+            }
+        }
+
+        #[cfg(feature = "debug-to-string")]
+        impl<T: ObjectType, R: Ref> Debug for $name<T, R> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                write!(
+                    f,
+                    "{}<{}>({:p}: {})",
+                    stringify!($name),
+                    T::SIGNATURE,
+                    *self.as_raw().as_raw(),
+                    ref_to_string(self.as_raw())
+                )
             }
         }
 
@@ -311,8 +443,60 @@ macro_rules! impl_common {
 impl_common!(Class);
 impl_common!(Object);
 
+impl<T: ObjectType, R: Ref> FromRaw for Class<T, R> {
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self {
+            reference: raw,
+            typ: PhantomData,
+        }
+    }
+}
+
+impl<T: ObjectType, R: Ref> FromRaw for Object<T, R> {
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        #[cfg(all(feature = "debug-typecheck", debug_assertions))]
+        assert_instance_of::<T>(*raw.as_raw());
+
+        Self {
+            reference: raw,
+            typ: PhantomData,
+        }
+    }
+}
+
+/// Panics if `object` is not an instance of `T`, which usually means a binding names the wrong
+/// class. Classes that `FindClass` cannot resolve from here, e.g. ones of an application class
+/// loader, are not checked.
+#[cfg(all(feature = "debug-typecheck", debug_assertions))]
+fn assert_instance_of<T: ObjectType>(object: *mut _jobject) {
+    if object.is_null() || T::SIGNATURE == crate::JObject::SIGNATURE {
+        return;
+    }
+
+    // nothing to check against on a detached thread
+    let _ = Context::with_current(|ctx| {
+        // FindClass is not allowed with an exception pending, e.g. while it is being fetched
+        if ctx.exception_check() {
+            return;
+        }
+
+        let Ok(class) = LocalClass::<T>::find_class(ctx) else {
+            return;
+        };
+
+        unsafe {
+            let object = Trampoline::from_raw(object);
+            if !ctx.is_instance_of(&object, class.as_raw()) {
+                let actual = LocalObject::<crate::JClass>::from_raw(ctx.get_object_class(&object)).get_name(ctx);
+
+                panic!("BROKEN: object of class {actual} used as {}", T::SIGNATURE);
+            }
+        }
+    });
+}
+
 impl<'ctx, T: ObjectType> Class<T, Local<'ctx>> {
-    pub fn find_class(ctx: &'ctx Context) -> Result<Self, LocalObject<'ctx, Throwable>> {
+    pub fn find_class(ctx: &'ctx Context) -> Result<Self, Error<'ctx>> {
         fn class_name_of(signature: &Signature) -> Cow<'static, str> {
             match signature {
                 Signature::Void => Cow::Borrowed("V"),
@@ -329,9 +513,24 @@ impl<'ctx, T: ObjectType> Class<T, Local<'ctx>> {
             }
         }
 
-        let class_name = class_name_of(&T::SIGNATURE);
+        let class_name = match T::CLASS_NAME {
+            Some(name) => Cow::Borrowed(name),
+            None => Cow::Owned(CString::new(class_name_of(&T::SIGNATURE).into_owned()).map_err(|_| Error::InvalidName)?),
+        };
 
-        let class_name = CString::new(class_name.into_owned()).unwrap();
+        // array classes are named by their descriptor
+        #[cfg(feature = "std")]
+        let class_name = match class_name.to_str() {
+            Ok(name) if name.starts_with('[') => match crate::class_cache::remap_descriptor(name) {
+                Some(remapped) => Cow::Owned(CString::new(remapped).map_err(|_| Error::InvalidName)?),
+                None => class_name,
+            },
+            Ok(name) => match crate::class_cache::remap_internal_name(name) {
+                Some(remapped) => Cow::Owned(CString::new(remapped).map_err(|_| Error::InvalidName)?),
+                None => class_name,
+            },
+            _ => class_name,
+        };
 
         ctx.find_class(&class_name).map(|r| Self {
             reference: r,
@@ -340,10 +539,199 @@ impl<'ctx, T: ObjectType> Class<T, Local<'ctx>> {
     }
 }
 
+/// Backs the `class` accessor generated by [`define_java_class!`](crate::define_java_class).
+#[doc(hidden)]
+pub struct __CachedClass(AtomicPtr<_jobject>);
+
+impl __CachedClass {
+    pub const fn new() -> Self {
+        Self(AtomicPtr::new(null_mut()))
+    }
+
+    pub fn get<'ctx, T: ObjectType>(&self, ctx: &'ctx Context) -> Result<LocalClass<'ctx, T>, Error<'ctx>> {
+        let raw = self.0.load(Ordering::Acquire);
+        if !raw.is_null() {
+            return unsafe { Ok(Class::from_raw(Local::from_raw(ctx.new_local_ref(raw)))) };
+        }
+
+        let class = LocalClass::<T>::find_class(ctx)?;
+
+        unsafe {
+            let global = ctx.new_global_ref(*class.as_raw().as_raw());
+
+            // out of memory, retried on the next call
+            if !global.is_null()
+                && self
+                    .0
+                    .compare_exchange(null_mut(), global, Ordering::AcqRel, Ordering::Acquire)
+                    .is_err()
+            {
+                ctx.delete_global_ref(global);
+            }
+        }
+
+        Ok(class)
+    }
+
+    /// # Safety
+    ///
+    /// Must not race with [`get`](Self::get), the global reference is deleted right away. Member ids
+    /// cached by [`java_bindings!`](crate::java_bindings) stay bound to the old class.
+    pub unsafe fn invalidate(&self) {
+        let raw = self.0.swap(null_mut(), Ordering::AcqRel);
+        if !raw.is_null() {
+            Context::with_attached(|ctx| unsafe { ctx.delete_global_ref(raw) })
+        }
+    }
+}
+
+impl Default for __CachedClass {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Backs the method accessors generated by [`java_bindings!`](crate::java_bindings). Method ids
+/// stay valid while the class is loaded, which the global reference of [`__CachedClass`] ensures.
+#[doc(hidden)]
+pub struct __CachedMethod<const STATIC: bool>(AtomicPtr<_jmethodID>);
+
+impl<const STATIC: bool> __CachedMethod<STATIC> {
+    pub const fn new() -> Self {
+        Self(AtomicPtr::new(null_mut()))
+    }
+
+    pub fn get<'ctx, 'a, C: StrongRef, A: Args<'a>, R: Type>(
+        &self,
+        ctx: &'ctx Context,
+        class: &C,
+        name: &'static str,
+    ) -> Result<Method<STATIC>, Error<'ctx>>
+    where
+        A::Array<Signature>: AsRef<[Signature]>,
+    {
+        let raw = self.0.load(Ordering::Acquire);
+        if !raw.is_null() {
+            return unsafe { Ok(Method::from_raw(raw)) };
+        }
+
+        let args = A::signatures();
+        let signature = MethodSignature {
+            args: args.as_ref(),
+            ret: R::SIGNATURE,
+        };
+
+        let method = resolver::with_member_cstrs(name, signature, |name, signature| ctx.find_method(class, name, signature))?;
+
+        self.0.store(*method.as_raw(), Ordering::Release);
+
+        Ok(method)
+    }
+}
+
+impl<const STATIC: bool> Default for __CachedMethod<STATIC> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Backs the field accessors generated by [`java_bindings!`](crate::java_bindings).
+#[doc(hidden)]
+pub struct __CachedField<const STATIC: bool>(AtomicPtr<_jfieldID>);
+
+impl<const STATIC: bool> __CachedField<STATIC> {
+    pub const fn new() -> Self {
+        Self(AtomicPtr::new(null_mut()))
+    }
+
+    pub fn get<'ctx, C: StrongRef, V: Type>(
+        &self,
+        ctx: &'ctx Context,
+        class: &C,
+        name: &'static str,
+    ) -> Result<Field<STATIC>, Error<'ctx>> {
+        let raw = self.0.load(Ordering::Acquire);
+        if !raw.is_null() {
+            return unsafe { Ok(Field::from_raw(raw)) };
+        }
+
+        let field = resolver::with_member_cstrs(name, V::SIGNATURE, |name, signature| ctx.find_field(class, name, signature))?;
+
+        self.0.store(*field.as_raw(), Ordering::Release);
+
+        Ok(field)
+    }
+}
+
+impl<const STATIC: bool> Default for __CachedField<STATIC> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: ObjectType, R: StrongRef> Class<T, R> {
     pub fn is_assignable_from<ST: ObjectType, SR: StrongRef>(&self, ctx: &Context, superclass: &Class<ST, SR>) -> bool {
         unsafe { ctx.is_assignable_from(self.as_raw(), superclass.as_raw()) }
     }
+
+    /// Resolves the method with the signature of `A` and `V` like [`call_method`](Self::call_method)
+    /// does, but bypasses the member cache, e.g. right after the class was redefined.
+    pub fn find_method_uncached<'ctx, 'a, const STATIC: bool, A, V>(
+        &self,
+        ctx: &'ctx Context,
+        name: &str,
+    ) -> Result<Method<STATIC>, Error<'ctx>>
+    where
+        A: Args<'a>,
+        A::Array<Signature>: AsRef<[Signature]>,
+        V: Type,
+    {
+        resolver::find_method_uncached::<STATIC, _, A, V>(ctx, self.as_raw(), name)
+    }
+
+    /// Resolves the field of type `V` bypassing the member cache.
+    pub fn find_field_uncached<'ctx, const STATIC: bool, V: Type>(
+        &self,
+        ctx: &'ctx Context,
+        name: &str,
+    ) -> Result<Field<STATIC>, Error<'ctx>> {
+        resolver::find_field_uncached::<STATIC, _, V>(ctx, self.as_raw(), name)
+    }
+
+    /// Resolves a method into the member cache ahead of its first call, so a missing method fails
+    /// at startup instead of on a hot path. `A` and `V` only need the signatures of the types later
+    /// passed to [`call_method`](Self::call_method), e.g. `Option<LocalObject<_>>` and
+    /// `GlobalObject<_>` share an entry. See [`cache::warm`](crate::cache::warm) for members named
+    /// by strings.
+    ///
+    /// The cache is per thread, so warming only helps calls on the current thread. Without the
+    /// `cache` feature the method is only checked.
+    pub fn warm_method<'ctx, 'a, const STATIC: bool, A, V>(
+        &self,
+        ctx: &'ctx Context,
+        name: &'static str,
+    ) -> Result<(), Error<'ctx>>
+    where
+        A: Args<'a>,
+        A::Array<Signature>: AsRef<[Signature]>,
+        V: Type,
+    {
+        resolver::find_method::<STATIC, _, A, V>(ctx, self.as_raw(), name)?;
+
+        Ok(())
+    }
+
+    /// Resolves a field into the member cache like [`warm_method`](Self::warm_method) does for
+    /// methods. `V` must be the type later read or written.
+    pub fn warm_field<'ctx, const STATIC: bool, V: Type>(
+        &self,
+        ctx: &'ctx Context,
+        name: &'static str,
+    ) -> Result<(), Error<'ctx>> {
+        resolver::find_field::<STATIC, _, V>(ctx, self.as_raw(), name)?;
+
+        Ok(())
+    }
 }
 
 pub trait Args<'a>: 'a {
@@ -365,7 +753,7 @@ fn call_method<'ctx, 't, 'a, const STATIC: bool, T, R, A>(
     this: &T,
     name: &'static str,
     args: A,
-) -> Result<R, LocalObject<'ctx, Throwable>>
+) -> Result<R, Error<'ctx>>
 where
     T: AsRaw,
     T::Raw: StrongRef,
@@ -386,16 +774,15 @@ where
 
     let raw_args = args.into_raw();
 
-    unsafe { ctx.call_method(this.as_raw(), method, raw_args).map(|v| R::from_raw(v)) }
+    unsafe { Ok(R::from_raw(ctx.call_method(this.as_raw(), method, raw_args)?)) }
 }
 
 impl<T: ObjectType, R: StrongRef> Object<T, R> {
-    pub fn call_method<'ctx, 'a, V, A>(
-        &self,
-        ctx: &'ctx Context,
-        name: &'static str,
-        args: A,
-    ) -> Result<V, LocalObject<'ctx, Throwable>>
+    /// `V` also picks the return type in the method signature: `()`, a primitive, or an object of
+    /// any [`ObjectType`], including [`define_java_class!`](crate::define_java_class) types. A
+    /// method that may return `null` needs `Option<LocalObject<_>>`, a plain `LocalObject<_>`
+    /// panics on `null`.
+    pub fn call_method<'ctx, 'a, V, A>(&self, ctx: &'ctx Context, name: &'static str, args: A) -> Result<V, Error<'ctx>>
     where
         V: Type,
         V: FromRaw,
@@ -409,12 +796,7 @@ impl<T: ObjectType, R: StrongRef> Object<T, R> {
 }
 
 impl<T: ObjectType, R: StrongRef> Class<T, R> {
-    pub fn call_method<'ctx, 'a, V, A>(
-        &self,
-        ctx: &'ctx Context,
-        name: &'static str,
-        args: A,
-    ) -> Result<V, LocalObject<'ctx, Throwable>>
+    pub fn call_method<'ctx, 'a, V, A>(&self, ctx: &'ctx Context, name: &'static str, args: A) -> Result<V, Error<'ctx>>
     where
         V: Type,
         V: FromRaw,
@@ -427,12 +809,79 @@ impl<T: ObjectType, R: StrongRef> Class<T, R> {
     }
 }
 
+impl<T: ObjectType, R: StrongRef> Object<T, R> {
+    /// `getClass()`, the runtime class of the object, which may be a subclass of `T` and is typed as
+    /// `Class<T>` so generic code keeps the relation to `T`.
+    pub fn get_class<'ctx>(&self, ctx: &'ctx Context) -> LocalClass<'ctx, T> {
+        unsafe { Class::from_raw(ctx.get_object_class(self.as_raw())) }
+    }
+}
+
+impl<T: ObjectType, R: StrongRef> Object<T, R> {
+    /// Calls an instance method resolved up front, e.g. with
+    /// [`find_method_uncached`](Class::find_method_uncached). Only a `Method<false>` is accepted,
+    /// a static method needs a class receiver:
+    ///
+    /// ```compile_fail
+    /// # use typed_jni::{Context, JString, LocalObject, Method, NoArgs};
+    /// # unsafe fn call(ctx: &Context, object: &LocalObject<JString>, method: Method<true>) {
+    /// let _: i32 = unsafe { object.call_method_id(ctx, method, NoArgs) }.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// `method` must be a method of the class of the object, and `A` and `V` must match its
+    /// signature.
+    pub unsafe fn call_method_id<'ctx, 'a, V, A>(
+        &self,
+        ctx: &'ctx Context,
+        method: Method<false>,
+        args: A,
+    ) -> Result<V, Error<'ctx>>
+    where
+        V: FromRaw,
+        V::Raw: CallResult<'ctx>,
+        A: Args<'a>,
+        A::Array<CallArg<'a>>: CallArgs,
+    {
+        unsafe { Ok(V::from_raw(ctx.call_method(self.as_raw(), method, args.into_raw())?)) }
+    }
+}
+
 impl<T: ObjectType, R: StrongRef> Class<T, R> {
-    pub fn new_object<'ctx, 'args, A>(
+    /// Calls a static method resolved up front. Only a `Method<true>` is accepted:
+    ///
+    /// ```compile_fail
+    /// # use typed_jni::{Context, JString, LocalClass, Method, NoArgs};
+    /// # unsafe fn call(ctx: &Context, class: &LocalClass<JString>, method: Method<false>) {
+    /// let _: i32 = unsafe { class.call_method_id(ctx, method, NoArgs) }.unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// `method` must be a static method of this class, and `A` and `V` must match its signature.
+    pub unsafe fn call_method_id<'ctx, 'a, V, A>(
         &self,
         ctx: &'ctx Context,
+        method: Method<true>,
         args: A,
-    ) -> Result<LocalObject<'ctx, T>, LocalObject<'ctx, Throwable>>
+    ) -> Result<V, Error<'ctx>>
+    where
+        V: FromRaw,
+        V::Raw: CallResult<'ctx>,
+        A: Args<'a>,
+        A::Array<CallArg<'a>>: CallArgs,
+    {
+        unsafe { Ok(V::from_raw(ctx.call_method(self.as_raw(), method, args.into_raw())?)) }
+    }
+}
+
+impl<T: ObjectType, R: StrongRef> Class<T, R> {
+    /// The constructor is looked up by the exact signature of `args`, so overloads are picked by
+    /// argument types and a missing one is a `NoSuchMethodError`.
+    pub fn new_object<'ctx, 'args, A>(&self, ctx: &'ctx Context, args: A) -> Result<LocalObject<'ctx, T>, Error<'ctx>>
     where
         A: Args<'args>,
         A::Array<Signature>: AsRef<[Signature]>,
@@ -442,15 +891,11 @@ impl<T: ObjectType, R: StrongRef> Class<T, R> {
         let method: Method<false> = resolver::find_method::<false, _, A, ()>(ctx, self.as_raw(), "<init>")?;
 
         let raw_args = args.into_raw();
-        unsafe { ctx.new_object(self.as_raw(), method, raw_args).map(|v| Object::from_raw(v)) }
+        unsafe { Ok(Object::from_raw(ctx.new_object(self.as_raw(), method, raw_args)?)) }
     }
 }
 
-fn get_field<'ctx, const STATIC: bool, T, R>(
-    ctx: &'ctx Context,
-    this: &T,
-    name: &'static str,
-) -> Result<R, LocalObject<'ctx, Throwable>>
+fn get_field<'ctx, const STATIC: bool, T, R>(ctx: &'ctx Context, this: &T, name: &'static str) -> Result<R, Error<'ctx>>
 where
     T: AsRaw,
     T::Raw: StrongRef,
@@ -469,7 +914,11 @@ where
 }
 
 impl<T: ObjectType, R: StrongRef> Object<T, R> {
-    pub fn get_field<'ctx, V>(&self, ctx: &'ctx Context, name: &'static str) -> Result<V, LocalObject<'ctx, Throwable>>
+    /// A plain load through `GetXField`, even if the Java field is declared `volatile`. JNI field
+    /// access has no `volatile` semantics, so a value written by another thread may not be visible
+    /// yet or be seen out of order with its other writes. Read state shared across threads through
+    /// a Java method instead, e.g. a getter or an `AtomicLong`.
+    pub fn get_field<'ctx, V>(&self, ctx: &'ctx Context, name: &'static str) -> Result<V, Error<'ctx>>
     where
         V: FromRaw + Type,
         V::Raw: GetReturn<'ctx>,
@@ -479,13 +928,26 @@ impl<T: ObjectType, R: StrongRef> Object<T, R> {
 }
 
 impl<T: ObjectType, R: StrongRef> Class<T, R> {
-    pub fn get_field<'ctx, V>(&self, ctx: &'ctx Context, name: &'static str) -> Result<V, LocalObject<'ctx, Throwable>>
+    /// A plain load through `GetXField`, even if the Java field is declared `volatile`. JNI field
+    /// access has no `volatile` semantics, so a value written by another thread may not be visible
+    /// yet or be seen out of order with its other writes. Read state shared across threads through
+    /// a Java method instead, e.g. a getter or an `AtomicLong`.
+    pub fn get_field<'ctx, V>(&self, ctx: &'ctx Context, name: &'static str) -> Result<V, Error<'ctx>>
     where
         V: FromRaw + Type,
         V::Raw: GetReturn<'ctx>,
     {
         get_field::<true, _, _>(ctx, self, name)
     }
+
+    /// An enum constant is a static field of the enum type itself, so `name` is the constant as
+    /// declared in Java. A missing one is a `NoSuchFieldError`.
+    pub fn get_enum_constant<'ctx>(&self, ctx: &'ctx Context, name: &'static str) -> Result<LocalObject<'ctx, T>, Error<'ctx>>
+    where
+        LocalObject<'ctx, T>: Raw<Raw = Local<'ctx>> + FromRaw,
+    {
+        self.get_field(ctx, name)
+    }
 }
 
 fn set_field<'ctx, const STATIC: bool, T, V>(
@@ -493,7 +955,7 @@ fn set_field<'ctx, const STATIC: bool, T, V>(
     this: &T,
     name: &'static str,
     value: V,
-) -> Result<(), LocalObject<'ctx, Throwable>>
+) -> Result<(), Error<'ctx>>
 where
     T: AsRaw,
     T::Raw: StrongRef,
@@ -508,11 +970,17 @@ where
         resolver::find_field::<STATIC, _, V>(ctx, &class, name)?
     };
 
-    unsafe { Ok(ctx.set_field(this.as_raw(), field, value.into_raw())) }
+    unsafe { ctx.set_field(this.as_raw(), field, value.into_raw()) };
+
+    Ok(())
 }
 
 impl<T: ObjectType, R: StrongRef> Object<T, R> {
-    pub fn set_field<'ctx, V>(&self, ctx: &'ctx Context, name: &'static str, value: V) -> Result<(), LocalObject<'ctx, Throwable>>
+    /// A plain store through `SetXField`, even if the Java field is declared `volatile`. JNI field
+    /// access has no `volatile` semantics, so other threads may not see the value yet or see it out
+    /// of order with other writes. Publish state shared across threads through a Java method
+    /// instead, e.g. a setter or an `AtomicLong`.
+    pub fn set_field<'ctx, V>(&self, ctx: &'ctx Context, name: &'static str, value: V) -> Result<(), Error<'ctx>>
     where
         V: IntoRaw + Type,
         V::Raw: SetArg,
@@ -522,7 +990,11 @@ impl<T: ObjectType, R: StrongRef> Object<T, R> {
 }
 
 impl<T: ObjectType, R: StrongRef> Class<T, R> {
-    pub fn set_field<'ctx, V>(&self, ctx: &'ctx Context, name: &'static str, value: V) -> Result<(), LocalObject<'ctx, Throwable>>
+    /// A plain store through `SetXField`, even if the Java field is declared `volatile`. JNI field
+    /// access has no `volatile` semantics, so other threads may not see the value yet or see it out
+    /// of order with other writes. Publish state shared across threads through a Java method
+    /// instead, e.g. a setter or an `AtomicLong`.
+    pub fn set_field<'ctx, V>(&self, ctx: &'ctx Context, name: &'static str, value: V) -> Result<(), Error<'ctx>>
     where
         V: IntoRaw + Type,
         V::Raw: SetArg,
@@ -530,3 +1002,210 @@ impl<T: ObjectType, R: StrongRef> Class<T, R> {
         set_field::<true, _, _>(ctx, self, name, value)
     }
 }
+
+pub trait FieldLayout {
+    fn read_fields<'ctx, R: StrongRef>(&mut self, fields: &mut FieldReader<'_, 'ctx, R>) -> Result<(), Error<'ctx>>;
+}
+
+pub struct FieldReader<'r, 'ctx, R: StrongRef> {
+    ctx: &'ctx Context,
+    this: &'r R,
+    class: &'r Local<'ctx>,
+    fields: &'r mut resolver::FieldSlots,
+    index: usize,
+}
+
+impl<'ctx, R: StrongRef> FieldReader<'_, 'ctx, R> {
+    pub fn read<V>(&mut self, name: &'static str) -> Result<V, Error<'ctx>>
+    where
+        V: FromRaw + Type,
+        V::Raw: GetReturn<'ctx>,
+    {
+        let field: Field<false> = match self.fields.get(self.index) {
+            Some(&(n, t, id)) if n.as_ptr() == name.as_ptr() && n.len() == name.len() && t == V::SIGNATURE => unsafe {
+                Field::from_raw(id as _)
+            },
+            _ => {
+                let field = resolver::with_member_cstrs(name, V::SIGNATURE, |name, signature| {
+                    self.ctx.find_field(self.class, name, signature)
+                })?;
+
+                self.fields.truncate(self.index);
+                self.fields.push((name, V::SIGNATURE, field.into_raw() as _));
+
+                field
+            }
+        };
+
+        self.index += 1;
+
+        unsafe { Ok(V::from_raw(self.ctx.get_field(self.this, field))) }
+    }
+
+    /// Reads the field and converts it right away, e.g. a `JString` into a `String`. The local
+    /// reference of an object field is deleted when `convert` returns, so reading a wide object
+    /// takes a constant number of local references instead of one per field.
+    pub fn read_with<V, O>(&mut self, name: &'static str, convert: impl FnOnce(&'ctx Context, V) -> O) -> Result<O, Error<'ctx>>
+    where
+        V: FromRaw + Type,
+        V::Raw: GetReturn<'ctx>,
+    {
+        let value = self.read(name)?;
+
+        Ok(convert(self.ctx, value))
+    }
+}
+
+impl<T: ObjectType, R: StrongRef> Object<T, R> {
+    pub fn read_fields_into<'ctx, S: FieldLayout + 'static>(&self, ctx: &'ctx Context, out: &mut S) -> Result<(), Error<'ctx>> {
+        let class = ctx.get_object_class(self.as_raw());
+
+        resolver::with_field_layout(ctx, &class, TypeId::of::<S>(), |fields| {
+            let mut reader = FieldReader {
+                ctx,
+                this: self.as_raw(),
+                class: &class,
+                fields,
+                index: 0,
+            };
+
+            out.read_fields(&mut reader)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{Jchar, MethodSignature, Signature};
+
+    #[test]
+    fn test_jchar_conversion() {
+        assert_eq!(Jchar::try_from('a').unwrap(), Jchar(0x61));
+        assert_eq!(Jchar::try_from('你').unwrap(), Jchar(0x4F60));
+        assert!(Jchar::try_from('😀').is_err());
+
+        assert_eq!(char::try_from(Jchar(0x61)).unwrap(), 'a');
+        assert_eq!(char::try_from(Jchar(0xFFFF)).unwrap(), '\u{FFFF}');
+        assert!(char::try_from(Jchar(0xD83D)).is_err());
+        assert!(char::try_from(Jchar(0xDE00)).is_err());
+    }
+
+    #[test]
+    fn test_signature_display_writes() {
+        use core::fmt::Write;
+
+        #[derive(Default)]
+        struct Writes(Vec<String>);
+
+        impl Write for Writes {
+            fn write_str(&mut self, s: &str) -> core::fmt::Result {
+                self.0.push(s.to_string());
+
+                Ok(())
+            }
+        }
+
+        let writes_of = |signature: Signature| {
+            let mut writes = Writes::default();
+            write!(writes, "{}", signature).unwrap();
+
+            writes.0
+        };
+
+        assert_eq!(writes_of(Signature::Int), ["I"]);
+        assert_eq!(writes_of(Signature::Void), ["V"]);
+        assert_eq!(
+            writes_of(Signature::Object("java/lang/String")),
+            ["L", "java/lang/String", ";"]
+        );
+        assert_eq!(
+            writes_of(Signature::Array(&Signature::Array(&Signature::Long))),
+            ["[", "[", "J"]
+        );
+    }
+
+    #[test]
+    fn test_signature_equality() {
+        const STRING: Signature = Signature::Object("java/lang/String");
+        const STRINGS: Signature = Signature::Array(&STRING);
+
+        assert_eq!(Signature::Array(&Signature::Object("java/lang/String")), STRINGS);
+        assert_ne!(Signature::Array(&Signature::Object("java/lang/Object")), STRINGS);
+        assert_ne!(Signature::Array(&Signature::Int), Signature::Int);
+
+        let set = HashSet::from([
+            MethodSignature {
+                args: &[STRING, Signature::Int],
+                ret: Signature::Void,
+            },
+            MethodSignature {
+                args: &[STRINGS],
+                ret: Signature::Void,
+            },
+        ]);
+
+        assert!(set.contains(&MethodSignature {
+            args: &[Signature::Object("java/lang/String"), Signature::Int],
+            ret: Signature::Void
+        }));
+        assert!(!set.contains(&MethodSignature {
+            args: &[STRING],
+            ret: Signature::Void
+        }));
+
+        assert_eq!(
+            MethodSignature {
+                args: &[STRINGS, Signature::Long],
+                ret: STRING
+            }
+            .to_string(),
+            "([Ljava/lang/String;J)Ljava/lang/String;"
+        );
+    }
+
+    #[test]
+    fn test_cached_class_skips_null_global() {
+        use core::{
+            ffi::c_char,
+            ptr::null_mut,
+            sync::atomic::{AtomicUsize, Ordering},
+        };
+
+        use crate::{
+            mock::{dangling_local, MockEnv},
+            sys::{jclass, jobject, JNIEnv},
+            typed::__CachedClass,
+            IntoRaw, JObject,
+        };
+
+        static FIND_CLASS_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        unsafe extern "C" fn find_class(_: *mut JNIEnv, _: *const c_char) -> jclass {
+            FIND_CLASS_CALLS.fetch_add(1, Ordering::Relaxed);
+
+            dangling_local().into_raw()
+        }
+
+        // the vm is out of memory for global references
+        unsafe extern "C" fn new_global_ref(_: *mut JNIEnv, _: jobject) -> jobject {
+            null_mut()
+        }
+
+        let env = MockEnv::new(|functions| {
+            functions.FindClass = Some(find_class);
+            functions.NewGlobalRef = Some(new_global_ref);
+        });
+        let ctx = env.ctx();
+
+        let cached = __CachedClass::new();
+        for calls in 1..=2 {
+            // dropping a local reference needs an attached vm
+            core::mem::forget(cached.get::<JObject>(ctx).unwrap());
+
+            assert!(cached.0.load(Ordering::Acquire).is_null());
+            assert_eq!(FIND_CLASS_CALLS.load(Ordering::Relaxed), calls);
+        }
+    }
+}
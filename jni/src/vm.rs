@@ -1,5 +1,6 @@
 use core::{
-    ptr::null_mut,
+    fmt::{Display, Formatter},
+    ptr::{null_mut, NonNull},
     sync::atomic::{AtomicPtr, Ordering},
 };
 
@@ -7,8 +8,22 @@ use crate::sys::JavaVM;
 
 static VM: AtomicPtr<JavaVM> = AtomicPtr::new(null_mut());
 
-pub fn attach_vm(vm: *mut JavaVM) {
-    VM.store(vm, Ordering::Relaxed);
+/// Stores the process-wide `JavaVM`, usually from `JNI_OnLoad`.
+///
+/// Attaching the vm already attached is a no-op, e.g. when the library is loaded by a second
+/// class loader. A different vm while one is attached is refused with `JNI_EEXIST` and leaves the
+/// attached one in place; a vm can only be replaced after [`Vm::destroy`].
+pub fn attach_vm(vm: *mut JavaVM) -> Result<(), VmError> {
+    match VM.compare_exchange(null_mut(), vm, Ordering::Relaxed, Ordering::Relaxed) {
+        Ok(_) => Ok(()),
+        Err(attached) if attached == vm => Ok(()),
+        Err(_) => Err(VmError(crate::sys::JNI_EEXIST)),
+    }
+}
+
+/// False before [`attach_vm`] and after [`Vm::destroy`].
+pub(crate) fn is_vm_available() -> bool {
+    !VM.load(Ordering::Relaxed).is_null()
 }
 
 pub fn require_vm() -> *mut JavaVM {
@@ -18,3 +33,104 @@ pub fn require_vm() -> *mut JavaVM {
     }
     vm
 }
+
+/// A `JavaVM` created from Rust. It stays on the thread that created it, which is attached to it
+/// until [`destroy`](Self::destroy).
+pub struct Vm {
+    raw: NonNull<JavaVM>,
+    env: NonNull<crate::sys::JNIEnv>,
+}
+
+impl Vm {
+    pub fn as_raw(&self) -> *mut JavaVM {
+        self.raw.as_ptr()
+    }
+
+    /// The `Context` of the creating thread, valid as long as the vm is borrowed.
+    pub fn context(&self) -> &crate::Context {
+        unsafe { crate::Context::from_raw(self.env.as_ptr()) }
+    }
+
+    /// Blocks until every other non-daemon thread has exited, then unloads the VM.
+    ///
+    /// Call it from the thread that created the VM, with no references, `Global` or `Weak`
+    /// included, left to drop afterwards. A destroyed VM cannot be created again in the same
+    /// process.
+    pub fn destroy(self) -> Result<(), VmError> {
+        let vm = self.raw.as_ptr();
+
+        let ret = unsafe { (**vm).DestroyJavaVM.unwrap()(vm) };
+
+        let _ = VM.compare_exchange(vm, null_mut(), Ordering::Relaxed, Ordering::Relaxed);
+
+        if ret == crate::sys::JNI_OK {
+            Ok(())
+        } else {
+            Err(VmError(ret))
+        }
+    }
+}
+
+/// A `JNI_*` error code returned by the invocation API.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VmError(pub i32);
+
+impl Display for VmError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("VmError({})", self.0))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VmError {}
+
+/// Creates a VM with `options` (`-Djava.class.path=...`, `-Xmx64m`, ...) and attaches it like
+/// [`attach_vm`]. The current thread uses it through [`Vm::context`].
+///
+/// `JNI_CreateJavaVM` is not resolved at runtime, the final binary has to link `libjvm`.
+#[cfg(feature = "invocation")]
+pub fn create_java_vm(options: &[&str]) -> Result<Vm, VmError> {
+    use alloc::vec::Vec;
+
+    use crate::{
+        context::to_modified_utf8,
+        sys::{JNIEnv, JNI_CreateJavaVM, JavaVMInitArgs, JavaVMOption, JNI_FALSE, JNI_OK, JNI_VERSION_1_8},
+    };
+
+    let strings = options.iter().map(|o| to_modified_utf8(o)).collect::<Vec<_>>();
+    let mut options = strings
+        .iter()
+        .map(|s| JavaVMOption {
+            optionString: s.as_ptr() as _,
+            extraInfo: null_mut(),
+        })
+        .collect::<Vec<_>>();
+
+    let mut args = JavaVMInitArgs {
+        version: JNI_VERSION_1_8 as _,
+        nOptions: options.len() as _,
+        options: options.as_mut_ptr(),
+        ignoreUnrecognized: JNI_FALSE as _,
+    };
+
+    unsafe {
+        let mut vm: *mut JavaVM = null_mut();
+        let mut env: *mut JNIEnv = null_mut();
+
+        let ret = JNI_CreateJavaVM(
+            &mut vm,
+            (&mut env as *mut *mut JNIEnv).cast(),
+            (&mut args as *mut JavaVMInitArgs).cast(),
+        );
+        if ret != JNI_OK {
+            return Err(VmError(ret));
+        }
+
+        attach_vm(vm)?;
+
+        Ok(Vm {
+            raw: NonNull::new(vm).unwrap(),
+            env: NonNull::new(env).unwrap(),
+        })
+    }
+}